@@ -0,0 +1,233 @@
+//! Age-depth models for sediment and ice cores.  A model is built from dated horizons ("tie
+//! points") and interpolates an age, with an uncertainty, for any depth between them.
+use crate::interp::Curve;
+use crate::{Error, Geotime, Result, UncertainGeotime};
+
+/// How ages are interpolated between tie points.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interpolation {
+    /// Straight lines between tie points, i.e., a constant sedimentation rate within each interval.
+    Linear,
+    /// A natural cubic spline through the tie points.  The curve is smooth but may imply age
+    /// reversals between closely spaced tie points.
+    Spline,
+    /// A monotone cubic (Fritsch-Carlson) curve.  Tie points must not contain age reversals, and
+    /// the model is guaranteed to get older with depth.
+    Monotone,
+}
+
+/// A dated horizon in a core.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TiePoint {
+    pub depth: f64,
+    pub age: UncertainGeotime,
+}
+
+impl TiePoint {
+    pub fn new(depth: f64, age: UncertainGeotime) -> Self {
+        Self { depth, age }
+    }
+}
+
+/// An interpolated age-depth model.  Depths are in whatever unit the tie points use, and
+/// sedimentation rates are reported in that unit per year.
+///
+/// ```
+/// use geotime::{AgeDepthModel, Interpolation, TiePoint, UncertainGeotime};
+///
+/// let model = AgeDepthModel::new(
+///     &[
+///         TiePoint::new(0.0, UncertainGeotime::from_years_bp(0.0, 0.0)),
+///         TiePoint::new(2.0, UncertainGeotime::from_years_bp(4000.0, 40.0)),
+///         TiePoint::new(5.0, UncertainGeotime::from_years_bp(10000.0, 100.0)),
+///     ],
+///     Interpolation::Linear,
+/// )
+/// .unwrap();
+///
+/// let age = model.age_at(1.0).unwrap();
+/// assert_eq!(age.to_years_bp().round(), 2000.0);
+/// assert_eq!(age.sigma_years().round(), 20.0);
+/// assert!((model.depth_at(age.time()).unwrap() - 1.0).abs() < 1e-9);
+/// assert!((model.sedimentation_rate(3.0).unwrap() - 0.0005).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AgeDepthModel {
+    ties: Vec<TiePoint>,
+    interpolation: Interpolation,
+    ages: Curve,
+    sigmas: Curve,
+}
+
+impl AgeDepthModel {
+    pub fn new(ties: &[TiePoint], interpolation: Interpolation) -> Result<Self> {
+        let mut ties = ties.to_vec();
+        if ties.len() < 2 {
+            return Err(Error::InvalidInput(
+                "an age-depth model needs at least two tie points".to_string(),
+            ));
+        }
+        if ties.iter().any(|t| !t.depth.is_finite()) {
+            return Err(Error::InvalidInput(
+                "tie point depths must be finite".to_string(),
+            ));
+        }
+
+        ties.sort_by(|a, b| a.depth.total_cmp(&b.depth));
+        for pair in ties.windows(2) {
+            if pair[0].depth == pair[1].depth {
+                return Err(Error::InvalidInput(format!(
+                    "more than one tie point at depth {}",
+                    pair[0].depth
+                )));
+            }
+            if interpolation == Interpolation::Monotone && pair[1].age.time() > pair[0].age.time() {
+                return Err(Error::InvalidInput(format!(
+                    "age reversal between depths {} and {}",
+                    pair[0].depth, pair[1].depth
+                )));
+            }
+        }
+
+        let depths: Vec<f64> = ties.iter().map(|t| t.depth).collect();
+        let years: Vec<f64> = ties.iter().map(|t| t.age.to_years_bp()).collect();
+        let sigmas: Vec<f64> = ties.iter().map(|t| t.age.sigma_years()).collect();
+
+        let ages = match interpolation {
+            Interpolation::Linear => Curve::linear(&depths, &years),
+            Interpolation::Spline => Curve::natural_spline(&depths, &years),
+            Interpolation::Monotone => Curve::monotone(&depths, &years),
+        };
+
+        Ok(Self {
+            ties,
+            interpolation,
+            ages,
+            sigmas: Curve::linear(&depths, &sigmas),
+        })
+    }
+
+    /// The tie points, sorted by depth.
+    pub fn ties(&self) -> &[TiePoint] {
+        &self.ties
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    fn out_of_range(&self, depth: f64) -> Error {
+        Error::OutOfRange(format!(
+            "depth {} is outside of the model ({} to {})",
+            depth,
+            self.ages.min_x(),
+            self.ages.max_x()
+        ))
+    }
+
+    /// The modelled age at `depth`.  The uncertainty is interpolated linearly between the
+    /// uncertainties of the neighbouring tie points.
+    pub fn age_at(&self, depth: f64) -> Result<UncertainGeotime> {
+        let years = self
+            .ages
+            .eval(depth)
+            .ok_or_else(|| self.out_of_range(depth))?;
+        let sigma = self
+            .sigmas
+            .eval(depth)
+            .ok_or_else(|| self.out_of_range(depth))?;
+        UncertainGeotime::try_from_years_bp(years, sigma)
+    }
+
+    /// The shallowest depth at which the model reaches `age`.
+    pub fn depth_at(&self, age: Geotime) -> Result<f64> {
+        self.ages
+            .solve(age.to_years_bp())
+            .ok_or_else(|| Error::OutOfRange(format!("{:?} is not reached by the model", age)))
+    }
+
+    /// Sedimentation (or accumulation) rate at `depth`, in depth units per year.
+    pub fn sedimentation_rate(&self, depth: f64) -> Result<f64> {
+        let years_per_unit = self
+            .ages
+            .derivative(depth)
+            .ok_or_else(|| self.out_of_range(depth))?;
+        Ok(1.0 / years_per_unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ties() -> Vec<TiePoint> {
+        vec![
+            TiePoint::new(3.0, UncertainGeotime::from_ma(1.2, 0.02)),
+            TiePoint::new(0.5, UncertainGeotime::from_ma(0.1, 0.005)),
+            TiePoint::new(1.5, UncertainGeotime::from_ma(0.4, 0.01)),
+            TiePoint::new(6.0, UncertainGeotime::from_ma(2.0, 0.04)),
+        ]
+    }
+
+    #[test]
+    fn sorts_ties() {
+        let model = AgeDepthModel::new(&ties(), Interpolation::Linear).unwrap();
+        let depths: Vec<f64> = model.ties().iter().map(|t| t.depth).collect();
+        assert_eq!(depths, vec![0.5, 1.5, 3.0, 6.0]);
+    }
+
+    #[test]
+    fn ties_are_reproduced() {
+        for method in [
+            Interpolation::Linear,
+            Interpolation::Spline,
+            Interpolation::Monotone,
+        ] {
+            let model = AgeDepthModel::new(&ties(), method).unwrap();
+            for tie in model.ties() {
+                let age = model.age_at(tie.depth).unwrap();
+                assert!((age.to_ma() - tie.age.to_ma()).abs() < 1e-9);
+                assert!((model.depth_at(tie.age.time()).unwrap() - tie.depth).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn monotone_model_gets_older_with_depth() {
+        let model = AgeDepthModel::new(&ties(), Interpolation::Monotone).unwrap();
+        let mut last = model.age_at(0.5).unwrap().time();
+        for i in 1..=550 {
+            let age = model.age_at(0.5 + i as f64 / 100.0).unwrap().time();
+            assert!(age <= last);
+            last = age;
+        }
+        assert!(model.sedimentation_rate(2.0).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn age_reversals() {
+        let mut ties = ties();
+        ties.push(TiePoint::new(7.0, UncertainGeotime::from_ma(1.9, 0.04)));
+        assert!(AgeDepthModel::new(&ties, Interpolation::Monotone).is_err());
+        assert!(AgeDepthModel::new(&ties, Interpolation::Linear).is_ok());
+    }
+
+    #[test]
+    fn invalid_models() {
+        let ties = ties();
+        assert!(AgeDepthModel::new(&ties[0..1], Interpolation::Linear).is_err());
+
+        let mut ties = ties.clone();
+        ties.push(TiePoint::new(3.0, UncertainGeotime::from_ma(1.3, 0.02)));
+        assert!(AgeDepthModel::new(&ties, Interpolation::Linear).is_err());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let model = AgeDepthModel::new(&ties(), Interpolation::Spline).unwrap();
+        assert!(model.age_at(0.0).is_err());
+        assert!(model.age_at(6.5).is_err());
+        assert!(model.sedimentation_rate(10.0).is_err());
+        assert!(model.depth_at(Geotime::from_ma(3.0)).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Low-precision positions of the Sun and Moon, after Meeus, *Astronomical Algorithms* (1998).
+//!
+//! Times are Julian Dates in Universal Time.  The algorithms work in Terrestrial Time, and the
+//! difference is bridged with the ΔT polynomials of Espenak and Meeus.
+use std::f64::consts::PI;
+
+use crate::{DeltaT, Geotime, MILLISECONDS_IN_DAY};
+
+const J2000: f64 = 2451545.0;
+const DAYS_PER_CENTURY: f64 = 36525.0;
+pub(crate) const MEAN_SYNODIC_MONTH: f64 = 29.530588861;
+pub(crate) const MEAN_TROPICAL_YEAR: f64 = 365.242189;
+
+fn sin(degrees: f64) -> f64 {
+    (degrees * PI / 180.0).sin()
+}
+
+/// TT - UT in days.
+pub(crate) fn delta_t(jd: f64) -> f64 {
+    let millis = (jd - 2440587.5) * MILLISECONDS_IN_DAY as f64;
+    DeltaT::EspenakMeeus
+        .seconds(Geotime::from(millis as i128))
+        .value
+        / 86400.0
+}
+
+/// The apparent ecliptic longitude of the Sun in degrees, in [0, 360), accurate to about 0.01°.
+pub(crate) fn solar_longitude(jd: f64) -> f64 {
+    let t = (jd + delta_t(jd) - J2000) / DAYS_PER_CENTURY;
+    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+    let m = 357.52911 + 35999.05029 * t - 0.0001537 * t * t;
+    let c = (1.914602 - 0.004817 * t - 0.000014 * t * t) * sin(m)
+        + (0.019993 - 0.000101 * t) * sin(2.0 * m)
+        + 0.000289 * sin(3.0 * m);
+    let omega = 125.04 - 1934.136 * t;
+    (l0 + c - 0.00569 - 0.00478 * sin(omega)).rem_euclid(360.0)
+}
+
+/// The last moment before `jd` at which the solar longitude was `longitude` degrees.
+pub(crate) fn solar_longitude_before(longitude: f64, jd: f64) -> f64 {
+    let rate = MEAN_TROPICAL_YEAR / 360.0;
+    let mut t = jd - rate * (solar_longitude(jd) - longitude).rem_euclid(360.0);
+    for _ in 0..4 {
+        let delta = (solar_longitude(t) - longitude + 180.0).rem_euclid(360.0) - 180.0;
+        t -= rate * delta;
+    }
+    match t < jd {
+        true => t,
+        false => t - MEAN_TROPICAL_YEAR,
+    }
+}
+
+// The time of the `k`th new moon after that of 6 January 2000, in Terrestrial Time.
+fn nth_new_moon(k: f64) -> f64 {
+    let t = k / 1236.85;
+    let (t2, t3, t4) = (t * t, t * t * t, t * t * t * t);
+    let jde = 2451550.09766 + MEAN_SYNODIC_MONTH * k + 0.00015437 * t2 - 0.000000150 * t3
+        + 0.00000000073 * t4;
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let m = 2.5534 + 29.10535670 * k - 0.0000014 * t2 - 0.00000011 * t3;
+    let mp = 201.5643 + 385.81693528 * k + 0.0107582 * t2 + 0.00001238 * t3 - 0.000000058 * t4;
+    let f = 160.7108 + 390.67050284 * k - 0.0016118 * t2 - 0.00000227 * t3 + 0.000000011 * t4;
+    let omega = 124.7746 - 1.56375588 * k + 0.0020672 * t2 + 0.00000215 * t3;
+
+    #[rustfmt::skip]
+    let periodic = -0.40720 * sin(mp)
+        + 0.17241 * e * sin(m)
+        + 0.01608 * sin(2.0 * mp)
+        + 0.01039 * sin(2.0 * f)
+        + 0.00739 * e * sin(mp - m)
+        - 0.00514 * e * sin(mp + m)
+        + 0.00208 * e * e * sin(2.0 * m)
+        - 0.00111 * sin(mp - 2.0 * f)
+        - 0.00057 * sin(mp + 2.0 * f)
+        + 0.00056 * e * sin(2.0 * mp + m)
+        - 0.00042 * sin(3.0 * mp)
+        + 0.00042 * e * sin(m + 2.0 * f)
+        + 0.00038 * e * sin(m - 2.0 * f)
+        - 0.00024 * e * sin(2.0 * mp - m)
+        - 0.00017 * sin(omega)
+        - 0.00007 * sin(mp + 2.0 * m)
+        + 0.00004 * sin(2.0 * mp - 2.0 * f)
+        + 0.00004 * sin(3.0 * m)
+        + 0.00003 * sin(mp + m - 2.0 * f)
+        + 0.00003 * sin(2.0 * mp + 2.0 * f)
+        - 0.00003 * sin(mp + m + 2.0 * f)
+        + 0.00003 * sin(mp - m + 2.0 * f)
+        - 0.00002 * sin(mp - m - 2.0 * f)
+        - 0.00002 * sin(3.0 * mp + m)
+        + 0.00002 * sin(4.0 * mp);
+
+    // Planetary perturbations: coefficient, and argument at k = 0 and its rate.
+    #[rustfmt::skip]
+    const PLANETARY: [(f64, f64, f64); 14] = [
+        (0.000325, 299.77, 0.107408), (0.000165, 251.88, 0.016321),
+        (0.000164, 251.83, 26.651886), (0.000126, 349.42, 36.412478),
+        (0.000110, 84.66, 18.206239), (0.000062, 141.74, 53.303771),
+        (0.000060, 207.14, 2.453732), (0.000056, 154.84, 7.306860),
+        (0.000047, 34.52, 27.261239), (0.000042, 207.19, 0.121824),
+        (0.000040, 291.34, 1.844379), (0.000037, 161.72, 24.198154),
+        (0.000035, 239.56, 25.513099), (0.000023, 331.55, 3.592518),
+    ];
+    let planetary: f64 = PLANETARY
+        .iter()
+        .enumerate()
+        .map(|(i, (coefficient, a, rate))| {
+            let a = a + rate * k - if i == 0 { 0.009173 * t2 } else { 0.0 };
+            coefficient * sin(a)
+        })
+        .sum();
+
+    jde + periodic + planetary
+}
+
+// The time of the `k`th new moon, in Universal Time.
+fn new_moon(k: f64) -> f64 {
+    let jde = nth_new_moon(k);
+    jde - delta_t(jde)
+}
+
+/// The first new moon at or after `jd`.
+pub(crate) fn new_moon_at_or_after(jd: f64) -> f64 {
+    let mut k = ((jd - 2451550.09766) / MEAN_SYNODIC_MONTH).floor() - 1.0;
+    while new_moon(k) < jd {
+        k += 1.0;
+    }
+    new_moon(k)
+}
+
+/// The last new moon before `jd`.
+pub(crate) fn new_moon_before(jd: f64) -> f64 {
+    let mut k = ((jd - 2451550.09766) / MEAN_SYNODIC_MONTH).ceil() + 1.0;
+    while new_moon(k) >= jd {
+        k -= 1.0;
+    }
+    new_moon(k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sun() {
+        // Meeus, example 25.a: 1992 October 13.0 TD, apparent longitude 199.90895°.
+        let jd = 2448908.5;
+        assert!((solar_longitude(jd - delta_t(jd)) - 199.909).abs() < 0.01);
+
+        // The March equinox of 2000 was at 07:35 UT on 20 March.
+        let equinox = solar_longitude_before(0.0, 2451630.0);
+        assert!((equinox - 2451623.816).abs() < 0.01, "{}", equinox);
+    }
+
+    #[test]
+    fn moon() {
+        // Meeus, example 49.a: the new moon of 1977 February 18, at 3:37:42 TD.
+        let jde = nth_new_moon(-283.0);
+        assert!((jde - 2443192.65118).abs() < 0.00001);
+
+        // The new moon of 11 January 2024 was at 11:57 UT.
+        let jd = new_moon_at_or_after(2460315.0);
+        assert!((jd - 2460320.998).abs() < 0.002, "{}", jd);
+        assert_eq!(new_moon_before(jd + 1.0), jd);
+        assert!(new_moon_before(jd) < jd - 29.0);
+    }
+}
@@ -0,0 +1,465 @@
+//! Biostratigraphy: the stratigraphic ranges of taxa, the diversity and turnover they imply, and
+//! the biozones they define.
+//!
+//! A range runs from the first appearance datum (FAD) of a taxon to its last appearance datum
+//! (LAD).  Diversity and rates are computed with the boundary-crosser counts of Foote (2000), using
+//! the central values of the datums.
+use std::fmt;
+
+use crate::{Error, GeoInterval, Geotime, Result, UncertainGeotime};
+
+/// The range of a taxon, from its first to its last appearance.
+///
+/// ```
+/// use geotime::{TaxonRange, UncertainGeotime};
+///
+/// let range = TaxonRange::new(
+///     "Tyrannosaurus rex",
+///     UncertainGeotime::from_ma(68.0, 0.5),
+///     UncertainGeotime::from_ma(66.0, 0.05),
+/// )
+/// .unwrap();
+/// assert!((range.duration_ma() - 2.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaxonRange {
+    taxon: String,
+    first: UncertainGeotime,
+    last: UncertainGeotime,
+}
+
+impl TaxonRange {
+    pub fn new(taxon: &str, first: UncertainGeotime, last: UncertainGeotime) -> Result<Self> {
+        if last.time() < first.time() {
+            return Err(Error::InvalidInput(format!(
+                "last appearance of {} is older than its first",
+                taxon
+            )));
+        }
+        Ok(Self {
+            taxon: taxon.to_string(),
+            first,
+            last,
+        })
+    }
+
+    /// The range spanned by dated occurrences of a taxon.
+    pub fn from_occurrences(taxon: &str, occurrences: &[UncertainGeotime]) -> Result<Self> {
+        let first = occurrences.iter().min_by_key(|o| o.time());
+        let last = occurrences.iter().max_by_key(|o| o.time());
+        match (first, last) {
+            (Some(first), Some(last)) => Self::new(taxon, *first, *last),
+            _ => Err(Error::InvalidInput(format!("no occurrences of {}", taxon))),
+        }
+    }
+
+    pub fn taxon(&self) -> &str {
+        &self.taxon
+    }
+
+    /// The first appearance datum.
+    pub fn first(&self) -> UncertainGeotime {
+        self.first
+    }
+
+    /// The last appearance datum.
+    pub fn last(&self) -> UncertainGeotime {
+        self.last
+    }
+
+    pub fn interval(&self) -> GeoInterval {
+        GeoInterval {
+            start: self.first,
+            end: self.last,
+        }
+    }
+
+    pub fn duration_ma(&self) -> f64 {
+        self.interval().duration_ma()
+    }
+
+    /// Whether the taxon existed at `time`, including at its first and last appearances.
+    pub fn contains(&self, time: Geotime) -> bool {
+        self.first.time() <= time && time <= self.last.time()
+    }
+}
+
+/// Counts of the taxa present in a time bin, by how their ranges cross its boundaries.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BinCounts {
+    pub interval: GeoInterval,
+    /// Taxa confined to the bin.
+    pub singletons: usize,
+    /// Taxa that cross the older boundary and end within the bin.
+    pub bottom_only: usize,
+    /// Taxa that begin within the bin and cross the younger boundary.
+    pub top_only: usize,
+    /// Taxa that cross both boundaries.
+    pub through: usize,
+}
+
+impl BinCounts {
+    /// The number of taxa present in the bin (range-through diversity).
+    pub fn diversity(&self) -> usize {
+        self.singletons + self.bottom_only + self.top_only + self.through
+    }
+
+    /// The number of taxa crossing the older boundary of the bin.
+    pub fn bottom_crossers(&self) -> usize {
+        self.bottom_only + self.through
+    }
+
+    /// The number of taxa crossing the younger boundary of the bin.
+    pub fn top_crossers(&self) -> usize {
+        self.top_only + self.through
+    }
+
+    /// The per-capita origination rate per million years, `-ln(Nbt / Nt) / Δt`, or `None` when no
+    /// taxa cross both boundaries.
+    pub fn origination_rate(&self) -> Option<f64> {
+        self.rate(self.top_crossers())
+    }
+
+    /// The per-capita extinction rate per million years, `-ln(Nbt / Nb) / Δt`, or `None` when no
+    /// taxa cross both boundaries.
+    pub fn extinction_rate(&self) -> Option<f64> {
+        self.rate(self.bottom_crossers())
+    }
+
+    fn rate(&self, crossers: usize) -> Option<f64> {
+        let duration = self.interval.duration_ma();
+        if self.through == 0 || duration <= 0.0 {
+            return None;
+        }
+        Some(-(self.through as f64 / crossers as f64).ln() / duration)
+    }
+}
+
+/// A first or last appearance.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Bioevent {
+    First(String),
+    Last(String),
+}
+
+impl fmt::Display for Bioevent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::First(taxon) => write!(f, "FAD {}", taxon),
+            Self::Last(taxon) => write!(f, "LAD {}", taxon),
+        }
+    }
+}
+
+/// An interval bounded by bioevents, with the taxa that range through it.  Simultaneous
+/// bioevents all bound the zone, so `base` and `top` each hold at least one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Biozone {
+    pub base: Vec<Bioevent>,
+    pub top: Vec<Bioevent>,
+    pub interval: GeoInterval,
+    pub taxa: Vec<String>,
+}
+
+/// A set of taxon ranges.
+///
+/// ```
+/// use geotime::{RangeChart, TaxonRange, UncertainGeotime};
+///
+/// let ma = |age| UncertainGeotime::from_ma(age, 0.0);
+/// let chart: RangeChart = vec![
+///     TaxonRange::new("A", ma(10.0), ma(4.0)).unwrap(),
+///     TaxonRange::new("B", ma(8.0), ma(2.0)).unwrap(),
+///     TaxonRange::new("C", ma(7.5), ma(6.5)).unwrap(),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let zone = chart.concurrent_range_zone(&["A", "B"]).unwrap();
+/// assert!((zone.start().to_ma() - 8.0).abs() < 1e-9);
+/// assert!((zone.end().to_ma() - 4.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangeChart {
+    ranges: Vec<TaxonRange>,
+}
+
+impl FromIterator<TaxonRange> for RangeChart {
+    fn from_iter<I: IntoIterator<Item = TaxonRange>>(iter: I) -> Self {
+        let mut chart = Self::new();
+        for range in iter {
+            chart.add(range);
+        }
+        chart
+    }
+}
+
+impl RangeChart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a range, replacing any range of the same taxon.
+    pub fn add(&mut self, range: TaxonRange) {
+        match self.ranges.iter().position(|r| r.taxon == range.taxon) {
+            Some(i) => self.ranges[i] = range,
+            None => self.ranges.push(range),
+        }
+    }
+
+    /// The ranges, in the order they were added.
+    pub fn ranges(&self) -> &[TaxonRange] {
+        &self.ranges
+    }
+
+    pub fn range(&self, taxon: &str) -> Option<&TaxonRange> {
+        self.ranges.iter().find(|r| r.taxon == taxon)
+    }
+
+    /// The taxa that existed at `time`.
+    pub fn present_at(&self, time: Geotime) -> Vec<&TaxonRange> {
+        self.ranges.iter().filter(|r| r.contains(time)).collect()
+    }
+
+    /// Boundary-crosser counts for each bin.  Bins are usually consecutive, such as the units of
+    /// one rank of a [`TimeScale`](crate::TimeScale) or the parts of [`GeoInterval::split`].
+    ///
+    /// ```
+    /// use geotime::{GeoInterval, RangeChart, TaxonRange, UncertainGeotime};
+    ///
+    /// let ma = |age| UncertainGeotime::from_ma(age, 0.0);
+    /// let mut chart = RangeChart::new();
+    /// chart.add(TaxonRange::new("A", ma(12.0), ma(1.0)).unwrap());
+    /// chart.add(TaxonRange::new("B", ma(7.0), ma(3.0)).unwrap());
+    ///
+    /// let bins = GeoInterval::new(ma(10.0), ma(0.0)).unwrap().split(2);
+    /// let counts = chart.counts(&bins);
+    /// assert_eq!(counts[0].diversity(), 2);
+    /// assert_eq!(counts[0].top_crossers(), 2);
+    /// assert!((counts[0].origination_rate().unwrap() - 2f64.ln() / 5.0).abs() < 1e-9);
+    /// ```
+    pub fn counts(&self, bins: &[GeoInterval]) -> Vec<BinCounts> {
+        bins.iter()
+            .map(|bin| {
+                let (start, end) = (bin.start.time(), bin.end.time());
+                let mut counts = BinCounts {
+                    interval: *bin,
+                    singletons: 0,
+                    bottom_only: 0,
+                    top_only: 0,
+                    through: 0,
+                };
+                for range in &self.ranges {
+                    let (first, last) = (range.first.time(), range.last.time());
+                    if first >= end || last < start {
+                        continue;
+                    }
+                    match (first < start, last >= end) {
+                        (false, false) => counts.singletons += 1,
+                        (true, false) => counts.bottom_only += 1,
+                        (false, true) => counts.top_only += 1,
+                        (true, true) => counts.through += 1,
+                    }
+                }
+                counts
+            })
+            .collect()
+    }
+
+    /// The number of taxa present in each bin.
+    pub fn diversity(&self, bins: &[GeoInterval]) -> Vec<usize> {
+        self.counts(bins).iter().map(BinCounts::diversity).collect()
+    }
+
+    /// The interval in which all of `taxa` coexisted, from the youngest of their first
+    /// appearances to the oldest of their last.
+    pub fn concurrent_range_zone(&self, taxa: &[&str]) -> Result<GeoInterval> {
+        let ranges = taxa
+            .iter()
+            .map(|t| {
+                self.range(t)
+                    .ok_or_else(|| Error::NotFound(format!("no range for {}", t)))
+            })
+            .collect::<Result<Vec<&TaxonRange>>>()?;
+
+        let start = ranges.iter().map(|r| r.first).max_by_key(|f| f.time());
+        let end = ranges.iter().map(|r| r.last).min_by_key(|l| l.time());
+        match (start, end) {
+            (Some(start), Some(end)) if start.time() < end.time() => Ok(GeoInterval { start, end }),
+            (Some(_), Some(_)) => Err(Error::InvalidInput(format!(
+                "the ranges of {} do not overlap",
+                taxa.join(", ")
+            ))),
+            _ => Err(Error::InvalidInput("no taxa given".to_string())),
+        }
+    }
+
+    /// Divides the span of the chart at every first and last appearance, oldest first.  Each
+    /// zone is bounded by the bioevents at its base and top, in the order of the chart, and
+    /// lists the taxa that range through it.
+    pub fn zones(&self) -> Vec<Biozone> {
+        let mut events: Vec<(UncertainGeotime, Bioevent)> = self
+            .ranges
+            .iter()
+            .flat_map(|r| {
+                [
+                    (r.first, Bioevent::First(r.taxon.clone())),
+                    (r.last, Bioevent::Last(r.taxon.clone())),
+                ]
+            })
+            .collect();
+        events.sort_by_key(|(t, _)| t.time());
+
+        // Bioevents at the same time bound the same zones.
+        let mut horizons: Vec<(UncertainGeotime, Vec<Bioevent>)> = Vec::new();
+        for (time, event) in events {
+            match horizons.last_mut() {
+                Some((t, simultaneous)) if t.time() == time.time() => simultaneous.push(event),
+                _ => horizons.push((time, vec![event])),
+            }
+        }
+
+        horizons
+            .windows(2)
+            .map(|pair| {
+                let ((start, base), (end, top)) = (&pair[0], &pair[1]);
+                let taxa = self
+                    .ranges
+                    .iter()
+                    .filter(|r| r.first.time() <= start.time() && r.last.time() >= end.time())
+                    .map(|r| r.taxon.clone())
+                    .collect();
+                Biozone {
+                    base: base.clone(),
+                    top: top.clone(),
+                    interval: GeoInterval {
+                        start: *start,
+                        end: *end,
+                    },
+                    taxa,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ma(age: f64) -> UncertainGeotime {
+        UncertainGeotime::from_ma(age, 0.0)
+    }
+
+    fn chart() -> RangeChart {
+        [
+            ("A", 20.0, 1.0),
+            ("B", 15.0, 12.0),
+            ("C", 18.0, 8.0),
+            ("D", 9.0, 5.0),
+            ("E", 11.0, 2.0),
+        ]
+        .iter()
+        .map(|(taxon, first, last)| TaxonRange::new(taxon, ma(*first), ma(*last)).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn ranges() {
+        assert!(TaxonRange::new("X", ma(1.0), ma(2.0)).is_err());
+
+        let range =
+            TaxonRange::from_occurrences("X", &[ma(3.0), ma(7.5), ma(1.0), ma(4.0)]).unwrap();
+        assert_eq!(range.first(), ma(7.5));
+        assert_eq!(range.last(), ma(1.0));
+        assert!(TaxonRange::from_occurrences("X", &[]).is_err());
+
+        let mut chart = chart();
+        chart.add(range);
+        chart.add(TaxonRange::new("X", ma(2.0), ma(1.0)).unwrap());
+        assert_eq!(chart.ranges().len(), 6);
+        assert_eq!(chart.range("X").unwrap().first(), ma(2.0));
+
+        let present: Vec<&str> = chart
+            .present_at(Geotime::from_ma(10.0))
+            .iter()
+            .map(|r| r.taxon())
+            .collect();
+        assert_eq!(present, vec!["A", "C", "E"]);
+    }
+
+    #[test]
+    fn counts() {
+        let bins = GeoInterval::new(ma(20.0), ma(0.0)).unwrap().split(4);
+        let counts = chart().counts(&bins);
+
+        // 20-15: A and C begin and continue; B begins at the younger boundary.
+        assert_eq!(
+            (
+                counts[0].singletons,
+                counts[0].bottom_only,
+                counts[0].top_only,
+                counts[0].through
+            ),
+            (0, 0, 2, 0)
+        );
+        // 15-10: A and C through, B confined, E begins; D begins at the younger boundary.
+        assert_eq!(
+            (
+                counts[1].singletons,
+                counts[1].bottom_only,
+                counts[1].top_only,
+                counts[1].through
+            ),
+            (1, 0, 1, 2)
+        );
+        assert_eq!(chart().diversity(&bins), vec![2, 4, 4, 3]);
+
+        assert_eq!(counts[1].extinction_rate(), Some(0.0));
+        let rate = counts[1].origination_rate().unwrap();
+        assert!((rate - 1.5f64.ln() / 5.0).abs() < 1e-9);
+        assert_eq!(counts[0].origination_rate(), None);
+    }
+
+    #[test]
+    fn concurrent_range_zone() {
+        let zone = chart().concurrent_range_zone(&["A", "C", "E"]).unwrap();
+        assert_eq!(zone.start(), ma(11.0));
+        assert_eq!(zone.end(), ma(8.0));
+
+        assert!(matches!(
+            chart().concurrent_range_zone(&["B", "D"]),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(matches!(
+            chart().concurrent_range_zone(&["A", "Z"]),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn zones() {
+        let zones = chart().zones();
+        assert_eq!(zones.len(), 9);
+        assert_eq!(zones[0].base, vec![Bioevent::First("A".to_string())]);
+        assert_eq!(zones[0].top[0].to_string(), "FAD C");
+        assert_eq!(zones[0].taxa, vec!["A"]);
+        assert_eq!(zones[2].taxa, vec!["A", "B", "C"]);
+        assert!(zones
+            .windows(2)
+            .all(|z| z[0].interval.end() == z[1].interval.start()));
+        assert_eq!(zones[8].top[0].to_string(), "LAD A");
+
+        // Simultaneous bioevents bound the same zones.
+        let mut chart = chart();
+        chart.add(TaxonRange::new("F", ma(15.0), ma(11.0)).unwrap());
+        let zones = chart.zones();
+        assert_eq!(zones.len(), 9);
+        let events =
+            |events: &[Bioevent]| -> Vec<String> { events.iter().map(|e| e.to_string()).collect() };
+        assert_eq!(events(&zones[1].top), vec!["FAD B", "FAD F"]);
+        assert_eq!(events(&zones[2].base), vec!["FAD B", "FAD F"]);
+        assert_eq!(events(&zones[3].top), vec!["FAD E", "LAD F"]);
+        assert_eq!(zones[3].taxa, vec!["A", "C", "F"]);
+    }
+}
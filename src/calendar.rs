@@ -0,0 +1,773 @@
+//! Historical calendars: the Julian calendar, the proleptic Gregorian calendar, the hybrid
+//! calendars that switched from one to the other when a country adopted the Gregorian reform, and
+//! the tabular Islamic, Hebrew and Solar Hijri calendars, all behind the [`Calendar`] trait.
+//! The Maya and Chinese calendars, the era names of China, Japan and Korea, and [`Era`] notation
+//! for years have their own types.
+//!
+//! Dates map to the [`Geotime`] at midnight UTC at the start of the day, so dates recorded in
+//! different calendars sort together.  Julian and Gregorian years are astronomical: year 0 is
+//! 1 BC.
+//!
+//! Until 1752, the legal year in England began on Lady Day, 25 March, and dates between 1 January
+//! and 24 March were often written with both years: "10 February 1750/51" is 10 February 1751 in
+//! a year starting on 1 January.
+use std::fmt;
+
+use crate::{Error, Geotime, Result, MILLISECONDS_IN_DAY};
+
+mod chinese;
+mod era;
+mod hebrew;
+mod islamic;
+mod maya;
+mod persian;
+mod regnal;
+
+pub use chinese::{ChineseCalendar, ChineseDate, Sexagenary};
+pub use era::Era;
+pub use hebrew::HebrewCalendar;
+pub use islamic::IslamicCalendar;
+pub use maya::{CalendarRound, Haab, LongCount, MayaCorrelation, Tzolkin};
+pub use persian::PersianCalendar;
+pub use regnal::{Realm, RegnalEra};
+
+// Julian Day Number of the Unix epoch, 1970-01-01.
+const UNIX_EPOCH_JDN: i64 = 2440588;
+
+// The largest year, either side of year 0, whose days fall within the range of times that have
+// calendar dates.
+const MAX_YEAR: i64 = i64::MAX / 1000 / 366;
+
+#[rustfmt::skip]
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September",
+    "October", "November", "December",
+];
+
+#[rustfmt::skip]
+const DAYS: [&str; 7] = [
+    "Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday",
+];
+
+/// A day in some calendar, as year, month and day of the month.  Months are numbered from 1 in
+/// the order they fall in the year.  `Display` and [`CalendarDate::month_name`] use Julian and
+/// Gregorian month names; see [`Calendar::format`] for other calendars.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CalendarDate {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl CalendarDate {
+    pub fn new(year: i64, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// The full English name of the month in the Julian and Gregorian calendars.
+    pub fn month_name(&self) -> &'static str {
+        MONTHS[(self.month.clamp(1, 12) - 1) as usize]
+    }
+
+    // Years beyond `MAX_YEAR` are out of range, and so cannot overflow the day count.
+    fn check_year(&self) -> Result<()> {
+        if self.year.unsigned_abs() > MAX_YEAR as u64 {
+            return Err(Error::OutOfRange(format!(
+                "the year of {} is too far from the present",
+                self
+            )));
+        }
+        Ok(())
+    }
+
+    fn check(&self, gregorian: bool) -> Result<()> {
+        let leap = match gregorian {
+            true => self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0),
+            false => self.year % 4 == 0,
+        };
+        let days = match self.month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => 0,
+        };
+        if self.day == 0 || self.day > days {
+            return Err(Error::InvalidInput(format!("no such date: {}", self)));
+        }
+        Ok(())
+    }
+
+    // Julian Day Number, following Richards (2013), with floor division so that it holds for
+    // years before the epoch of the algorithm.
+    fn jdn(&self, gregorian: bool) -> i64 {
+        let a = (14 - self.month as i64).div_euclid(12);
+        let y = self.year + 4800 - a;
+        let m = self.month as i64 + 12 * a - 3;
+        let days = self.day as i64 + (153 * m + 2).div_euclid(5) + 365 * y + y.div_euclid(4);
+        match gregorian {
+            true => days - y.div_euclid(100) + y.div_euclid(400) - 32045,
+            false => days - 32083,
+        }
+    }
+
+    fn from_jdn(jdn: i64, gregorian: bool) -> Self {
+        let (b, c) = match gregorian {
+            true => {
+                let a = jdn + 32044;
+                let b = (4 * a + 3).div_euclid(146097);
+                (b, a - (146097 * b).div_euclid(4))
+            }
+            false => (0, jdn + 32082),
+        };
+        let d = (4 * c + 3).div_euclid(1461);
+        let e = c - (1461 * d).div_euclid(4);
+        let m = (5 * e + 2).div_euclid(153);
+        Self {
+            year: 100 * b + d - 4800 + m.div_euclid(10),
+            month: (m + 3 - 12 * m.div_euclid(10)) as u8,
+            day: (e - (153 * m + 2).div_euclid(5) + 1) as u8,
+        }
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    /// Formats the date as "10 February 1751", or "15 March 44 BC" for years before AD 1.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = MONTHS
+            .get((self.month as usize).wrapping_sub(1))
+            .unwrap_or(&"?");
+        match self.year {
+            y if y > 0 => write!(f, "{} {} {}", self.day, name, y),
+            y => write!(f, "{} {} {} BC", self.day, name, 1 - y as i128),
+        }
+    }
+}
+
+/// A calendar: a way of naming days that maps each to the [`Geotime`] at its start.
+///
+/// ```
+/// use geotime::{Calendar, HebrewCalendar, IslamicCalendar, PersianCalendar, ReformCalendar};
+///
+/// // Manuscripts dated in different calendars, in one sortable key space.
+/// let calendars: [(&dyn Calendar, &str); 4] = [
+///     (&IslamicCalendar, "1 Ramadan 1444 AH"),
+///     (&HebrewCalendar, "15 Nisan 5783 AM"),
+///     (&PersianCalendar, "1 Farvardin 1402"),
+///     (&ReformCalendar::GREGORIAN, "22 March 2023"),
+/// ];
+/// let mut times: Vec<_> = calendars
+///     .iter()
+///     .map(|(calendar, date)| (calendar.parse(date).unwrap(), *calendar))
+///     .collect();
+/// times.sort_by_key(|(time, _)| *time);
+///
+/// let (time, calendar) = times[0];
+/// assert_eq!(calendar.format(time).unwrap(), "1 Farvardin 1402 SH");
+/// assert_eq!(IslamicCalendar.format(time).unwrap(), "28 Sha'ban 1444 AH");
+/// assert_eq!(calendar.day_name(time).unwrap(), "Seshanbeh");
+/// ```
+pub trait Calendar {
+    /// The abbreviation of the era years are counted in, such as "AH", written after the year.
+    fn era(&self) -> &'static str;
+
+    /// The start of `date`.  Dates that do not exist in the calendar are an error.
+    fn to_geotime(&self, date: CalendarDate) -> Result<Geotime>;
+
+    /// The date containing `time`.
+    fn date(&self, time: Geotime) -> Result<CalendarDate>;
+
+    fn months_in_year(&self, year: i64) -> u8;
+
+    fn days_in_month(&self, year: i64, month: u8) -> u8;
+
+    /// The name of a month, which in some calendars depends on the year.
+    fn month_name(&self, year: i64, month: u8) -> Option<&'static str>;
+
+    /// The names of the days of the week, starting with the day that falls on Sunday.
+    fn day_names(&self) -> [&'static str; 7];
+
+    /// The name of the day of the week containing `time`.
+    fn day_name(&self, time: Geotime) -> Result<&'static str> {
+        Ok(self.day_names()[(jdn(time)? + 1).rem_euclid(7) as usize])
+    }
+
+    /// Formats the date containing `time` as day, month name, year and era: "1 Ramadan 1444 AH".
+    fn format(&self, time: Geotime) -> Result<String> {
+        let date = self.date(time)?;
+        let month = self.month_name(date.year, date.month).unwrap_or("?");
+        Ok(
+            format!("{} {} {} {}", date.day, month, date.year, self.era())
+                .trim_end()
+                .to_string(),
+        )
+    }
+
+    /// Parses a date written as day, month name and year, optionally followed by the era, as in
+    /// "1 Ramadan 1444 AH", or as year, month and day numbers, as in "1444-09-01".  Month names
+    /// are matched ignoring case and punctuation, and may be abbreviated.
+    fn parse(&self, input: &str) -> Result<Geotime> {
+        let invalid = || Error::InvalidInput(format!("unable to parse date: {}", input));
+        let mut tokens: Vec<&str> = input.split_whitespace().collect();
+        if tokens.len() > 1 && tokens.last().map(|t| normalize(t)) == Some(normalize(self.era())) {
+            tokens.pop();
+        }
+        let date = match tokens[..] {
+            [iso] => parse_iso(iso).ok_or_else(invalid)?,
+            [day, ref month @ .., year] if !month.is_empty() => {
+                let year: i64 = year.parse().map_err(|_| invalid())?;
+                CalendarDate::new(year, 1, 1).check_year()?;
+                let month = normalize(&month.join(" "));
+                let names: Vec<(u8, String)> = (1..=self.months_in_year(year))
+                    .filter_map(|m| self.month_name(year, m).map(|name| (m, normalize(name))))
+                    .collect();
+                let month = names
+                    .iter()
+                    .find(|(_, name)| *name == month)
+                    .or_else(|| {
+                        names
+                            .iter()
+                            .find(|(_, name)| month.len() >= 3 && name.starts_with(&month))
+                    })
+                    .ok_or_else(invalid)?
+                    .0;
+                CalendarDate::new(
+                    year,
+                    month,
+                    day.trim_end_matches(',').parse().map_err(|_| invalid())?,
+                )
+            }
+            _ => return Err(invalid()),
+        };
+        self.to_geotime(date)
+    }
+}
+
+// Lowercase letters and digits only, for matching names written with varying punctuation.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// A date written as year, month and day numbers, such as "1751-02-10" or "-43-03-15".
+fn parse_iso(input: &str) -> Option<CalendarDate> {
+    let (sign, input) = match input.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, input),
+    };
+    let fields: Vec<&str> = input.split('-').collect();
+    match fields[..] {
+        [y, m, d] => Some(CalendarDate::new(
+            sign * y.parse::<i64>().ok()?,
+            m.parse().ok()?,
+            d.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
+
+// Checks that a date exists in a calendar with fixed month lengths.
+fn check(calendar: &impl Calendar, date: CalendarDate) -> Result<()> {
+    date.check_year()?;
+    if date.month == 0
+        || date.month > calendar.months_in_year(date.year)
+        || date.day == 0
+        || date.day > calendar.days_in_month(date.year, date.month)
+    {
+        return Err(Error::InvalidInput(format!(
+            "no such date: {}-{:02}-{:02} {}",
+            date.year,
+            date.month,
+            date.day,
+            calendar.era()
+        )));
+    }
+    Ok(())
+}
+
+/// A calendar that follows the Julian rules up to a reform and the Gregorian rules from then
+/// on.  [`ReformCalendar::JULIAN`] and [`ReformCalendar::GREGORIAN`] never switch.
+///
+/// ```
+/// use geotime::{CalendarDate, ReformCalendar};
+///
+/// let britain = ReformCalendar::BRITAIN;
+/// let last_julian = britain.to_geotime(CalendarDate::new(1752, 9, 2)).unwrap();
+/// let first_gregorian = britain.to_geotime(CalendarDate::new(1752, 9, 14)).unwrap();
+/// assert_eq!(first_gregorian.millis() - last_julian.millis(), 86_400_000);
+/// assert!(britain.to_geotime(CalendarDate::new(1752, 9, 10)).is_err());
+///
+/// // The same day, as recorded in Rome and in London.
+/// let rome = ReformCalendar::ROME.parse("21 February 1751").unwrap();
+/// let london = britain.parse("10 February 1750/51").unwrap();
+/// assert_eq!(rome, london);
+/// assert_eq!(britain.format_dual(london).unwrap(), "10 February 1750/51");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ReformCalendar {
+    // Julian Day Number of the first day counted in the Gregorian calendar.
+    first_gregorian: i64,
+    // The first year to begin on 1 January rather than on Lady Day.
+    first_january_year: i64,
+}
+
+impl ReformCalendar {
+    /// The proleptic Julian calendar.
+    pub const JULIAN: Self = Self {
+        first_gregorian: i64::MAX,
+        first_january_year: i64::MIN,
+    };
+
+    /// The proleptic Gregorian calendar.
+    pub const GREGORIAN: Self = Self {
+        first_gregorian: i64::MIN,
+        first_january_year: i64::MIN,
+    };
+
+    /// The papal reform: 4 October 1582 was followed by 15 October 1582.
+    pub const ROME: Self = Self {
+        first_gregorian: 2299161,
+        first_january_year: i64::MIN,
+    };
+
+    /// Great Britain and its colonies: 2 September 1752 was followed by 14 September 1752.  Years
+    /// began on Lady Day until the Calendar Act made 1752 begin on 1 January.
+    pub const BRITAIN: Self = Self {
+        first_gregorian: 2361222,
+        first_january_year: 1752,
+    };
+
+    /// Soviet Russia: 31 January 1918 was followed by 14 February 1918.
+    pub const RUSSIA: Self = Self {
+        first_gregorian: 2421639,
+        first_january_year: i64::MIN,
+    };
+
+    /// A calendar whose reform took effect on `first_gregorian`, the first date in the Gregorian
+    /// calendar.  Its years begin on 1 January; see [`ReformCalendar::with_lady_day_years`].
+    pub fn new(first_gregorian: CalendarDate) -> Result<Self> {
+        first_gregorian.check_year()?;
+        first_gregorian.check(true)?;
+        Ok(Self {
+            first_gregorian: first_gregorian.jdn(true),
+            first_january_year: i64::MIN,
+        })
+    }
+
+    /// The calendar with years that began on Lady Day, 25 March, until `first_january_year`, the
+    /// first year to begin on 1 January.
+    pub fn with_lady_day_years(self, first_january_year: i64) -> Self {
+        Self {
+            first_january_year,
+            ..self
+        }
+    }
+
+    /// The first day on the Gregorian calendar, or `None` for a calendar that never switches.
+    pub fn reform(&self) -> Option<CalendarDate> {
+        match self.first_gregorian {
+            i64::MIN | i64::MAX => None,
+            jdn => Some(CalendarDate::from_jdn(jdn, true)),
+        }
+    }
+
+    /// Whether the calendar follows the Gregorian rules at `time`.
+    pub fn is_gregorian(&self, time: Geotime) -> Result<bool> {
+        Ok(jdn(time)? >= self.first_gregorian)
+    }
+
+    /// The start of `date`.  Dates that do not exist, including those dropped by the reform, are
+    /// an error.
+    pub fn to_geotime(&self, date: CalendarDate) -> Result<Geotime> {
+        <Self as Calendar>::to_geotime(self, date)
+    }
+
+    /// The date containing `time`.
+    pub fn date(&self, time: Geotime) -> Result<CalendarDate> {
+        <Self as Calendar>::date(self, time)
+    }
+
+    /// Formats the date containing `time`, as in "14 September 1752".
+    pub fn format(&self, time: Geotime) -> Result<String> {
+        <Self as Calendar>::format(self, time)
+    }
+
+    /// Parses a date written as "10 February 1751", "10 Feb 1750/51", "15 March 44 BC" or
+    /// "1751-02-10".  A trailing "O.S." or "N.S." reads the date in the Julian or Gregorian
+    /// calendar whatever the date of the reform.
+    pub fn parse(&self, input: &str) -> Result<Geotime> {
+        <Self as Calendar>::parse(self, input)
+    }
+
+    /// Formats the date containing `time`, giving both years for dates from 1 January to 24
+    /// March while years began on Lady Day: "10 February 1750/51".  The first year is the one
+    /// that began on Lady Day, the second the one that began on 1 January.  Other dates are
+    /// formatted as [`ReformCalendar::format`] does.
+    pub fn format_dual(&self, time: Geotime) -> Result<String> {
+        let date = self.date(time)?;
+        if date.year < 2
+            || date.year >= self.first_january_year
+            || date.month > 3
+            || (date.month == 3 && date.day >= 25)
+        {
+            return Ok(date.to_string());
+        }
+        let (old, new) = (date.year - 1, date.year.to_string());
+        let new = match old / 100 == date.year / 100 {
+            true => &new[new.len() - 2..],
+            false => &new,
+        };
+        Ok(format!(
+            "{} {} {}/{}",
+            date.day,
+            date.month_name(),
+            old,
+            new
+        ))
+    }
+}
+
+impl Calendar for ReformCalendar {
+    fn era(&self) -> &'static str {
+        ""
+    }
+
+    fn to_geotime(&self, date: CalendarDate) -> Result<Geotime> {
+        date.check_year()?;
+        let julian = date.jdn(false);
+        if julian < self.first_gregorian {
+            date.check(false)?;
+            return Ok(from_jdn(julian));
+        }
+        let gregorian = date.jdn(true);
+        if gregorian >= self.first_gregorian {
+            date.check(true)?;
+            return Ok(from_jdn(gregorian));
+        }
+        Err(Error::InvalidInput(format!(
+            "{} was skipped by the Gregorian reform",
+            date
+        )))
+    }
+
+    fn date(&self, time: Geotime) -> Result<CalendarDate> {
+        let jdn = jdn(time)?;
+        Ok(CalendarDate::from_jdn(jdn, jdn >= self.first_gregorian))
+    }
+
+    fn format(&self, time: Geotime) -> Result<String> {
+        Ok(self.date(time)?.to_string())
+    }
+
+    fn months_in_year(&self, _year: i64) -> u8 {
+        12
+    }
+
+    /// The number of days in the month, which is fewer than usual in the month of the reform.
+    fn days_in_month(&self, year: i64, month: u8) -> u8 {
+        (1..=31)
+            .filter(|&day| self.to_geotime(CalendarDate::new(year, month, day)).is_ok())
+            .count() as u8
+    }
+
+    fn month_name(&self, _year: i64, month: u8) -> Option<&'static str> {
+        MONTHS.get((month as usize).wrapping_sub(1)).copied()
+    }
+
+    fn day_names(&self) -> [&'static str; 7] {
+        DAYS
+    }
+
+    fn parse(&self, input: &str) -> Result<Geotime> {
+        let invalid = || Error::InvalidInput(format!("unable to parse date: {}", input));
+        let mut tokens: Vec<&str> = input.split_whitespace().collect();
+
+        let calendar = match tokens
+            .last()
+            .map(|t| t.to_ascii_uppercase().replace('.', ""))
+        {
+            Some(style) if style == "OS" => {
+                tokens.pop();
+                Self::JULIAN
+            }
+            Some(style) if style == "NS" => {
+                tokens.pop();
+                Self::GREGORIAN
+            }
+            _ => *self,
+        };
+
+        let bc = match tokens
+            .last()
+            .map(|t| t.to_ascii_uppercase().replace('.', ""))
+        {
+            Some(era) if era == "BC" || era == "BCE" => {
+                tokens.pop();
+                true
+            }
+            Some(era) if era == "AD" || era == "CE" => {
+                tokens.pop();
+                false
+            }
+            _ => false,
+        };
+
+        let date = match tokens[..] {
+            [iso] if !bc => parse_iso(iso).ok_or_else(invalid)?,
+            [day, month, year] => {
+                let month = MONTHS
+                    .iter()
+                    .position(|m| {
+                        month.len() >= 3
+                            && m.to_ascii_lowercase()
+                                .starts_with(&month.trim_end_matches('.').to_ascii_lowercase())
+                    })
+                    .ok_or_else(invalid)?;
+                let year = match year.split_once('/') {
+                    Some((old, new)) => dual_year(old, new).ok_or_else(invalid)?,
+                    None => year.parse().map_err(|_| invalid())?,
+                };
+                let year = match bc {
+                    true => 1i64.checked_sub(year).ok_or_else(invalid)?,
+                    false => year,
+                };
+                CalendarDate::new(
+                    year,
+                    month as u8 + 1,
+                    day.trim_end_matches(',').parse().map_err(|_| invalid())?,
+                )
+            }
+            _ => return Err(invalid()),
+        };
+        calendar.to_geotime(date)
+    }
+}
+
+// The year beginning on 1 January in a dual year such as "1750/51" or "1699/1700".
+fn dual_year(old: &str, new: &str) -> Option<i64> {
+    let year: i64 = old.parse().ok()?;
+    let digits = new.len();
+    if digits == 0 || digits > old.len() {
+        return None;
+    }
+    let full = format!("{}{}", &old[..old.len() - digits], new);
+    let next = year.checked_add(1)?;
+    match full.parse::<i64>() {
+        Ok(n) if n == next => Some(n),
+        _ if new.parse::<i64>().ok()? == next => Some(next),
+        _ => None,
+    }
+}
+
+fn jdn(time: Geotime) -> Result<i64> {
+    let days = time.millis().div_euclid(MILLISECONDS_IN_DAY) + UNIX_EPOCH_JDN as i128;
+    // Leave room for the arithmetic in the conversions.
+    match i64::try_from(days) {
+        Ok(days) if days.unsigned_abs() < i64::MAX as u64 / 1000 => Ok(days),
+        _ => Err(Error::OutOfRange(format!(
+            "{:?} is too far from the present for a calendar date",
+            time
+        ))),
+    }
+}
+
+fn from_jdn(jdn: i64) -> Geotime {
+    Geotime::from((jdn - UNIX_EPOCH_JDN) as i128 * MILLISECONDS_IN_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Lexical64;
+
+    fn date(year: i64, month: u8, day: u8) -> CalendarDate {
+        CalendarDate::new(year, month, day)
+    }
+
+    #[test]
+    fn reforms() {
+        for (calendar, first, last_julian) in [
+            (ReformCalendar::ROME, date(1582, 10, 15), date(1582, 10, 4)),
+            (ReformCalendar::BRITAIN, date(1752, 9, 14), date(1752, 9, 2)),
+            (ReformCalendar::RUSSIA, date(1918, 2, 14), date(1918, 1, 31)),
+        ] {
+            assert_eq!(
+                ReformCalendar::new(first).unwrap().reform(),
+                calendar.reform()
+            );
+            assert_eq!(calendar.reform(), Some(first));
+            let t = calendar.to_geotime(first).unwrap();
+            let day = Geotime::from(t.millis() - MILLISECONDS_IN_DAY);
+            assert_eq!(calendar.date(day).unwrap(), last_julian);
+            assert!(calendar.is_gregorian(t).unwrap());
+            assert!(!calendar.is_gregorian(day).unwrap());
+        }
+        assert_eq!(ReformCalendar::JULIAN.reform(), None);
+        assert_eq!(
+            ReformCalendar::new(date(1582, 10, 15)).unwrap(),
+            ReformCalendar::ROME
+        );
+        assert_eq!(
+            ReformCalendar::new(date(1752, 9, 14))
+                .unwrap()
+                .with_lady_day_years(1752),
+            ReformCalendar::BRITAIN
+        );
+        assert!(ReformCalendar::new(date(1700, 2, 29)).is_err());
+    }
+
+    #[test]
+    fn epoch_and_chrono() {
+        let gregorian = ReformCalendar::GREGORIAN;
+        assert_eq!(
+            gregorian.to_geotime(date(1970, 1, 1)).unwrap(),
+            Geotime::from(0)
+        );
+        assert_eq!(
+            gregorian.to_geotime(date(1950, 1, 1)).unwrap(),
+            Geotime::PRESENT
+        );
+        assert_eq!(
+            gregorian.date(Geotime::from(-1)).unwrap(),
+            date(1969, 12, 31)
+        );
+        let t = gregorian.to_geotime(date(1800, 3, 1)).unwrap();
+        assert_eq!(t.display_string("%Y-%m-%d"), "1800-03-01");
+    }
+
+    #[test]
+    fn round_trip() {
+        for calendar in [ReformCalendar::JULIAN, ReformCalendar::GREGORIAN] {
+            let mut jdn = -1_000_000;
+            while jdn < 3_000_000 {
+                let t = from_jdn(jdn);
+                let date = calendar.date(t).unwrap();
+                assert_eq!(calendar.to_geotime(date).unwrap(), t, "{}", date);
+                jdn += 997;
+            }
+        }
+        // JDN 0 is 1 January 4713 BC in the Julian calendar.
+        assert_eq!(
+            ReformCalendar::JULIAN.date(from_jdn(0)).unwrap(),
+            date(-4712, 1, 1)
+        );
+    }
+
+    #[test]
+    fn leap_years() {
+        let julian = ReformCalendar::JULIAN;
+        let gregorian = ReformCalendar::GREGORIAN;
+        assert!(julian.to_geotime(date(1700, 2, 29)).is_ok());
+        assert!(gregorian.to_geotime(date(1700, 2, 29)).is_err());
+        assert!(gregorian.to_geotime(date(2000, 2, 29)).is_ok());
+        assert!(ReformCalendar::BRITAIN
+            .to_geotime(date(1700, 2, 29))
+            .is_ok());
+        assert!(ReformCalendar::ROME.to_geotime(date(1700, 2, 29)).is_err());
+        assert!(julian.to_geotime(date(1700, 13, 1)).is_err());
+        assert!(julian.to_geotime(date(1700, 4, 31)).is_err());
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let britain = ReformCalendar::BRITAIN;
+        let t = britain.parse("10 February 1750/51").unwrap();
+        assert_eq!(britain.parse("10 Feb. 1751").unwrap(), t);
+        assert_eq!(britain.parse("1751-02-10").unwrap(), t);
+        assert_eq!(britain.parse("21 February 1751 N.S.").unwrap(), t);
+        assert_eq!(
+            ReformCalendar::ROME.parse("10 February 1751 O.S.").unwrap(),
+            t
+        );
+        assert_eq!(britain.format(t).unwrap(), "10 February 1751");
+        assert_eq!(ReformCalendar::ROME.format(t).unwrap(), "21 February 1751");
+
+        let t = britain.parse("1 January 1699/1700").unwrap();
+        assert_eq!(britain.format_dual(t).unwrap(), "1 January 1699/1700");
+        let t = britain.parse("25 March 1751").unwrap();
+        assert_eq!(britain.format_dual(t).unwrap(), "25 March 1751");
+
+        // The Calendar Act began 1752 on 1 January.
+        let t = britain.parse("24 March 1751/52").unwrap();
+        assert_eq!(britain.format_dual(t).unwrap(), "24 March 1752");
+        let t = britain.parse("31 December 1751").unwrap();
+        assert_eq!(britain.format_dual(t).unwrap(), "31 December 1751");
+        let t = britain.parse("10 February 2024").unwrap();
+        assert_eq!(britain.format_dual(t).unwrap(), "10 February 2024");
+        let t = ReformCalendar::GREGORIAN.parse("10 February 1700").unwrap();
+        assert_eq!(
+            ReformCalendar::GREGORIAN.format_dual(t).unwrap(),
+            "10 February 1700"
+        );
+        let venice = ReformCalendar::ROME.with_lady_day_years(1797);
+        assert_eq!(venice.format_dual(t).unwrap(), "10 February 1699/1700");
+
+        let ides = ReformCalendar::JULIAN.parse("15 March 44 BC").unwrap();
+        assert_eq!(ReformCalendar::JULIAN.parse("-43-03-15").unwrap(), ides);
+        assert_eq!(
+            ReformCalendar::JULIAN.format(ides).unwrap(),
+            "15 March 44 BC"
+        );
+
+        for huge in [
+            "1 January 99999999999999999",
+            "1 January 99999999999999999 BC",
+            "1 January -9223372036854775807",
+            "9999999999999999-01-01",
+        ] {
+            assert!(
+                matches!(
+                    ReformCalendar::JULIAN.parse(huge),
+                    Err(Error::OutOfRange(_))
+                ),
+                "{}",
+                huge
+            );
+        }
+        let last = date(MAX_YEAR, 12, 31);
+        let t = ReformCalendar::GREGORIAN.to_geotime(last).unwrap();
+        assert_eq!(ReformCalendar::GREGORIAN.date(t).unwrap(), last);
+        let first = date(-MAX_YEAR, 1, 1);
+        let t = ReformCalendar::JULIAN.to_geotime(first).unwrap();
+        assert_eq!(ReformCalendar::JULIAN.date(t).unwrap(), first);
+        assert!(ReformCalendar::new(date(i64::MIN, 1, 1)).is_err());
+        assert!(ReformCalendar::JULIAN
+            .parse("1 January -9223372036854775808 BC")
+            .is_err());
+        assert!(ReformCalendar::BRITAIN
+            .parse("1 January 9223372036854775807/8")
+            .is_err());
+        assert_eq!(
+            date(i64::MIN, 1, 1).to_string(),
+            "1 January 9223372036854775809 BC"
+        );
+
+        for bad in [
+            "",
+            "10 Febuary 1751",
+            "10 February 1750/52",
+            "1751-02",
+            "5 October 1582",
+        ] {
+            assert!(ReformCalendar::ROME.parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn sorting() {
+        let dates = [
+            ReformCalendar::BRITAIN
+                .parse("10 February 1750/51")
+                .unwrap(),
+            ReformCalendar::ROME.parse("20 February 1751").unwrap(),
+            ReformCalendar::RUSSIA.parse("1751-02-11").unwrap(),
+        ];
+        let keys: Vec<String> = dates
+            .iter()
+            .map(|t| Lexical64::from(*t).to_string())
+            .collect();
+        assert!(keys[1] < keys[0] && keys[0] < keys[2]);
+    }
+}
@@ -0,0 +1,465 @@
+//! The Chinese lunisolar calendar and the sexagenary cycle.
+use std::fmt;
+
+use super::{from_jdn, jdn, CalendarDate};
+use crate::astro::{
+    new_moon_at_or_after, new_moon_before, solar_longitude, solar_longitude_before,
+    MEAN_SYNODIC_MONTH, MEAN_TROPICAL_YEAR,
+};
+use crate::{Error, Geotime, Result};
+
+#[rustfmt::skip]
+const STEMS: [(&str, char); 10] = [
+    ("Jia", '甲'), ("Yi", '乙'), ("Bing", '丙'), ("Ding", '丁'), ("Wu", '戊'), ("Ji", '己'),
+    ("Geng", '庚'), ("Xin", '辛'), ("Ren", '壬'), ("Gui", '癸'),
+];
+
+#[rustfmt::skip]
+const BRANCHES: [(&str, char); 12] = [
+    ("Zi", '子'), ("Chou", '丑'), ("Yin", '寅'), ("Mao", '卯'), ("Chen", '辰'), ("Si", '巳'),
+    ("Wu", '午'), ("Wei", '未'), ("Shen", '申'), ("You", '酉'), ("Xu", '戌'), ("Hai", '亥'),
+];
+
+// Gregorian years over which the astronomical series are worth evaluating.
+const YEARS: std::ops::RangeInclusive<i64> = -5000..=5000;
+
+/// A position in the sexagenary cycle of sixty stem-branch pairs, counting from 0 for jiazi
+/// (甲子).  Years and days both run through the cycle without a break.
+///
+/// ```
+/// use geotime::{ReformCalendar, Calendar, Sexagenary};
+///
+/// let year = Sexagenary::of_year(2024);
+/// assert_eq!(year.to_string(), "Jiachen");
+/// assert_eq!(year.chinese(), "甲辰");
+/// assert_eq!((year.stem(), year.branch()), ("Jia", "Chen"));
+///
+/// let day = ReformCalendar::GREGORIAN.parse("1 January 2000").unwrap();
+/// assert_eq!(Sexagenary::of_day(day).unwrap().to_string(), "Wuwu");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Sexagenary(u8);
+
+impl Sexagenary {
+    /// The `index`th pair of the cycle, modulo 60.
+    pub fn new(index: i64) -> Self {
+        Self(index.rem_euclid(60) as u8)
+    }
+
+    /// The pair naming a Chinese year, numbered as the Gregorian year in which it begins.
+    pub fn of_year(year: i64) -> Self {
+        Self::new(year - 4)
+    }
+
+    /// The pair naming the day on which `time` falls, in UTC.
+    pub fn of_day(time: Geotime) -> Result<Self> {
+        Ok(Self::new(jdn(time)? - 11))
+    }
+
+    pub fn index(&self) -> u8 {
+        self.0
+    }
+
+    /// The heavenly stem, in pinyin.
+    pub fn stem(&self) -> &'static str {
+        STEMS[(self.0 % 10) as usize].0
+    }
+
+    /// The earthly branch, in pinyin.
+    pub fn branch(&self) -> &'static str {
+        BRANCHES[(self.0 % 12) as usize].0
+    }
+
+    /// The pair in Chinese characters.
+    pub fn chinese(&self) -> String {
+        [
+            STEMS[(self.0 % 10) as usize].1,
+            BRANCHES[(self.0 % 12) as usize].1,
+        ]
+        .iter()
+        .collect()
+    }
+}
+
+impl fmt::Display for Sexagenary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.stem(), self.branch().to_lowercase())
+    }
+}
+
+/// A day in the Chinese calendar.  The year is numbered as the Gregorian year in which it
+/// begins, and a leap month follows the ordinary month with the same number.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ChineseDate {
+    pub year: i64,
+    pub month: u8,
+    pub leap: bool,
+    pub day: u8,
+}
+
+impl ChineseDate {
+    pub fn new(year: i64, month: u8, leap: bool, day: u8) -> Self {
+        Self {
+            year,
+            month,
+            leap,
+            day,
+        }
+    }
+
+    /// The sexagenary name of the year.
+    pub fn year_name(&self) -> Sexagenary {
+        Sexagenary::of_year(self.year)
+    }
+}
+
+impl fmt::Display for ChineseDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let leap = if self.leap { "leap " } else { "" };
+        write!(
+            f,
+            "{} {}, {}month {}, day {}",
+            self.year_name(),
+            self.year,
+            leap,
+            self.month,
+            self.day
+        )
+    }
+}
+
+/// The Chinese lunisolar calendar, computed astronomically under the rules in force since 1645:
+/// each month begins on the day of a new moon, the winter solstice falls in the eleventh month,
+/// and in a year of thirteen months the first month without a major solar term is a leap month.
+/// The algorithms follow Reingold and Dershowitz, *Calendrical Calculations*, with the positions
+/// of the Sun and Moon after Meeus.
+///
+/// Days begin at midnight local time.  The Japanese and Korean calendars followed the same rules
+/// for the meridians of Kyoto and Seoul, so only the reference time zone differs.  Dates before
+/// 1645 are proleptic: earlier calendars used mean motions and can differ by a day, or rarely a
+/// month, from the ones computed here.  The calendar supports Gregorian years -5000 to 5000.
+///
+/// ```
+/// use geotime::{ChineseCalendar, ChineseDate, ReformCalendar, Calendar};
+///
+/// let new_year = ChineseCalendar::CHINA.new_year(2024).unwrap();
+/// assert_eq!(ReformCalendar::GREGORIAN.format(new_year).unwrap(), "10 February 2024");
+///
+/// let date = ChineseCalendar::CHINA.date(new_year).unwrap();
+/// assert_eq!(date, ChineseDate::new(2024, 1, false, 1));
+/// assert_eq!(date.to_string(), "Jiachen 2024, month 1, day 1");
+/// assert_eq!(ChineseCalendar::CHINA.leap_month(2023).unwrap(), Some(2));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ChineseCalendar {
+    // Local mean time of the reference meridian, in seconds east of Greenwich.
+    local: i64,
+    // The standard time zone, in seconds east of Greenwich, and the first year it applies.
+    standard: i64,
+    standard_since: i64,
+}
+
+impl ChineseCalendar {
+    /// Beijing, on local mean time until 1928 and UTC+8 since.
+    pub const CHINA: Self = Self {
+        local: 27940,
+        standard: 28800,
+        standard_since: 1929,
+    };
+
+    /// Kyoto, on local mean time until 1887.  Japan left the lunisolar calendar in 1873.
+    pub const JAPAN: Self = Self {
+        local: 32584,
+        standard: 32400,
+        standard_since: 1888,
+    };
+
+    /// Seoul, on local mean time until 1911.
+    pub const KOREA: Self = Self {
+        local: 30472,
+        standard: 32400,
+        standard_since: 1912,
+    };
+
+    /// The first day of the year that begins in Gregorian `year`.
+    pub fn new_year(&self, year: i64) -> Result<Geotime> {
+        self.to_geotime(ChineseDate::new(year, 1, false, 1))
+    }
+
+    /// The number of the month followed by a leap month in `year`, if any.
+    pub fn leap_month(&self, year: i64) -> Result<Option<u8>> {
+        let start = self.jdn(ChineseDate::new(year, 1, false, 1))?;
+        let end = self.jdn(ChineseDate::new(year + 1, 1, false, 1))?;
+        let mut day = start;
+        while day < end {
+            let date = self.date_at(day);
+            if date.leap {
+                return Ok(Some(date.month));
+            }
+            day = self.new_moon_on_or_after(day + 1);
+        }
+        Ok(None)
+    }
+
+    /// The number of days, 29 or 30, in a month.
+    pub fn days_in_month(&self, year: i64, month: u8, leap: bool) -> Result<u8> {
+        let start = self.jdn(ChineseDate::new(year, month, leap, 1))?;
+        Ok((self.new_moon_on_or_after(start + 1) - start) as u8)
+    }
+
+    pub fn to_geotime(&self, date: ChineseDate) -> Result<Geotime> {
+        Ok(from_jdn(self.jdn(date)?))
+    }
+
+    pub fn date(&self, time: Geotime) -> Result<ChineseDate> {
+        let jdn = jdn(time)?;
+        check_range(jdn)?;
+        Ok(self.date_at(jdn))
+    }
+
+    fn jdn(&self, date: ChineseDate) -> Result<i64> {
+        let invalid = || Error::InvalidInput(format!("no such Chinese date: {}", date));
+        if !YEARS.contains(&date.year) || date.month == 0 || date.month > 12 || date.day == 0 {
+            return Err(match YEARS.contains(&date.year) {
+                true => invalid(),
+                false => out_of_range(date.year),
+            });
+        }
+        let middle = epoch() + ((date.year + 2636) as f64 + 0.5) * MEAN_TROPICAL_YEAR;
+        let new_year = self.new_year_on_or_before(middle.floor() as i64);
+        let guess = self.new_moon_on_or_after(new_year + (date.month as i64 - 1) * 29);
+        let found = self.date_at(guess);
+        let start = match (found.month, found.leap) == (date.month, date.leap) {
+            true => guess,
+            false => self.new_moon_on_or_after(guess + 1),
+        };
+        let jdn = start + date.day as i64 - 1;
+        match self.date_at(jdn) == date {
+            true => Ok(jdn),
+            false => Err(invalid()),
+        }
+    }
+
+    fn date_at(&self, day: i64) -> ChineseDate {
+        let s1 = self.solstice_on_or_before(day);
+        let s2 = self.solstice_on_or_before(s1 + 370);
+        let m12 = self.new_moon_on_or_after(s1 + 1);
+        let next_m11 = self.new_moon_before(s2 + 1);
+        let m = self.new_moon_before(day + 1);
+        let leap_year = months_between(m12, next_m11) == 12;
+        let offset = (leap_year && self.prior_leap_month(m12, m)) as i64;
+        let month = (months_between(m12, m) - offset - 1).rem_euclid(12) + 1;
+        let leap = leap_year
+            && self.no_major_term(m)
+            && !self.prior_leap_month(m12, self.new_moon_before(m));
+        let elapsed =
+            (1.5 - month as f64 / 12.0 + (day as f64 - epoch()) / MEAN_TROPICAL_YEAR).floor();
+        ChineseDate::new(
+            elapsed as i64 - 2637,
+            month as u8,
+            leap,
+            (day - m + 1) as u8,
+        )
+    }
+
+    // The UTC offset, as a fraction of a day, in force on `day`.
+    fn offset(&self, day: i64) -> f64 {
+        let since = CalendarDate::new(self.standard_since, 1, 1).jdn(true);
+        let seconds = match day < since {
+            true => self.local,
+            false => self.standard,
+        };
+        seconds as f64 / 86400.0
+    }
+
+    // The Julian Date of local midnight at the start of `day`.
+    fn midnight(&self, day: i64) -> f64 {
+        day as f64 - 0.5 - self.offset(day)
+    }
+
+    // The local day on which the Julian Date `jd` falls.
+    fn day(&self, jd: f64) -> i64 {
+        let approximate = (jd + 0.5).floor() as i64;
+        (jd + 0.5 + self.offset(approximate)).floor() as i64
+    }
+
+    fn new_moon_on_or_after(&self, day: i64) -> i64 {
+        self.day(new_moon_at_or_after(self.midnight(day)))
+    }
+
+    fn new_moon_before(&self, day: i64) -> i64 {
+        self.day(new_moon_before(self.midnight(day)))
+    }
+
+    fn solstice_on_or_before(&self, day: i64) -> i64 {
+        self.day(solar_longitude_before(270.0, self.midnight(day + 1)))
+    }
+
+    // The major solar term (zhongqi) most recently passed at the start of `day`, numbered as the
+    // month it marks, so that the March equinox is term 2 and the winter solstice term 11.
+    fn major_term(&self, day: i64) -> i64 {
+        let longitude = solar_longitude(self.midnight(day));
+        (1 + (longitude / 30.0).floor() as i64).rem_euclid(12) + 1
+    }
+
+    // Whether the month beginning on `month` contains no major solar term.
+    fn no_major_term(&self, month: i64) -> bool {
+        self.major_term(month) == self.major_term(self.new_moon_on_or_after(month + 1))
+    }
+
+    // Whether there is a month without a major solar term from `start` to `month`, inclusive.
+    fn prior_leap_month(&self, start: i64, mut month: i64) -> bool {
+        while month >= start {
+            if self.no_major_term(month) {
+                return true;
+            }
+            month = self.new_moon_before(month);
+        }
+        false
+    }
+
+    // The new year of the suì (solstice-to-solstice year) containing `day`.
+    fn new_year_in_sui(&self, day: i64) -> i64 {
+        let s1 = self.solstice_on_or_before(day);
+        let s2 = self.solstice_on_or_before(s1 + 370);
+        let m12 = self.new_moon_on_or_after(s1 + 1);
+        let m13 = self.new_moon_on_or_after(m12 + 1);
+        let next_m11 = self.new_moon_before(s2 + 1);
+        match months_between(m12, next_m11) == 12
+            && (self.no_major_term(m12) || self.no_major_term(m13))
+        {
+            true => self.new_moon_on_or_after(m13 + 1),
+            false => m13,
+        }
+    }
+
+    fn new_year_on_or_before(&self, day: i64) -> i64 {
+        let new_year = self.new_year_in_sui(day);
+        match day >= new_year {
+            true => new_year,
+            false => self.new_year_in_sui(day - 180),
+        }
+    }
+}
+
+// Julian Day Number of the traditional epoch, the new year of 2637 BC.
+fn epoch() -> f64 {
+    CalendarDate::new(-2636, 2, 15).jdn(true) as f64
+}
+
+fn months_between(start: i64, end: i64) -> i64 {
+    ((end - start) as f64 / MEAN_SYNODIC_MONTH).round() as i64
+}
+
+fn out_of_range(year: i64) -> Error {
+    Error::OutOfRange(format!(
+        "year {} is outside the range of the Chinese calendar",
+        year
+    ))
+}
+
+fn check_range(jdn: i64) -> Result<()> {
+    let year = CalendarDate::from_jdn(jdn, true).year;
+    match YEARS.contains(&year) {
+        true => Ok(()),
+        false => Err(out_of_range(year)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReformCalendar;
+
+    fn gregorian(date: &str) -> Geotime {
+        ReformCalendar::GREGORIAN.parse(date).unwrap()
+    }
+
+    #[test]
+    fn new_years() {
+        let china = ChineseCalendar::CHINA;
+        for (year, date) in [
+            (1900, "31 January 1900"),
+            (1949, "29 January 1949"),
+            (1985, "20 February 1985"),
+            (2000, "5 February 2000"),
+            (2020, "25 January 2020"),
+            (2023, "22 January 2023"),
+            (2025, "29 January 2025"),
+            (2030, "3 February 2030"),
+        ] {
+            assert_eq!(china.new_year(year).unwrap(), gregorian(date), "{}", year);
+        }
+    }
+
+    #[test]
+    fn leap_months() {
+        let china = ChineseCalendar::CHINA;
+        for (year, leap) in [
+            (2017, Some(6)),
+            (2018, None),
+            (2020, Some(4)),
+            (2023, Some(2)),
+            (2025, Some(6)),
+            (2028, Some(5)),
+        ] {
+            assert_eq!(china.leap_month(year).unwrap(), leap, "{}", year);
+        }
+        let date = ChineseDate::new(2023, 2, true, 1);
+        let t = china.to_geotime(date).unwrap();
+        assert_eq!(t, gregorian("22 March 2023"));
+        assert_eq!(china.date(t).unwrap(), date);
+        assert_eq!(china.days_in_month(2023, 2, true).unwrap(), 29);
+        assert!(china
+            .to_geotime(ChineseDate::new(2023, 3, true, 1))
+            .is_err());
+        assert!(china
+            .to_geotime(ChineseDate::new(2023, 2, true, 30))
+            .is_err());
+    }
+
+    #[test]
+    fn mid_autumn() {
+        let china = ChineseCalendar::CHINA;
+        let t = china
+            .to_geotime(ChineseDate::new(2024, 8, false, 15))
+            .unwrap();
+        assert_eq!(t, gregorian("17 September 2024"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let china = ChineseCalendar::CHINA;
+        let mut jdn = CalendarDate::new(1850, 1, 1).jdn(true);
+        while jdn < CalendarDate::new(2100, 1, 1).jdn(true) {
+            let date = china.date_at(jdn);
+            assert_eq!(china.jdn(date).unwrap(), jdn, "{}", date);
+            jdn += 47;
+        }
+    }
+
+    #[test]
+    fn early_dates() {
+        // Ancient dates, far from where chrono or the series are tested, still convert.
+        let china = ChineseCalendar::CHINA;
+        let t = china.new_year(-139).unwrap();
+        let date = china.date(t).unwrap();
+        assert_eq!(date, ChineseDate::new(-139, 1, false, 1));
+        assert!(china.new_year(-6000).is_err());
+    }
+
+    #[test]
+    fn sexagenary() {
+        assert_eq!(Sexagenary::of_year(1984).index(), 0);
+        assert_eq!(Sexagenary::of_year(1984).chinese(), "甲子");
+        assert_eq!(Sexagenary::of_year(1983).to_string(), "Guihai");
+        assert_eq!(Sexagenary::new(-1), Sexagenary::new(59));
+        // 1 October 1949 was a jiazi day.
+        assert_eq!(
+            Sexagenary::of_day(gregorian("1 October 1949"))
+                .unwrap()
+                .index(),
+            0
+        );
+    }
+}
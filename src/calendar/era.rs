@@ -0,0 +1,362 @@
+//! Era notation for years: BCE/CE, BC/AD, astronomical numbering and the Holocene Era.
+use std::fmt;
+
+use super::{from_jdn, jdn, CalendarDate};
+use crate::{Error, GeoInterval, Geotime, Result};
+
+// Astronomical year 1 - HOLOCENE is 1 HE.
+const HOLOCENE: i64 = 10000;
+
+/// A system for numbering years.  Years are passed to and returned from these methods in
+/// astronomical numbering, in which 1 BC is year 0 and 2 BC is year -1; this is what
+/// [`CalendarDate`] and chrono use.
+///
+/// - `BceCe`: "500 BCE", "2024 CE".
+/// - `BcAd`: "500 BC", "AD 2024".  There is no year zero.
+/// - `Astronomical`: "-499", "2024".
+/// - `Holocene`: "9501 HE", "12024 HE", counting from 10,000 BCE.  Earlier years are "BHE".
+///
+/// Centuries and millennia are ordinal and count from year 1 of the era, so the 21st century
+/// runs from 2001 to 2100.  Astronomical numbering has no ordinal centuries of its own, and uses
+/// BCE and CE for them.
+///
+/// ```
+/// use geotime::Era;
+///
+/// assert_eq!(Era::BceCe.format_year(-499), "500 BCE");
+/// assert_eq!(Era::BcAd.format_year(2024), "AD 2024");
+/// assert_eq!(Era::Astronomical.format_year(-499), "-499");
+/// assert_eq!(Era::Holocene.format_year(2024), "12024 HE");
+/// assert_eq!(Era::BceCe.format_millennium(-2500), "3rd millennium BCE");
+/// assert_eq!(Era::BcAd.format_century(1900), "19th century AD");
+///
+/// assert_eq!(Era::parse_year("44 BC").unwrap(), -43);
+/// assert_eq!(Era::parse_year("12024 HE").unwrap(), 2024);
+///
+/// let millennium = Era::parse_period("3rd millennium BCE").unwrap();
+/// assert_eq!(Era::BceCe.format(millennium.start().time()), "3000 BCE");
+/// assert_eq!(Era::BceCe.format(millennium.end().time()), "2000 BCE");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Era {
+    #[default]
+    BceCe,
+    BcAd,
+    Astronomical,
+    Holocene,
+}
+
+impl Era {
+    /// The year written in this era.
+    pub fn format_year(&self, year: i64) -> String {
+        // Widened so that the earliest years cannot overflow.
+        let year = year as i128;
+        match self {
+            Era::BceCe if year > 0 => format!("{} CE", year),
+            Era::BceCe => format!("{} BCE", 1 - year),
+            Era::BcAd if year > 0 => format!("AD {}", year),
+            Era::BcAd => format!("{} BC", 1 - year),
+            Era::Astronomical => year.to_string(),
+            Era::Holocene if year + HOLOCENE as i128 > 0 => {
+                format!("{} HE", year + HOLOCENE as i128)
+            }
+            Era::Holocene => format!("{} BHE", 1 - year - HOLOCENE as i128),
+        }
+    }
+
+    /// The century containing the year, such as "5th century BCE".
+    pub fn format_century(&self, year: i64) -> String {
+        self.format_ordinal(year, 100, "century")
+    }
+
+    /// The millennium containing the year, such as "3rd millennium BCE".
+    pub fn format_millennium(&self, year: i64) -> String {
+        self.format_ordinal(year, 1000, "millennium")
+    }
+
+    /// The proleptic Gregorian year of `time`, as chrono would give it, written in this era.
+    /// Times too far away for a calendar date are written in years before present, or after it.
+    pub fn format(&self, time: Geotime) -> String {
+        match jdn(time) {
+            Ok(jdn) => self.format_year(CalendarDate::from_jdn(jdn, true).year),
+            Err(_) => match time.to_years_bp() {
+                years if years >= 0.0 => format!("{:.0} years BP", years),
+                years => format!("{:.0} years after present", -years),
+            },
+        }
+    }
+
+    /// A Julian or Gregorian date written in this era, such as "15 March 44 BC".
+    pub fn format_date(&self, date: CalendarDate) -> String {
+        format!(
+            "{} {} {}",
+            date.day,
+            date.month_name(),
+            self.format_year(date.year)
+        )
+    }
+
+    fn format_ordinal(&self, year: i64, size: i128, unit: &str) -> String {
+        let (year, holocene) = (year as i128, HOLOCENE as i128);
+        let (count, label) = match self {
+            Era::Holocene if year + holocene > 0 => (year + holocene, "HE"),
+            Era::Holocene => (1 - year - holocene, "BHE"),
+            Era::BcAd if year > 0 => (year, "AD"),
+            Era::BcAd => (1 - year, "BC"),
+            _ if year > 0 => (year, "CE"),
+            _ => (1 - year, "BCE"),
+        };
+        format!("{} {} {}", ordinal((count - 1) / size + 1), unit, label)
+    }
+
+    /// Parses a year written in any of the eras, such as "44 BC", "AD 2024", "500 BCE",
+    /// "12024 HE" or "-499", into astronomical numbering.  A year with no label is astronomical.
+    pub fn parse_year(input: &str) -> Result<i64> {
+        let invalid = || Error::InvalidInput(format!("unable to parse year: {}", input));
+        let (number, label) = split_label(input).ok_or_else(invalid)?;
+        let number: i64 = number.parse().map_err(|_| invalid())?;
+        if label.is_some() && number < 1 {
+            return Err(invalid());
+        }
+        from_era(number, label).ok_or_else(|| out_of_range(input))
+    }
+
+    /// Parses a year, century or millennium, such as "44 BC", "5th century BCE" or "3rd
+    /// millennium", into the interval it spans, from 1 January of its first year to 1 January of
+    /// the year after it in the proleptic Gregorian calendar.
+    pub fn parse_period(input: &str) -> Result<GeoInterval> {
+        let invalid = || Error::InvalidInput(format!("unable to parse period: {}", input));
+        let (text, label) = split_label(input).ok_or_else(invalid)?;
+        let (first, last) = match text.split_whitespace().collect::<Vec<_>>()[..] {
+            [_] => {
+                let year = Self::parse_year(input)?;
+                (year, year)
+            }
+            [n, unit] => {
+                let size = match unit.to_lowercase().as_str() {
+                    "century" => 100,
+                    "millennium" => 1000,
+                    _ => return Err(invalid()),
+                };
+                let text = n.to_lowercase();
+                let digits = text.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+                let n: i64 = digits.parse().map_err(|_| invalid())?;
+                if n < 1 || ordinal(n as i128) != text {
+                    return Err(invalid());
+                }
+                // The first year cannot overflow if the last does not.
+                let years = n.checked_mul(size).and_then(|b| {
+                    Some((from_era((n - 1) * size + 1, label)?, from_era(b, label)?))
+                });
+                let (a, b) = years.ok_or_else(|| out_of_range(input))?;
+                (a.min(b), a.max(b))
+            }
+            _ => return Err(invalid()),
+        };
+        let end = last.checked_add(1).ok_or_else(|| out_of_range(input))?;
+        GeoInterval::new(new_year(first)?.into(), new_year(end)?.into())
+    }
+}
+
+impl fmt::Display for Era {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Era::BceCe => "BCE/CE",
+            Era::BcAd => "BC/AD",
+            Era::Astronomical => "astronomical",
+            Era::Holocene => "HE",
+        })
+    }
+}
+
+// An era label written with a year.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Label {
+    Before,
+    After,
+    Holocene,
+    BeforeHolocene,
+}
+
+// Separates the era label, written before or after a year, from the rest of the text.
+fn split_label(input: &str) -> Option<(String, Option<Label>)> {
+    let mut tokens: Vec<String> = input
+        .split_whitespace()
+        .map(|t| t.replace('.', ""))
+        .filter(|t| !t.is_empty())
+        .collect();
+    let label = |token: &str| match token.to_uppercase().as_str() {
+        "BC" | "BCE" => Some(Label::Before),
+        "AD" | "CE" => Some(Label::After),
+        "HE" => Some(Label::Holocene),
+        "BHE" => Some(Label::BeforeHolocene),
+        _ => None,
+    };
+    let found = match (
+        tokens.first().map(|t| label(t)),
+        tokens.last().map(|t| label(t)),
+    ) {
+        (_, Some(Some(found))) => {
+            tokens.pop();
+            Some(found)
+        }
+        (Some(Some(Label::After)), _) => {
+            tokens.remove(0);
+            Some(Label::After)
+        }
+        _ => None,
+    };
+    match tokens.is_empty() {
+        true => None,
+        false => Some((tokens.join(" "), found)),
+    }
+}
+
+// The astronomical year of year `number` counted in the era of `label`, or `None` if it does not
+// fit in an `i64`.
+fn from_era(number: i64, label: Option<Label>) -> Option<i64> {
+    match label {
+        Some(Label::Before) => 1i64.checked_sub(number),
+        Some(Label::Holocene) => number.checked_sub(HOLOCENE),
+        Some(Label::BeforeHolocene) => (1 - HOLOCENE).checked_sub(number),
+        Some(Label::After) | None => Some(number),
+    }
+}
+
+fn out_of_range(input: &str) -> Error {
+    Error::OutOfRange(format!("{} is too far from the present", input))
+}
+
+fn new_year(year: i64) -> Result<Geotime> {
+    let date = CalendarDate::new(year, 1, 1);
+    date.check_year()?;
+    let time = from_jdn(date.jdn(true));
+    jdn(time)?;
+    Ok(time)
+}
+
+fn ordinal(n: i128) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn years() {
+        for (year, written) in [
+            (2024, ["2024 CE", "AD 2024", "2024", "12024 HE"]),
+            (1, ["1 CE", "AD 1", "1", "10001 HE"]),
+            (0, ["1 BCE", "1 BC", "0", "10000 HE"]),
+            (-499, ["500 BCE", "500 BC", "-499", "9501 HE"]),
+            (-9999, ["10000 BCE", "10000 BC", "-9999", "1 HE"]),
+            (-10000, ["10001 BCE", "10001 BC", "-10000", "1 BHE"]),
+        ] {
+            for (era, text) in [Era::BceCe, Era::BcAd, Era::Astronomical, Era::Holocene]
+                .iter()
+                .zip(written)
+            {
+                assert_eq!(era.format_year(year), text);
+                assert_eq!(Era::parse_year(text).unwrap(), year, "{}", text);
+            }
+        }
+        assert_eq!(Era::parse_year("A.D. 1066").unwrap(), 1066);
+        assert_eq!(Era::parse_year("753 B.C.").unwrap(), -752);
+        assert!(Era::parse_year("0 BC").is_err());
+        assert!(Era::parse_year("BC").is_err());
+        assert!(Era::parse_year("fourty BC").is_err());
+    }
+
+    #[test]
+    fn ordinals() {
+        assert_eq!(Era::BceCe.format_century(2000), "20th century CE");
+        assert_eq!(Era::BceCe.format_century(2001), "21st century CE");
+        assert_eq!(Era::BcAd.format_century(-99), "1st century BC");
+        assert_eq!(Era::BcAd.format_century(-100), "2nd century BC");
+        assert_eq!(Era::Astronomical.format_century(-1111), "12th century BCE");
+        assert_eq!(Era::Holocene.format_millennium(2024), "13th millennium HE");
+        assert_eq!(
+            Era::BceCe.format_millennium(-110_000),
+            "111th millennium BCE"
+        );
+    }
+
+    #[test]
+    fn periods() {
+        let era = Era::BceCe;
+        let century = Era::parse_period("5th century BC").unwrap();
+        assert_eq!(era.format(century.start().time()), "500 BCE");
+        assert_eq!(era.format(century.end().time()), "400 BCE");
+        let century = Era::parse_period("21st Century").unwrap();
+        assert_eq!(era.format(century.start().time()), "2001 CE");
+        assert_eq!(era.format(century.end().time()), "2101 CE");
+        let year = Era::parse_period("1 BC").unwrap();
+        assert_eq!(era.format(year.start().time()), "1 BCE");
+        assert_eq!(era.format(year.end().time()), "1 CE");
+        assert_eq!(
+            Era::parse_period("12th millennium HE").unwrap(),
+            Era::parse_period("2nd millennium CE").unwrap()
+        );
+        assert!(Era::parse_period("3th century").is_err());
+        assert!(Era::parse_period("3rd decade").is_err());
+    }
+
+    #[test]
+    fn extremes() {
+        assert_eq!(Era::BceCe.format_year(i64::MIN), "9223372036854775809 BCE");
+        assert_eq!(
+            Era::Holocene.format_year(i64::MAX),
+            "9223372036854785807 HE"
+        );
+        assert_eq!(
+            Era::Holocene.format_century(i64::MIN),
+            "92233720368547659th century BHE"
+        );
+        for input in ["9223372036854775807 BHE", "9223372036854765810 BHE"] {
+            assert!(matches!(Era::parse_year(input), Err(Error::OutOfRange(_))));
+        }
+        assert_eq!(
+            Era::parse_year("9223372036854765808 BHE").unwrap(),
+            i64::MIN + 1
+        );
+        assert_eq!(
+            Era::parse_year("9223372036854775807 BC").unwrap(),
+            1 - i64::MAX
+        );
+        for input in [
+            "99999999999999999th millennium",
+            "9223372036854775807 BCE",
+            "9223372036854775807",
+            "1000000000000000 AD",
+        ] {
+            assert!(
+                matches!(Era::parse_period(input), Err(Error::OutOfRange(_))),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(Era::BcAd.format(Geotime::from(0)), "AD 1970");
+        assert_eq!(Era::Holocene.format(Geotime::from_ma(0.5)), "488062 BHE");
+        assert_eq!(
+            Era::BceCe.format(Geotime::from_ma(1e9)),
+            "1000000000000000 years BP"
+        );
+        assert!(Era::BceCe
+            .format(Geotime::from(i128::MAX))
+            .ends_with("years after present"));
+        let ides = CalendarDate::new(-43, 3, 15);
+        assert_eq!(Era::BceCe.format_date(ides), "15 March 44 BCE");
+    }
+}
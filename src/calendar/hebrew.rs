@@ -0,0 +1,250 @@
+//! The Hebrew calendar.
+use super::{check, from_jdn, jdn, Calendar, CalendarDate};
+use crate::{Geotime, Result};
+
+// Julian Day Number of 1 Tishri AM 1, 7 October 3761 BC (Julian).
+const EPOCH: i64 = 347998;
+
+// Month names and lengths from Tishri, in common and leap years.  Heshvan and Kislev vary.
+#[rustfmt::skip]
+const COMMON: [(&str, u8); 12] = [
+    ("Tishri", 30), ("Heshvan", 29), ("Kislev", 30), ("Tevet", 29), ("Shevat", 30), ("Adar", 29),
+    ("Nisan", 30), ("Iyar", 29), ("Sivan", 30), ("Tammuz", 29), ("Av", 30), ("Elul", 29),
+];
+
+#[rustfmt::skip]
+const LEAP: [(&str, u8); 13] = [
+    ("Tishri", 30), ("Heshvan", 29), ("Kislev", 30), ("Tevet", 29), ("Shevat", 30),
+    ("Adar I", 30), ("Adar II", 29), ("Nisan", 30), ("Iyar", 29), ("Sivan", 30), ("Tammuz", 29),
+    ("Av", 30), ("Elul", 29),
+];
+
+#[rustfmt::skip]
+const DAYS: [&str; 7] = [
+    "Yom Rishon", "Yom Sheni", "Yom Shlishi", "Yom Revi'i", "Yom Chamishi", "Yom Shishi",
+    "Shabbat",
+];
+
+/// The Hebrew calendar, a lunisolar calendar with 7 leap months in a 19-year cycle.  The year
+/// begins on 1 Tishri, fixed from the molad (mean conjunction) of Tishri and the postponement
+/// rules (dehiyyot), following Reingold and Dershowitz, *Calendrical Calculations*.  Months are
+/// numbered from Tishri, so Nisan is month 7 in a common year and month 8 in a leap year.
+///
+/// ```
+/// use geotime::{Calendar, HebrewCalendar, ReformCalendar};
+///
+/// let rosh_hashanah = HebrewCalendar.parse("1 Tishri 5784").unwrap();
+/// assert_eq!(ReformCalendar::GREGORIAN.format(rosh_hashanah).unwrap(), "16 September 2023");
+/// assert_eq!(HebrewCalendar.day_name(rosh_hashanah).unwrap(), "Shabbat");
+/// assert!(HebrewCalendar.is_leap_year(5784));
+/// assert_eq!(HebrewCalendar.days_in_year(5784), 383);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct HebrewCalendar;
+
+impl HebrewCalendar {
+    pub fn is_leap_year(&self, year: i64) -> bool {
+        (7 * year.rem_euclid(19) + 1).rem_euclid(19) < 7
+    }
+
+    /// The number of days in the year: 353 to 355 in a common year, and 383 to 385 in a leap
+    /// year.
+    pub fn days_in_year(&self, year: i64) -> i64 {
+        new_year(year + 1) - new_year(year)
+    }
+
+    fn months(&self, year: i64) -> &'static [(&'static str, u8)] {
+        match self.is_leap_year(year) {
+            true => &LEAP,
+            false => &COMMON,
+        }
+    }
+
+    fn jdn(&self, date: CalendarDate) -> i64 {
+        let before: i64 = (1..date.month)
+            .map(|m| self.days_in_month(date.year, m) as i64)
+            .sum();
+        new_year(date.year) + before + date.day as i64 - 1
+    }
+}
+
+// Days from the epoch to the molad of Tishri of `year`, delayed a day when it falls on a Sunday,
+// Wednesday or Friday.
+fn elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12084 + 13753 * months as i128;
+    let days = 29 * months + parts.div_euclid(25920) as i64;
+    match (3 * (days + 1)).rem_euclid(7) < 3 {
+        true => days + 1,
+        false => days,
+    }
+}
+
+// The further delay that keeps every year within the permitted lengths.
+fn new_year_delay(year: i64) -> i64 {
+    let (last, this, next) = (
+        elapsed_days(year - 1),
+        elapsed_days(year),
+        elapsed_days(year + 1),
+    );
+    if next - this == 356 {
+        2
+    } else if this - last == 382 {
+        1
+    } else {
+        0
+    }
+}
+
+// Julian Day Number of 1 Tishri of `year`.
+fn new_year(year: i64) -> i64 {
+    EPOCH + elapsed_days(year) + new_year_delay(year)
+}
+
+impl Calendar for HebrewCalendar {
+    fn era(&self) -> &'static str {
+        "AM"
+    }
+
+    fn to_geotime(&self, date: CalendarDate) -> Result<Geotime> {
+        check(self, date)?;
+        Ok(from_jdn(self.jdn(date)))
+    }
+
+    fn date(&self, time: Geotime) -> Result<CalendarDate> {
+        let jdn = jdn(time)?;
+        let mut year = ((jdn - EPOCH) as i128 * 98496).div_euclid(35975351) as i64;
+        while new_year(year + 1) <= jdn {
+            year += 1;
+        }
+        let mut day = jdn - new_year(year);
+        let mut month = 1;
+        loop {
+            let length = self.days_in_month(year, month) as i64;
+            if day < length {
+                let date = CalendarDate::new(year, month, day as u8 + 1);
+                date.check_year()?;
+                return Ok(date);
+            }
+            day -= length;
+            month += 1;
+        }
+    }
+
+    fn months_in_year(&self, year: i64) -> u8 {
+        self.months(year).len() as u8
+    }
+
+    fn days_in_month(&self, year: i64, month: u8) -> u8 {
+        let days = self.days_in_year(year);
+        match self.months(year).get((month as usize).wrapping_sub(1)) {
+            // Heshvan is long, and Kislev short, in complete and deficient years respectively.
+            Some(_) if month == 2 && days % 10 == 5 => 30,
+            Some(_) if month == 3 && days % 10 == 3 => 29,
+            Some((_, length)) => *length,
+            None => 0,
+        }
+    }
+
+    fn month_name(&self, year: i64, month: u8) -> Option<&'static str> {
+        self.months(year)
+            .get((month as usize).wrapping_sub(1))
+            .map(|(name, _)| *name)
+    }
+
+    fn day_names(&self) -> [&'static str; 7] {
+        DAYS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReformCalendar;
+
+    #[test]
+    fn known_dates() {
+        let gregorian = ReformCalendar::GREGORIAN;
+        for (hebrew, date) in [
+            ("1 Tishri 5785", "3 October 2024"),
+            ("15 Nisan 5783", "6 April 2023"),
+            ("14 Adar II 5784", "24 March 2024"),
+            ("25 Kislev 5784", "8 December 2023"),
+            ("1 Tishri 5760", "11 September 1999"),
+        ] {
+            let t = HebrewCalendar.parse(hebrew).unwrap();
+            assert_eq!(gregorian.format(t).unwrap(), date, "{}", hebrew);
+            assert_eq!(HebrewCalendar.format(t).unwrap(), format!("{} AM", hebrew));
+        }
+        assert_eq!(
+            HebrewCalendar.parse("5783-07-15").unwrap(),
+            HebrewCalendar.parse("15 Nisan 5783").unwrap()
+        );
+        assert!(HebrewCalendar.parse("1 Adar II 5783").is_err());
+        assert_eq!(
+            HebrewCalendar.parse("1 Adar 5784").unwrap(),
+            HebrewCalendar.parse("1 Adar I 5784").unwrap()
+        );
+        assert_eq!(
+            HebrewCalendar
+                .date(HebrewCalendar.parse("1 Tishri 1").unwrap())
+                .unwrap(),
+            CalendarDate::new(1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn year_lengths() {
+        for year in 5000..6000 {
+            let days = HebrewCalendar.days_in_year(year);
+            let lengths = match HebrewCalendar.is_leap_year(year) {
+                true => [383, 384, 385],
+                false => [353, 354, 355],
+            };
+            assert!(lengths.contains(&days), "{} has {} days", year, days);
+            let sum: i64 = (1..=HebrewCalendar.months_in_year(year))
+                .map(|m| HebrewCalendar.days_in_month(year, m) as i64)
+                .sum();
+            assert_eq!(sum, days);
+            // Rosh Hashanah never falls on a Sunday, Wednesday or Friday.
+            assert!(![0, 3, 5].contains(&(new_year(year) + 1).rem_euclid(7)));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut jdn = EPOCH - 10_000;
+        while jdn < EPOCH + 2_500_000 {
+            let t = from_jdn(jdn);
+            let date = HebrewCalendar.date(t).unwrap();
+            assert_eq!(HebrewCalendar.to_geotime(date).unwrap(), t, "{:?}", date);
+            jdn += 101;
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        for input in [
+            "1 Tishri 99999999999999999",
+            "1 Tishri -9223372036854775808",
+        ] {
+            assert!(matches!(
+                HebrewCalendar.parse(input),
+                Err(crate::Error::OutOfRange(_))
+            ));
+        }
+        for years in [-1e12, 1e12] {
+            let t = Geotime::from_years_bp(years);
+            let date = HebrewCalendar.date(t).unwrap();
+            let start = HebrewCalendar.to_geotime(date).unwrap();
+            assert!(start <= t && t.millis() - start.millis() < crate::MILLISECONDS_IN_DAY);
+        }
+        for jdn in [1 - i64::MAX / 1000, i64::MAX / 1000 - 1] {
+            assert!(matches!(
+                HebrewCalendar.date(from_jdn(jdn)),
+                Err(crate::Error::OutOfRange(_))
+            ));
+        }
+        assert!(HebrewCalendar.date(Geotime::from(i128::MAX)).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! The tabular Islamic calendar.
+use super::{check, from_jdn, jdn, Calendar, CalendarDate};
+use crate::{Geotime, Result};
+
+// Julian Day Number of 1 Muharram AH 1, 16 July 622 (Julian), in the civil reckoning.
+const EPOCH: i64 = 1948440;
+
+#[rustfmt::skip]
+const MONTHS: [&str; 12] = [
+    "Muharram", "Safar", "Rabi' al-Awwal", "Rabi' al-Thani", "Jumada al-Awwal", "Jumada al-Thani",
+    "Rajab", "Sha'ban", "Ramadan", "Shawwal", "Dhu al-Qa'dah", "Dhu al-Hijjah",
+];
+
+#[rustfmt::skip]
+const DAYS: [&str; 7] = [
+    "al-Ahad", "al-Ithnayn", "ath-Thulatha'", "al-Arba'a'", "al-Khamis", "al-Jumu'ah", "as-Sabt",
+];
+
+/// The tabular (arithmetic) Islamic calendar, with the civil epoch and the common cycle of 11
+/// leap years in 30, as given by Reingold and Dershowitz, *Calendrical Calculations*.  Months
+/// alternate between 30 and 29 days, and the last month has 30 days in leap years.  Observed
+/// dates, which depend on sighting the new crescent, can differ by a day or two.
+///
+/// ```
+/// use geotime::{Calendar, CalendarDate, IslamicCalendar, ReformCalendar};
+///
+/// let hijra = IslamicCalendar.to_geotime(CalendarDate::new(1, 1, 1)).unwrap();
+/// assert_eq!(ReformCalendar::JULIAN.format(hijra).unwrap(), "16 July 622");
+/// assert_eq!(IslamicCalendar.day_name(hijra).unwrap(), "al-Jumu'ah");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IslamicCalendar;
+
+impl IslamicCalendar {
+    pub fn is_leap_year(&self, year: i64) -> bool {
+        (14 + 11 * year.rem_euclid(30)).rem_euclid(30) < 11
+    }
+
+    fn jdn(&self, date: CalendarDate) -> i64 {
+        let (year, month) = (date.year, date.month as i64);
+        EPOCH - 1
+            + (year - 1) * 354
+            + (3 + 11 * year).div_euclid(30)
+            + 29 * (month - 1)
+            + month / 2
+            + date.day as i64
+    }
+}
+
+impl Calendar for IslamicCalendar {
+    fn era(&self) -> &'static str {
+        "AH"
+    }
+
+    fn to_geotime(&self, date: CalendarDate) -> Result<Geotime> {
+        check(self, date)?;
+        Ok(from_jdn(self.jdn(date)))
+    }
+
+    fn date(&self, time: Geotime) -> Result<CalendarDate> {
+        let jdn = jdn(time)?;
+        let year = (30 * (jdn - EPOCH) + 10646).div_euclid(10631);
+        let prior = jdn - self.jdn(CalendarDate::new(year, 1, 1));
+        let month = (11 * prior + 330).div_euclid(325) as u8;
+        let day = jdn - self.jdn(CalendarDate::new(year, month, 1)) + 1;
+        let date = CalendarDate::new(year, month, day as u8);
+        date.check_year()?;
+        Ok(date)
+    }
+
+    fn months_in_year(&self, _year: i64) -> u8 {
+        12
+    }
+
+    fn days_in_month(&self, year: i64, month: u8) -> u8 {
+        match month {
+            12 if self.is_leap_year(year) => 30,
+            m => 30 - (m + 1) % 2,
+        }
+    }
+
+    fn month_name(&self, _year: i64, month: u8) -> Option<&'static str> {
+        MONTHS.get((month as usize).wrapping_sub(1)).copied()
+    }
+
+    fn day_names(&self) -> [&'static str; 7] {
+        DAYS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReformCalendar;
+
+    #[test]
+    fn known_dates() {
+        let gregorian = ReformCalendar::GREGORIAN;
+        let t = IslamicCalendar.parse("1 Ramadan 1444 AH").unwrap();
+        assert_eq!(gregorian.format(t).unwrap(), "23 March 2023");
+        let t = IslamicCalendar.parse("1 Muharram 1445").unwrap();
+        assert_eq!(gregorian.format(t).unwrap(), "19 July 2023");
+        assert_eq!(IslamicCalendar.parse("1445-01-01").unwrap(), t);
+        assert_eq!(
+            IslamicCalendar.parse("1 rabi al-awwal 1445").unwrap(),
+            IslamicCalendar.parse("1 Rabi' al-Awwal 1445 AH").unwrap()
+        );
+        assert!(IslamicCalendar.parse("30 Safar 1445").is_err());
+    }
+
+    #[test]
+    fn cycle() {
+        let leap: Vec<i64> = (1..=30)
+            .filter(|&y| IslamicCalendar.is_leap_year(y))
+            .collect();
+        assert_eq!(leap, vec![2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29]);
+
+        let start = IslamicCalendar
+            .to_geotime(CalendarDate::new(1401, 1, 1))
+            .unwrap();
+        let end = IslamicCalendar
+            .to_geotime(CalendarDate::new(1431, 1, 1))
+            .unwrap();
+        assert_eq!((end.millis() - start.millis()) / 86_400_000, 10631);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut jdn = EPOCH - 100_000;
+        while jdn < EPOCH + 1_000_000 {
+            let t = from_jdn(jdn);
+            let date = IslamicCalendar.date(t).unwrap();
+            assert_eq!(IslamicCalendar.to_geotime(date).unwrap(), t, "{:?}", date);
+            jdn += 37;
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        for input in [
+            "1 Muharram 99999999999999999",
+            "1 Muharram -9223372036854775808",
+        ] {
+            assert!(matches!(
+                IslamicCalendar.parse(input),
+                Err(crate::Error::OutOfRange(_))
+            ));
+        }
+        for years in [-1e12, 1e12] {
+            let t = Geotime::from_years_bp(years);
+            let date = IslamicCalendar.date(t).unwrap();
+            let start = IslamicCalendar.to_geotime(date).unwrap();
+            assert!(start <= t && t.millis() - start.millis() < crate::MILLISECONDS_IN_DAY);
+        }
+        for jdn in [1 - i64::MAX / 1000, i64::MAX / 1000 - 1] {
+            assert!(matches!(
+                IslamicCalendar.date(from_jdn(jdn)),
+                Err(crate::Error::OutOfRange(_))
+            ));
+        }
+        assert!(IslamicCalendar.date(Geotime::from(i128::MAX)).is_err());
+    }
+}
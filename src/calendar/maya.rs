@@ -0,0 +1,520 @@
+//! The Maya Long Count and the Calendar Round.
+use std::fmt;
+use std::str::FromStr;
+
+use super::{normalize, UNIX_EPOCH_JDN};
+use crate::{Error, Geotime, Result, MILLISECONDS_IN_DAY};
+
+// The number of units of each place, counting from the kin, that make one of the next: 18
+// uinals make a tun, and 20 of every other.
+fn base(place: usize) -> i128 {
+    match place {
+        1 => 18,
+        _ => 20,
+    }
+}
+
+// Tzolk'in day names, with the older orthography as an alternative.
+#[rustfmt::skip]
+const DAY_NAMES: [(&str, &str); 20] = [
+    ("Imix", "Imix"), ("Ik'", "Ik"), ("Ak'bal", "Akbal"), ("K'an", "Kan"),
+    ("Chikchan", "Chicchan"), ("Kimi", "Cimi"), ("Manik'", "Manik"), ("Lamat", "Lamat"),
+    ("Muluk", "Muluc"), ("Ok", "Oc"), ("Chuwen", "Chuen"), ("Eb", "Eb"), ("Ben", "Ben"),
+    ("Ix", "Ix"), ("Men", "Men"), ("K'ib", "Cib"), ("Kaban", "Caban"), ("Etz'nab", "Etznab"),
+    ("Kawak", "Cauac"), ("Ajaw", "Ahau"),
+];
+
+// Haab' month names, with the older orthography as an alternative.
+#[rustfmt::skip]
+const MONTH_NAMES: [(&str, &str); 19] = [
+    ("Pop", "Pop"), ("Wo", "Uo"), ("Sip", "Zip"), ("Sotz'", "Zotz"), ("Sek", "Tzec"),
+    ("Xul", "Xul"), ("Yaxk'in", "Yaxkin"), ("Mol", "Mol"), ("Ch'en", "Chen"), ("Yax", "Yax"),
+    ("Sak'", "Zac"), ("Keh", "Ceh"), ("Mak", "Mac"), ("K'ank'in", "Kankin"), ("Muwan", "Muan"),
+    ("Pax", "Pax"), ("K'ayab", "Kayab"), ("Kumk'u", "Cumku"), ("Wayeb'", "Uayeb"),
+];
+
+// The Calendar Round of the creation date, 4 Ajaw 8 Kumk'u: positions in the 260-day and
+// 365-day cycles.
+const CREATION_TZOLKIN: i128 = 159;
+const CREATION_HAAB: i128 = 348;
+
+// Length of the Calendar Round, the least common multiple of 260 and 365.
+const CALENDAR_ROUND: i128 = 18980;
+
+fn position(names: &[(&str, &str)], input: &str) -> Option<usize> {
+    let input = normalize(input);
+    names
+        .iter()
+        .position(|(name, old)| normalize(name) == input || normalize(old) == input)
+}
+
+/// A correlation constant: the Julian Day Number of the Long Count creation date, 13.0.0.0.0
+/// 4 Ajaw 8 Kumk'u.
+///
+/// ```
+/// use geotime::{Calendar, LongCount, MayaCorrelation, ReformCalendar};
+///
+/// let date: LongCount = "9.12.11.5.18".parse().unwrap();
+/// let t = date.to_geotime(MayaCorrelation::GMT).unwrap();
+/// assert_eq!(ReformCalendar::JULIAN.format(t).unwrap(), "26 August 683");
+///
+/// let t = date.to_geotime(MayaCorrelation::SPINDEN).unwrap();
+/// assert_eq!(ReformCalendar::JULIAN.format(t).unwrap(), "31 October 423");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MayaCorrelation(pub i64);
+
+impl MayaCorrelation {
+    /// The Goodman–Martínez–Thompson correlation, the most widely used.
+    pub const GMT: Self = Self(584283);
+
+    /// Lounsbury's variant of the GMT correlation, two days later.
+    pub const LOUNSBURY: Self = Self(584285);
+
+    /// Spinden's correlation, about 260 years earlier than the GMT.
+    pub const SPINDEN: Self = Self(489384);
+}
+
+/// A date in the Long Count, a count of days from the creation date.  The five places are the
+/// baktun, katun, tun, uinal and kin; higher places (piktun, kalabtun, k'inchiltun, alawtun and
+/// beyond) are counted in twenties and written only when needed.  The creation date itself,
+/// written 13.0.0.0.0 in inscriptions, is 0.0.0.0.0 here.
+///
+/// ```
+/// use geotime::{LongCount, MayaCorrelation};
+///
+/// let end_of_baktun_13: LongCount = "13.0.0.0.0".parse().unwrap();
+/// assert_eq!(end_of_baktun_13.days(), 1_872_000);
+/// assert_eq!(end_of_baktun_13.calendar_round().to_string(), "4 Ajaw 3 K'ank'in");
+///
+/// let piktun = LongCount::from_days(20 * 144_000);
+/// assert_eq!(piktun.to_string(), "1.0.0.0.0.0");
+/// assert_eq!(piktun.places(), vec![1, 0, 0, 0, 0, 0]);
+///
+/// // Dates before the creation date are written with a leading minus sign.
+/// let before = LongCount::from_days(-21);
+/// assert_eq!(before.to_string(), "-0.0.0.1.1");
+/// assert_eq!("-0.0.0.1.1".parse::<LongCount>().unwrap(), before);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LongCount(i128);
+
+impl LongCount {
+    pub fn from_days(days: i128) -> Self {
+        Self(days)
+    }
+
+    /// The Long Count from its places, most significant first, with at least the five of the
+    /// baktun to the kin.
+    pub fn new(places: &[i64]) -> Result<Self> {
+        Self::signed(places, 1)
+    }
+
+    // The Long Count from its places, counted forward from the creation date when `sign` is 1
+    // and backward when it is -1.
+    fn signed(places: &[i64], sign: i128) -> Result<Self> {
+        if places.len() < 5 {
+            return Err(Error::InvalidInput(format!(
+                "a Long Count needs at least five places, not {}",
+                places.len()
+            )));
+        }
+        let mut days: i128 = 0;
+        let mut weight: i128 = 1;
+        for (k, &place) in places.iter().rev().enumerate() {
+            let base = base(k);
+            if place < 0 || place >= base as i64 {
+                return Err(Error::InvalidInput(format!(
+                    "Long Count place {} is out of range",
+                    place
+                )));
+            }
+            days = weight
+                .checked_mul(place as i128 * sign)
+                .and_then(|d| d.checked_add(days))
+                .ok_or_else(overflow)?;
+            if k + 1 < places.len() {
+                weight = weight.checked_mul(base).ok_or_else(overflow)?;
+            }
+        }
+        Ok(Self(days))
+    }
+
+    /// Days since the creation date.
+    pub fn days(&self) -> i128 {
+        self.0
+    }
+
+    /// The places, most significant first: the five from the baktun to the kin, preceded by any
+    /// higher places needed.  Dates before the creation date have negative places.
+    pub fn places(&self) -> Vec<i64> {
+        let sign = if self.0 < 0 { -1 } else { 1 };
+        let mut days = self.0.unsigned_abs();
+        let mut places = vec![];
+        for k in 0.. {
+            if k >= 5 && days == 0 {
+                break;
+            }
+            let base = base(k) as u128;
+            places.push((days % base) as i64 * sign);
+            days /= base;
+        }
+        places.reverse();
+        places
+    }
+
+    /// The Long Count containing `time` under `correlation`.
+    pub fn from_geotime(time: Geotime, correlation: MayaCorrelation) -> Self {
+        let days = time.millis().div_euclid(MILLISECONDS_IN_DAY);
+        Self(days + (UNIX_EPOCH_JDN - correlation.0) as i128)
+    }
+
+    /// The start of the day under `correlation`.
+    pub fn to_geotime(&self, correlation: MayaCorrelation) -> Result<Geotime> {
+        self.0
+            .checked_add((correlation.0 - UNIX_EPOCH_JDN) as i128)
+            .and_then(|days| days.checked_mul(MILLISECONDS_IN_DAY))
+            .map(Geotime::from)
+            .ok_or_else(overflow)
+    }
+
+    pub fn tzolkin(&self) -> Tzolkin {
+        let position = (self.0.rem_euclid(260) + CREATION_TZOLKIN).rem_euclid(260);
+        Tzolkin {
+            number: (position % 13) as u8 + 1,
+            name: (position % 20) as u8 + 1,
+        }
+    }
+
+    pub fn haab(&self) -> Haab {
+        let position = (self.0.rem_euclid(365) + CREATION_HAAB).rem_euclid(365);
+        Haab {
+            day: (position % 20) as u8,
+            month: (position / 20) as u8 + 1,
+        }
+    }
+
+    pub fn calendar_round(&self) -> CalendarRound {
+        CalendarRound {
+            tzolkin: self.tzolkin(),
+            haab: self.haab(),
+        }
+    }
+}
+
+fn overflow() -> Error {
+    Error::OutOfRange("Long Count is out of range".to_string())
+}
+
+impl fmt::Display for LongCount {
+    /// Formats the places separated by dots, as in "9.12.11.5.18".  Dates before the creation
+    /// date have a leading minus sign rather than negative places: "-0.0.0.1.1".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let places: Vec<String> = self.places().iter().map(|p| p.abs().to_string()).collect();
+        write!(f, "{}{}", sign, places.join("."))
+    }
+}
+
+impl FromStr for LongCount {
+    type Err = Error;
+
+    /// Parses places separated by dots, with a leading minus sign for dates before the
+    /// creation date.
+    fn from_str(s: &str) -> Result<Self> {
+        let (sign, count) = match s.trim().strip_prefix('-') {
+            Some(count) => (-1, count),
+            None => (1, s.trim()),
+        };
+        let places = count
+            .split('.')
+            .map(|p| p.trim().parse::<i64>())
+            .collect::<std::result::Result<Vec<i64>, _>>()
+            .map_err(|_| Error::InvalidInput(format!("unable to parse Long Count: {}", s)))?;
+        Self::signed(&places, sign)
+    }
+}
+
+/// A day in the 260-day Tzolk'in cycle: a number from 1 to 13 and one of 20 day names.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Tzolkin {
+    pub number: u8,
+    /// The day name, from 1 (Imix) to 20 (Ajaw).
+    pub name: u8,
+}
+
+impl Tzolkin {
+    pub fn name(&self) -> &'static str {
+        DAY_NAMES[(self.name.clamp(1, 20) - 1) as usize].0
+    }
+
+    // Days after 1 Imix, the first day of the cycle.
+    fn position(&self) -> i128 {
+        (0..260)
+            .find(|p| p % 13 + 1 == self.number as i128 && p % 20 + 1 == self.name as i128)
+            .unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Tzolkin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.number, self.name())
+    }
+}
+
+impl FromStr for Tzolkin {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("unable to parse Tzolk'in date: {}", s));
+        let (number, name) = s.trim().split_once(' ').ok_or_else(invalid)?;
+        let number: u8 = number.parse().map_err(|_| invalid())?;
+        let name = position(&DAY_NAMES, name).ok_or_else(invalid)?;
+        if !(1..=13).contains(&number) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            number,
+            name: name as u8 + 1,
+        })
+    }
+}
+
+/// A day in the 365-day Haab' year: a day from 0 to 19 in one of 18 months of 20 days, or from 0
+/// to 4 in the closing Wayeb'.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Haab {
+    pub day: u8,
+    /// The month, from 1 (Pop) to 19 (Wayeb').
+    pub month: u8,
+}
+
+impl Haab {
+    pub fn month_name(&self) -> &'static str {
+        MONTH_NAMES[(self.month.clamp(1, 19) - 1) as usize].0
+    }
+
+    fn position(&self) -> i128 {
+        (self.month as i128 - 1) * 20 + self.day as i128
+    }
+}
+
+impl fmt::Display for Haab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.day, self.month_name())
+    }
+}
+
+impl FromStr for Haab {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("unable to parse Haab' date: {}", s));
+        let (day, month) = s.trim().split_once(' ').ok_or_else(invalid)?;
+        let day: u8 = day.parse().map_err(|_| invalid())?;
+        let month = position(&MONTH_NAMES, month).ok_or_else(invalid)? as u8 + 1;
+        if day >= if month == 19 { 5 } else { 20 } {
+            return Err(invalid());
+        }
+        Ok(Self { day, month })
+    }
+}
+
+/// A Tzolk'in and Haab' date together, which repeat every 18,980 days (52 Haab' years).
+///
+/// Only a quarter of the combinations occur.  Inscriptions often give a Calendar Round without a
+/// Long Count; [`CalendarRound::occurrences`] finds the days it can fall on.
+///
+/// ```
+/// use geotime::{CalendarRound, LongCount};
+///
+/// let round: CalendarRound = "6 Etz'nab 11 Yax".parse().unwrap();
+/// let after: LongCount = "9.12.0.0.0".parse().unwrap();
+/// let before: LongCount = "9.13.0.0.0".parse().unwrap();
+/// let dates = round.occurrences(after, before).unwrap();
+/// assert_eq!(dates.len(), 1);
+/// assert_eq!(dates[0].to_string(), "9.12.11.5.18");
+///
+/// assert!("4 Ajaw 9 Kumk'u".parse::<CalendarRound>().unwrap().occurrences(after, before).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CalendarRound {
+    pub tzolkin: Tzolkin,
+    pub haab: Haab,
+}
+
+impl CalendarRound {
+    // Days after the creation date of the first occurrence, if the combination occurs at all.
+    fn offset(&self) -> Option<i128> {
+        let tzolkin = self.tzolkin.position() - CREATION_TZOLKIN;
+        let haab = self.haab.position() - CREATION_HAAB;
+        (0..CALENDAR_ROUND / 260)
+            .map(|k| (tzolkin + 260 * k).rem_euclid(CALENDAR_ROUND))
+            .find(|d| (d - haab).rem_euclid(365) == 0)
+    }
+
+    // The first day on or after `start` that falls on this Calendar Round, or `None` if it would
+    // be beyond the last Long Count.
+    fn first(&self, start: LongCount) -> Result<Option<i128>> {
+        let offset = self.offset().ok_or_else(|| {
+            Error::InvalidInput(format!("{} is not a possible Calendar Round", self))
+        })?;
+        let ahead = (offset - start.0.rem_euclid(CALENDAR_ROUND)).rem_euclid(CALENDAR_ROUND);
+        Ok(start.0.checked_add(ahead))
+    }
+
+    /// The days from `start` up to, but not including, `end` that fall on this Calendar Round.
+    /// A combination of Tzolk'in and Haab' dates that never occurs is an error.
+    pub fn occurrences(&self, start: LongCount, end: LongCount) -> Result<Vec<LongCount>> {
+        Ok(
+            std::iter::successors(self.first(start)?, |d| d.checked_add(CALENDAR_ROUND))
+                .take_while(|&d| d < end.0)
+                .map(LongCount)
+                .collect(),
+        )
+    }
+
+    /// The first day on or after `start` that falls on this Calendar Round.
+    pub fn next(&self, start: LongCount) -> Result<LongCount> {
+        self.first(start)?.map(LongCount).ok_or_else(overflow)
+    }
+}
+
+impl fmt::Display for CalendarRound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.tzolkin, self.haab)
+    }
+}
+
+impl FromStr for CalendarRound {
+    type Err = Error;
+
+    /// Parses a Calendar Round such as "4 Ajaw 8 Kumk'u" or, in the older orthography,
+    /// "4 Ahau 8 Cumku".
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("unable to parse Calendar Round: {}", s));
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        let split = (2..tokens.len())
+            .find(|&i| tokens[i].parse::<u8>().is_ok())
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            tzolkin: tokens[..split].join(" ").parse()?,
+            haab: tokens[split..].join(" ").parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReformCalendar;
+
+    #[test]
+    fn places() {
+        let date: LongCount = "9.12.11.5.18".parse().unwrap();
+        assert_eq!(date.days(), 9 * 144000 + 12 * 7200 + 11 * 360 + 5 * 20 + 18);
+        assert_eq!(date.to_string(), "9.12.11.5.18");
+        assert_eq!(LongCount::from_days(0).to_string(), "0.0.0.0.0");
+        assert_eq!(LongCount::from_days(-21).places(), vec![0, 0, 0, -1, -1]);
+        for days in [-21, -1, -144_000 * 20, i128::MIN, i128::MAX] {
+            let date = LongCount::from_days(days);
+            assert_eq!(date.to_string().parse::<LongCount>().unwrap(), date);
+        }
+        assert_eq!("-0.0.0.0.0".parse::<LongCount>().unwrap().days(), 0);
+
+        let alawtun: LongCount = "1.0.0.0.0.0.0.0.0".parse().unwrap();
+        assert_eq!(alawtun.days(), 144000 * 20i128.pow(4));
+        assert_eq!(LongCount::new(&alawtun.places()).unwrap(), alawtun);
+
+        for bad in [
+            "9.12.11.5",
+            "9.12.11.18.0",
+            "9.12.20.5.0",
+            "9.12.x.5.0",
+            "9.-1.0.0.0",
+        ] {
+            assert!(bad.parse::<LongCount>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn correlations() {
+        let end: LongCount = "13.0.0.0.0".parse().unwrap();
+        let gregorian = ReformCalendar::GREGORIAN;
+        let t = end.to_geotime(MayaCorrelation::GMT).unwrap();
+        assert_eq!(gregorian.format(t).unwrap(), "21 December 2012");
+        assert_eq!(LongCount::from_geotime(t, MayaCorrelation::GMT), end);
+        let t = end.to_geotime(MayaCorrelation::LOUNSBURY).unwrap();
+        assert_eq!(gregorian.format(t).unwrap(), "23 December 2012");
+
+        let creation = LongCount::from_days(0)
+            .to_geotime(MayaCorrelation::GMT)
+            .unwrap();
+        assert_eq!(
+            ReformCalendar::JULIAN.format(creation).unwrap(),
+            "6 September 3114 BC"
+        );
+        assert_eq!(gregorian.format(creation).unwrap(), "11 August 3114 BC");
+
+        // Deep time, far outside the range of chrono.
+        let far = Geotime::from_ma(4500.0);
+        let count = LongCount::from_geotime(far, MayaCorrelation::GMT);
+        assert_eq!(count.places().len(), 10);
+        let start = count.to_geotime(MayaCorrelation::GMT).unwrap();
+        assert!(start <= far && far.millis() - start.millis() < MILLISECONDS_IN_DAY);
+        assert!(LongCount::from_days(i128::MAX)
+            .to_geotime(MayaCorrelation::GMT)
+            .is_err());
+    }
+
+    #[test]
+    fn calendar_round() {
+        let creation = LongCount::from_days(0).calendar_round();
+        assert_eq!(creation.to_string(), "4 Ajaw 8 Kumk'u");
+        assert_eq!("4 Ahau 8 Cumku".parse::<CalendarRound>().unwrap(), creation);
+        assert_eq!(
+            LongCount::from_days(CALENDAR_ROUND).calendar_round(),
+            creation
+        );
+        let next = LongCount::from_days(1).calendar_round();
+        assert_eq!(next.to_string(), "5 Imix 9 Kumk'u");
+        let wayeb = LongCount::from_days(12).haab();
+        assert_eq!(wayeb.to_string(), "0 Wayeb'");
+
+        for bad in [
+            "14 Ajaw 8 Kumk'u",
+            "4 Ajaw 20 Kumk'u",
+            "4 Ajaw 5 Wayeb'",
+            "4 Ajaw",
+            "Ajaw 8 Kumk'u",
+        ] {
+            assert!(bad.parse::<CalendarRound>().is_err(), "{}", bad);
+        }
+
+        // Every day in a Calendar Round is found exactly once per cycle.
+        for days in (0..CALENDAR_ROUND).step_by(97) {
+            let date = LongCount::from_days(days + 1_000_000);
+            let round = date.calendar_round();
+            assert_eq!(round.next(LongCount::from_days(1_000_000)).unwrap(), date);
+        }
+
+        // Searches near the ends of the range stop rather than overflow.
+        let (min, max) = (
+            LongCount::from_days(i128::MIN),
+            LongCount::from_days(i128::MAX),
+        );
+        assert_eq!(max.calendar_round().next(max).unwrap(), max);
+        assert!(matches!(
+            min.calendar_round().next(max),
+            Err(Error::OutOfRange(_))
+        ));
+        assert_eq!(min.calendar_round().next(min).unwrap(), min);
+        let all = creation
+            .occurrences(LongCount::from_days(i128::MAX - 40_000), max)
+            .unwrap();
+        assert!(all.len() == 2 && all.iter().all(|d| d.calendar_round() == creation));
+        assert!(creation
+            .occurrences(min, LongCount::from_days(i128::MIN + 10))
+            .is_ok());
+    }
+}
@@ -0,0 +1,231 @@
+//! The Solar Hijri calendar.
+use super::{check, from_jdn, jdn, Calendar, CalendarDate};
+use crate::{Error, Geotime, Result};
+
+// Years in which the 33-year leap cycle is broken, after Borkowski (1996).  The rules hold from
+// AP -61 to AP 3177.
+#[rustfmt::skip]
+const BREAKS: [i64; 20] = [
+    -61, 9, 38, 199, 426, 686, 756, 818, 1111, 1181, 1210, 1635, 2060, 2097, 2192, 2262, 2324,
+    2394, 2456, 3178,
+];
+
+#[rustfmt::skip]
+const MONTHS: [&str; 12] = [
+    "Farvardin", "Ordibehesht", "Khordad", "Tir", "Mordad", "Shahrivar", "Mehr", "Aban", "Azar",
+    "Dey", "Bahman", "Esfand",
+];
+
+#[rustfmt::skip]
+const DAYS: [&str; 7] = [
+    "Yekshanbeh", "Doshanbeh", "Seshanbeh", "Chaharshanbeh", "Panjshanbeh", "Jomeh", "Shanbeh",
+];
+
+/// The Solar Hijri calendar of Iran and Afghanistan.  The year begins at the March equinox; the
+/// first six months have 31 days, the next five 30, and Esfand 29, or 30 in leap years.  Leap
+/// years follow the arithmetic approximation of the astronomical rule by Borkowski (1996), which
+/// agrees with it from AP 1 to AP 3177.  Years are counted in the Solar Hijri era (SH, also
+/// written AP).
+///
+/// ```
+/// use geotime::{Calendar, PersianCalendar, ReformCalendar};
+///
+/// let nowruz = PersianCalendar.parse("1 Farvardin 1403").unwrap();
+/// assert_eq!(ReformCalendar::GREGORIAN.format(nowruz).unwrap(), "20 March 2024");
+/// assert!(PersianCalendar.is_leap_year(1403).unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PersianCalendar;
+
+// The leap cycle position of `year`, the Gregorian year in which it begins, and the day of March
+// on which it begins.  A position of 0 marks a leap year.
+fn cycle(year: i64) -> Result<(i64, i64, i64)> {
+    if year < BREAKS[0] || year >= BREAKS[BREAKS.len() - 1] {
+        return Err(out_of_range(year));
+    }
+    let gregorian_year = year + 621;
+    let mut leaps = -14;
+    let mut previous = BREAKS[0];
+    let mut jump = 0;
+    for &next in &BREAKS[1..] {
+        jump = next - previous;
+        if year < next {
+            break;
+        }
+        leaps += jump / 33 * 8 + (jump % 33) / 4;
+        previous = next;
+    }
+    let mut n = year - previous;
+    leaps += n / 33 * 8 + (n % 33 + 3) / 4;
+    if jump % 33 == 4 && jump - n == 4 {
+        leaps += 1;
+    }
+    let gregorian_leaps = gregorian_year / 4 - (gregorian_year / 100 + 1) * 3 / 4 - 150;
+    let march = 20 + leaps - gregorian_leaps;
+    if jump - n < 6 {
+        n = n - jump + (jump + 4) / 33 * 33;
+    }
+    let position = match ((n + 1) % 33 - 1) % 4 {
+        -1 => 4,
+        position => position,
+    };
+    Ok((position, gregorian_year, march))
+}
+
+fn out_of_range(year: i64) -> Error {
+    Error::OutOfRange(format!(
+        "Solar Hijri year {} is outside the supported range",
+        year
+    ))
+}
+
+impl PersianCalendar {
+    pub fn is_leap_year(&self, year: i64) -> Result<bool> {
+        Ok(cycle(year)?.0 == 0)
+    }
+
+    fn jdn(&self, date: CalendarDate) -> Result<i64> {
+        let (_, gregorian_year, march) = cycle(date.year)?;
+        let month = date.month as i64;
+        let new_year = CalendarDate::new(gregorian_year, 3, march as u8).jdn(true);
+        Ok(new_year + (month - 1) * 31 - month / 7 * (month - 7) + date.day as i64 - 1)
+    }
+}
+
+impl Calendar for PersianCalendar {
+    fn era(&self) -> &'static str {
+        "SH"
+    }
+
+    fn to_geotime(&self, date: CalendarDate) -> Result<Geotime> {
+        check(self, date)?;
+        Ok(from_jdn(self.jdn(date)?))
+    }
+
+    fn date(&self, time: Geotime) -> Result<CalendarDate> {
+        let jdn = jdn(time)?;
+        let new_year = |year| self.jdn(CalendarDate::new(year, 1, 1));
+        // The year that began in March of the Gregorian year, unless Nowruz is yet to come.  The
+        // range is checked only once the year is known, so that the last days of AP 3177 are
+        // not lost.
+        let mut year = CalendarDate::from_jdn(jdn, true).year - 621;
+        let mut day = match new_year(year) {
+            Ok(start) => jdn - start,
+            Err(_) => -1,
+        };
+        if day < 0 {
+            year -= 1;
+            day = jdn - new_year(year)?;
+        }
+        if day >= 365 + self.is_leap_year(year)? as i64 {
+            return Err(out_of_range(year + 1));
+        }
+        Ok(match day <= 185 {
+            true => CalendarDate::new(year, (1 + day / 31) as u8, (day % 31 + 1) as u8),
+            false => {
+                let day = day - 186;
+                CalendarDate::new(year, (7 + day / 30) as u8, (day % 30 + 1) as u8)
+            }
+        })
+    }
+
+    fn months_in_year(&self, _year: i64) -> u8 {
+        12
+    }
+
+    fn days_in_month(&self, year: i64, month: u8) -> u8 {
+        match month {
+            1..=6 => 31,
+            7..=11 => 30,
+            12 if self.is_leap_year(year).unwrap_or(false) => 30,
+            12 => 29,
+            _ => 0,
+        }
+    }
+
+    fn month_name(&self, _year: i64, month: u8) -> Option<&'static str> {
+        MONTHS.get((month as usize).wrapping_sub(1)).copied()
+    }
+
+    fn day_names(&self) -> [&'static str; 7] {
+        DAYS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReformCalendar;
+
+    #[test]
+    fn known_dates() {
+        let gregorian = ReformCalendar::GREGORIAN;
+        for (persian, date) in [
+            ("1 Farvardin 1402", "21 March 2023"),
+            ("1 Farvardin 1404", "21 March 2025"),
+            ("30 Esfand 1403", "20 March 2025"),
+            ("11 Bahman 1357", "31 January 1979"),
+            ("1 Dey 1300", "22 December 1921"),
+        ] {
+            let t = PersianCalendar.parse(persian).unwrap();
+            assert_eq!(gregorian.format(t).unwrap(), date, "{}", persian);
+            assert_eq!(
+                PersianCalendar.format(t).unwrap(),
+                format!("{} SH", persian)
+            );
+        }
+        assert!(PersianCalendar.parse("30 Esfand 1402").is_err());
+        assert!(PersianCalendar.parse("1 Farvardin 3200").is_err());
+        assert!(PersianCalendar
+            .date(gregorian.parse("1 January 3900").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn leap_years() {
+        let leap: Vec<i64> = (1395..1412)
+            .filter(|&y| PersianCalendar.is_leap_year(y).unwrap())
+            .collect();
+        assert_eq!(leap, vec![1395, 1399, 1403, 1408]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let start = PersianCalendar.jdn(CalendarDate::new(1, 1, 1)).unwrap();
+        let mut jdn = start;
+        while jdn < start + 1_100_000 {
+            let t = from_jdn(jdn);
+            let date = PersianCalendar.date(t).unwrap();
+            assert_eq!(PersianCalendar.to_geotime(date).unwrap(), t, "{:?}", date);
+            jdn += 29;
+        }
+    }
+
+    #[test]
+    fn range() {
+        let last_day = match PersianCalendar.is_leap_year(3177).unwrap() {
+            true => 30,
+            false => 29,
+        };
+        for date in [
+            CalendarDate::new(-61, 1, 1),
+            CalendarDate::new(-61, 12, 1),
+            CalendarDate::new(3177, 1, 1),
+            CalendarDate::new(3177, 12, last_day),
+        ] {
+            let t = PersianCalendar.to_geotime(date).unwrap();
+            assert_eq!(PersianCalendar.date(t).unwrap(), date);
+        }
+
+        let first = PersianCalendar.jdn(CalendarDate::new(-61, 1, 1)).unwrap();
+        let last = PersianCalendar
+            .jdn(CalendarDate::new(3177, 12, last_day))
+            .unwrap();
+        for jdn in [first - 1, last + 1] {
+            assert!(matches!(
+                PersianCalendar.date(from_jdn(jdn)),
+                Err(Error::OutOfRange(_))
+            ));
+        }
+    }
+}
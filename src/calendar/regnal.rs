@@ -0,0 +1,994 @@
+//! Era names (nianhao, nengō, yeonho) of China, Japan and Korea.
+use std::fmt;
+
+use super::{from_jdn, jdn, normalize, CalendarDate, ChineseCalendar};
+use crate::{Error, GeoInterval, Geotime, Result};
+
+/// A state whose years are counted in eras.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Realm {
+    China,
+    Japan,
+    Korea,
+}
+
+impl Realm {
+    /// The lunisolar calendar, reckoned for the capital.
+    pub fn calendar(&self) -> ChineseCalendar {
+        match self {
+            Realm::China => ChineseCalendar::CHINA,
+            Realm::Japan => ChineseCalendar::JAPAN,
+            Realm::Korea => ChineseCalendar::KOREA,
+        }
+    }
+
+    // The first year that began on 1 January of the Gregorian calendar.
+    fn gregorian_since(&self) -> i64 {
+        match self {
+            Realm::China => 1912,
+            Realm::Japan => 1873,
+            Realm::Korea => 1896,
+        }
+    }
+
+    /// The first day of the civil year numbered as Gregorian `year`: the lunisolar new year, or
+    /// 1 January once the Gregorian calendar was adopted.
+    pub fn new_year(&self, year: i64) -> Result<Geotime> {
+        match year >= self.gregorian_since() {
+            true => Ok(gregorian(year, 1, 1)),
+            false => self.calendar().new_year(year),
+        }
+    }
+}
+
+impl fmt::Display for Realm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A named era in which years are counted from 1.  The first year of an era is the civil year
+/// in which it was proclaimed, and runs from the proclamation when that is recorded, and
+/// otherwise from the new year; later years run from new year to new year.  Years are numbered
+/// here by the Gregorian year in which they begin.
+///
+/// The tables cover the Chinese eras in the traditional succession from the first era name
+/// (Jianyuan of Han Wudi, 140 BC) through Han, Wei, Jin, the Southern Dynasties, Sui, Tang, the
+/// Five Dynasties, Song, Yuan, Ming and Qing to the Republic of China; the Japanese eras from
+/// Taika (645), following the Southern Court from 1331 to 1392; and the Korean reckonings from
+/// the founding of Joseon, of the Korean Empire, and the Dangun era.  Eras still in use are
+/// counted up to Gregorian year 9999.
+///
+/// ```
+/// use geotime::{Calendar, ReformCalendar, Realm, RegnalEra};
+///
+/// let gregorian = ReformCalendar::GREGORIAN;
+/// let year = RegnalEra::parse("Kangxi 21").unwrap();
+/// assert_eq!(gregorian.format(year.start().time()).unwrap(), "7 February 1682");
+/// assert_eq!(gregorian.format(year.end().time()).unwrap(), "27 January 1683");
+///
+/// let meiji = RegnalEra::find("Meiji").unwrap();
+/// assert_eq!(meiji.realm(), Realm::Japan);
+/// let year = meiji.year(5).unwrap();
+/// assert_eq!(gregorian.format(year.end().time()).unwrap(), "1 January 1873");
+///
+/// let time = gregorian.parse("1 January 1900").unwrap();
+/// let (era, year) = RegnalEra::at(Realm::Japan, time).unwrap().unwrap();
+/// assert_eq!((era.name(), year), ("Meiji", 33));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RegnalEra {
+    realm: Realm,
+    name: &'static str,
+    first_year: i64,
+    last_year: i64,
+    start: Option<(i64, u8, u8)>,
+    end: Option<(i64, u8, u8)>,
+}
+
+// The last year of an era still in use.
+const ONGOING: i64 = 9999;
+
+const fn era(realm: Realm, name: &'static str, first_year: i64, last_year: i64) -> RegnalEra {
+    RegnalEra {
+        realm,
+        name,
+        first_year,
+        last_year,
+        start: None,
+        end: None,
+    }
+}
+
+// An era proclaimed on a Gregorian date.
+const fn proclaimed(
+    realm: Realm,
+    name: &'static str,
+    start: (i64, u8, u8),
+    first_year: i64,
+    last_year: i64,
+) -> RegnalEra {
+    RegnalEra {
+        realm,
+        name,
+        first_year,
+        last_year,
+        start: Some(start),
+        end: None,
+    }
+}
+
+use Realm::{China, Japan, Korea};
+
+#[rustfmt::skip]
+const ERAS: &[RegnalEra] = &[
+    era(China, "Jianyuan", -139, -134),
+    era(China, "Yuanguang", -133, -128),
+    era(China, "Yuanshuo", -127, -122),
+    era(China, "Yuanshou", -121, -116),
+    era(China, "Yuanding", -115, -110),
+    era(China, "Yuanfeng", -109, -104),
+    era(China, "Taichu", -103, -100),
+    era(China, "Tianhan", -99, -96),
+    era(China, "Taishi", -95, -92),
+    era(China, "Zhenghe", -91, -88),
+    era(China, "Houyuan", -87, -86),
+    era(China, "Shiyuan", -85, -79),
+    era(China, "Yuanfeng", -79, -74),
+    era(China, "Yuanping", -73, -73),
+    era(China, "Benshi", -72, -69),
+    era(China, "Dijie", -68, -65),
+    era(China, "Yuankang", -64, -60),
+    era(China, "Shenjue", -60, -57),
+    era(China, "Wufeng", -56, -53),
+    era(China, "Ganlu", -52, -49),
+    era(China, "Huanglong", -48, -48),
+    era(China, "Chuyuan", -47, -43),
+    era(China, "Yongguang", -42, -38),
+    era(China, "Jianzhao", -37, -33),
+    era(China, "Jingning", -32, -32),
+    era(China, "Jianshi", -31, -27),
+    era(China, "Heping", -27, -24),
+    era(China, "Yangshuo", -23, -20),
+    era(China, "Hongjia", -19, -16),
+    era(China, "Yongshi", -15, -12),
+    era(China, "Yuanyan", -11, -8),
+    era(China, "Suihe", -7, -6),
+    era(China, "Jianping", -5, -2),
+    era(China, "Yuanshou", -1, 0),
+    era(China, "Yuanshi", 1, 5),
+    era(China, "Jushe", 6, 8),
+    era(China, "Chushi", 8, 8),
+    era(China, "Shijianguo", 9, 13),
+    era(China, "Tianfeng", 14, 19),
+    era(China, "Dihuang", 20, 23),
+    era(China, "Gengshi", 23, 25),
+    era(China, "Jianwu", 25, 56),
+    era(China, "Jianwu Zhongyuan", 56, 57),
+    era(China, "Yongping", 58, 75),
+    era(China, "Jianchu", 76, 84),
+    era(China, "Yuanhe", 84, 87),
+    era(China, "Zhanghe", 87, 88),
+    era(China, "Yongyuan", 89, 105),
+    era(China, "Yuanxing", 105, 105),
+    era(China, "Yanping", 106, 106),
+    era(China, "Yongchu", 107, 113),
+    era(China, "Yuanchu", 114, 120),
+    era(China, "Yongning", 120, 121),
+    era(China, "Jianguang", 121, 122),
+    era(China, "Yanguang", 122, 125),
+    era(China, "Yongjian", 126, 132),
+    era(China, "Yangjia", 132, 135),
+    era(China, "Yonghe", 136, 141),
+    era(China, "Han'an", 142, 144),
+    era(China, "Jiankang", 144, 144),
+    era(China, "Yongjia", 145, 145),
+    era(China, "Benchu", 146, 146),
+    era(China, "Jianhe", 147, 149),
+    era(China, "Heping", 150, 150),
+    era(China, "Yuanjia", 151, 153),
+    era(China, "Yongxing", 153, 154),
+    era(China, "Yongshou", 155, 158),
+    era(China, "Yanxi", 158, 167),
+    era(China, "Yongkang", 167, 167),
+    era(China, "Jianning", 168, 172),
+    era(China, "Xiping", 172, 178),
+    era(China, "Guanghe", 178, 184),
+    era(China, "Zhongping", 184, 189),
+    era(China, "Guangxi", 189, 189),
+    era(China, "Zhaoning", 189, 189),
+    era(China, "Yonghan", 189, 189),
+    era(China, "Chuping", 190, 193),
+    era(China, "Xingping", 194, 195),
+    era(China, "Jian'an", 196, 220),
+    era(China, "Yankang", 220, 220),
+    era(China, "Huangchu", 220, 226),
+    era(China, "Taihe", 227, 233),
+    era(China, "Qinglong", 233, 237),
+    era(China, "Jingchu", 237, 239),
+    era(China, "Zhengshi", 240, 249),
+    era(China, "Jiaping", 249, 254),
+    era(China, "Zhengyuan", 254, 256),
+    era(China, "Ganlu", 256, 260),
+    era(China, "Jingyuan", 260, 264),
+    era(China, "Xianxi", 264, 265),
+    era(China, "Taishi", 265, 274),
+    era(China, "Xianning", 275, 280),
+    era(China, "Taikang", 280, 289),
+    era(China, "Taixi", 290, 290),
+    era(China, "Yongxi", 290, 291),
+    era(China, "Yongping", 291, 291),
+    era(China, "Yuankang", 291, 299),
+    era(China, "Yongkang", 300, 301),
+    era(China, "Yongning", 301, 302),
+    era(China, "Tai'an", 302, 303),
+    era(China, "Yong'an", 304, 304),
+    era(China, "Jianwu", 304, 304),
+    era(China, "Yongxing", 304, 306),
+    era(China, "Guangxi", 306, 306),
+    era(China, "Yongjia", 307, 313),
+    era(China, "Jianxing", 313, 317),
+    era(China, "Jianwu", 317, 318),
+    era(China, "Taixing", 318, 321),
+    era(China, "Yongchang", 322, 323),
+    era(China, "Taining", 323, 326),
+    era(China, "Xianhe", 326, 335),
+    era(China, "Xiankang", 335, 342),
+    era(China, "Jianyuan", 343, 344),
+    era(China, "Yonghe", 345, 356),
+    era(China, "Shengping", 357, 361),
+    era(China, "Longhe", 362, 363),
+    era(China, "Xingning", 363, 365),
+    era(China, "Taihe", 366, 371),
+    era(China, "Xian'an", 371, 372),
+    era(China, "Ningkang", 373, 375),
+    era(China, "Taiyuan", 376, 396),
+    era(China, "Long'an", 397, 401),
+    era(China, "Yuanxing", 402, 404),
+    era(China, "Yixi", 405, 418),
+    era(China, "Yuanxi", 419, 420),
+    era(China, "Yongchu", 420, 422),
+    era(China, "Jingping", 423, 424),
+    era(China, "Yuanjia", 424, 453),
+    era(China, "Xiaojian", 454, 456),
+    era(China, "Daming", 457, 464),
+    era(China, "Yongguang", 465, 465),
+    era(China, "Jinghe", 465, 465),
+    era(China, "Taishi", 465, 471),
+    era(China, "Taiyu", 472, 472),
+    era(China, "Yuanhui", 473, 477),
+    era(China, "Shengming", 477, 479),
+    era(China, "Jianyuan", 479, 482),
+    era(China, "Yongming", 483, 493),
+    era(China, "Longchang", 494, 494),
+    era(China, "Yanxing", 494, 494),
+    era(China, "Jianwu", 494, 498),
+    era(China, "Yongtai", 498, 498),
+    era(China, "Yongyuan", 499, 501),
+    era(China, "Zhongxing", 501, 502),
+    era(China, "Tianjian", 502, 519),
+    era(China, "Putong", 520, 527),
+    era(China, "Datong", 527, 529),
+    era(China, "Zhongdatong", 529, 534),
+    era(China, "Datong", 535, 546),
+    era(China, "Zhongdatong", 546, 547),
+    era(China, "Taiqing", 547, 549),
+    era(China, "Dabao", 550, 551),
+    era(China, "Tianzheng", 551, 552),
+    era(China, "Chengsheng", 552, 555),
+    era(China, "Tiancheng", 555, 555),
+    era(China, "Shaotai", 555, 556),
+    era(China, "Taiping", 556, 557),
+    era(China, "Yongding", 557, 559),
+    era(China, "Tianjia", 560, 566),
+    era(China, "Tiankang", 566, 566),
+    era(China, "Guangda", 567, 568),
+    era(China, "Taijian", 569, 582),
+    era(China, "Kaihuang", 581, 600),
+    era(China, "Zhide", 583, 586),
+    era(China, "Zhenming", 587, 589),
+    era(China, "Renshou", 601, 604),
+    era(China, "Daye", 605, 618),
+    era(China, "Yining", 617, 618),
+    era(China, "Wude", 618, 626),
+    era(China, "Zhenguan", 627, 649),
+    era(China, "Yonghui", 650, 655),
+    era(China, "Xianqing", 656, 661),
+    era(China, "Longshuo", 661, 663),
+    era(China, "Linde", 664, 665),
+    era(China, "Qianfeng", 666, 668),
+    era(China, "Zongzhang", 668, 670),
+    era(China, "Xianheng", 670, 674),
+    era(China, "Shangyuan", 674, 676),
+    era(China, "Yifeng", 676, 679),
+    era(China, "Tiaolu", 679, 680),
+    era(China, "Yonglong", 680, 681),
+    era(China, "Kaiyao", 681, 682),
+    era(China, "Yongchun", 682, 683),
+    era(China, "Hongdao", 683, 683),
+    era(China, "Sisheng", 684, 684),
+    era(China, "Wenming", 684, 684),
+    era(China, "Guangzhai", 684, 684),
+    era(China, "Chuigong", 685, 688),
+    era(China, "Yongchang", 689, 689),
+    era(China, "Zaichu", 689, 690),
+    era(China, "Tianshou", 690, 692),
+    era(China, "Ruyi", 692, 692),
+    era(China, "Changshou", 692, 694),
+    era(China, "Yanzai", 694, 694),
+    era(China, "Zhengsheng", 695, 695),
+    era(China, "Tiancewansui", 695, 696),
+    era(China, "Wansuidengfeng", 696, 696),
+    era(China, "Wansuitongtian", 696, 697),
+    era(China, "Shengong", 697, 697),
+    era(China, "Shengli", 698, 700),
+    era(China, "Jiushi", 700, 700),
+    era(China, "Dazu", 701, 701),
+    era(China, "Chang'an", 701, 704),
+    era(China, "Shenlong", 705, 707),
+    era(China, "Jinglong", 707, 710),
+    era(China, "Tanglong", 710, 710),
+    era(China, "Jingyun", 710, 711),
+    era(China, "Taiji", 712, 712),
+    era(China, "Yanhe", 712, 712),
+    era(China, "Xiantian", 712, 713),
+    era(China, "Kaiyuan", 713, 741),
+    era(China, "Tianbao", 742, 756),
+    era(China, "Zhide", 756, 758),
+    era(China, "Qianyuan", 758, 760),
+    era(China, "Shangyuan", 760, 761),
+    era(China, "Baoying", 762, 763),
+    era(China, "Guangde", 763, 764),
+    era(China, "Yongtai", 765, 766),
+    era(China, "Dali", 766, 779),
+    era(China, "Jianzhong", 780, 783),
+    era(China, "Xingyuan", 784, 784),
+    era(China, "Zhenyuan", 785, 805),
+    era(China, "Yongzhen", 805, 805),
+    era(China, "Yuanhe", 806, 820),
+    era(China, "Changqing", 821, 824),
+    era(China, "Baoli", 825, 827),
+    era(China, "Dahe", 827, 835),
+    era(China, "Kaicheng", 836, 840),
+    era(China, "Huichang", 841, 846),
+    era(China, "Dazhong", 847, 860),
+    era(China, "Xiantong", 860, 874),
+    era(China, "Qianfu", 874, 879),
+    era(China, "Guangming", 880, 881),
+    era(China, "Zhonghe", 881, 885),
+    era(China, "Guangqi", 885, 888),
+    era(China, "Wende", 888, 888),
+    era(China, "Longji", 889, 889),
+    era(China, "Dashun", 890, 891),
+    era(China, "Jingfu", 892, 893),
+    era(China, "Qianning", 894, 898),
+    era(China, "Guanghua", 898, 901),
+    era(China, "Tianfu", 901, 904),
+    era(China, "Tianyou", 904, 907),
+    era(China, "Kaiping", 907, 911),
+    era(China, "Qianhua", 911, 915),
+    era(China, "Zhenming", 915, 921),
+    era(China, "Longde", 921, 923),
+    era(China, "Tongguang", 923, 926),
+    era(China, "Tiancheng", 926, 930),
+    era(China, "Changxing", 930, 933),
+    era(China, "Yingshun", 934, 934),
+    era(China, "Qingtai", 934, 936),
+    era(China, "Tianfu", 936, 944),
+    era(China, "Kaiyun", 944, 946),
+    era(China, "Qianyou", 948, 950),
+    era(China, "Guangshun", 951, 953),
+    era(China, "Xiande", 954, 960),
+    era(China, "Jianlong", 960, 963),
+    era(China, "Qiande", 963, 968),
+    era(China, "Kaibao", 968, 976),
+    era(China, "Taiping Xingguo", 976, 984),
+    era(China, "Yongxi", 984, 987),
+    era(China, "Duangong", 988, 989),
+    era(China, "Chunhua", 990, 994),
+    era(China, "Zhidao", 995, 997),
+    era(China, "Xianping", 998, 1003),
+    era(China, "Jingde", 1004, 1007),
+    era(China, "Dazhong Xiangfu", 1008, 1016),
+    era(China, "Tianxi", 1017, 1021),
+    era(China, "Qianxing", 1022, 1022),
+    era(China, "Tiansheng", 1023, 1032),
+    era(China, "Mingdao", 1032, 1033),
+    era(China, "Jingyou", 1034, 1038),
+    era(China, "Baoyuan", 1038, 1040),
+    era(China, "Kangding", 1040, 1041),
+    era(China, "Qingli", 1041, 1048),
+    era(China, "Huangyou", 1049, 1054),
+    era(China, "Zhihe", 1054, 1056),
+    era(China, "Jiayou", 1056, 1063),
+    era(China, "Zhiping", 1064, 1067),
+    era(China, "Xining", 1068, 1077),
+    era(China, "Yuanfeng", 1078, 1085),
+    era(China, "Yuanyou", 1086, 1094),
+    era(China, "Shaosheng", 1094, 1098),
+    era(China, "Yuanfu", 1098, 1100),
+    era(China, "Jianzhong Jingguo", 1101, 1101),
+    era(China, "Chongning", 1102, 1106),
+    era(China, "Daguan", 1107, 1110),
+    era(China, "Zhenghe", 1111, 1118),
+    era(China, "Chonghe", 1118, 1119),
+    era(China, "Xuanhe", 1119, 1125),
+    era(China, "Jingkang", 1126, 1127),
+    era(China, "Jianyan", 1127, 1130),
+    era(China, "Shaoxing", 1131, 1162),
+    era(China, "Longxing", 1163, 1164),
+    era(China, "Qiandao", 1165, 1173),
+    era(China, "Chunxi", 1174, 1189),
+    era(China, "Shaoxi", 1190, 1194),
+    era(China, "Qingyuan", 1195, 1200),
+    era(China, "Jiatai", 1201, 1204),
+    era(China, "Kaixi", 1205, 1207),
+    era(China, "Jiading", 1208, 1224),
+    era(China, "Baoqing", 1225, 1227),
+    era(China, "Shaoding", 1228, 1233),
+    era(China, "Duanping", 1234, 1236),
+    era(China, "Jiaxi", 1237, 1240),
+    era(China, "Chunyou", 1241, 1252),
+    era(China, "Baoyou", 1253, 1258),
+    era(China, "Kaiqing", 1259, 1259),
+    era(China, "Jingding", 1260, 1264),
+    era(China, "Zhongtong", 1260, 1264),
+    era(China, "Zhiyuan", 1264, 1294),
+    era(China, "Xianchun", 1265, 1274),
+    era(China, "Deyou", 1275, 1276),
+    era(China, "Jingyan", 1276, 1278),
+    era(China, "Xiangxing", 1278, 1279),
+    era(China, "Yuanzhen", 1295, 1297),
+    era(China, "Dade", 1297, 1307),
+    era(China, "Zhida", 1308, 1311),
+    era(China, "Huangqing", 1312, 1313),
+    era(China, "Yanyou", 1314, 1320),
+    era(China, "Zhizhi", 1321, 1323),
+    era(China, "Taiding", 1324, 1328),
+    era(China, "Zhihe", 1328, 1328),
+    era(China, "Tianshun", 1328, 1328),
+    era(China, "Tianli", 1328, 1330),
+    era(China, "Zhishun", 1330, 1333),
+    era(China, "Yuantong", 1333, 1335),
+    era(China, "Zhiyuan", 1335, 1340),
+    era(China, "Zhizheng", 1341, 1368),
+    era(China, "Hongwu", 1368, 1398),
+    era(China, "Jianwen", 1399, 1402),
+    era(China, "Yongle", 1403, 1424),
+    era(China, "Hongxi", 1425, 1425),
+    era(China, "Xuande", 1426, 1435),
+    era(China, "Zhengtong", 1436, 1449),
+    era(China, "Jingtai", 1450, 1456),
+    era(China, "Tianshun", 1457, 1464),
+    era(China, "Chenghua", 1465, 1487),
+    era(China, "Hongzhi", 1488, 1505),
+    era(China, "Zhengde", 1506, 1521),
+    era(China, "Jiajing", 1522, 1566),
+    era(China, "Longqing", 1567, 1572),
+    era(China, "Wanli", 1573, 1620),
+    era(China, "Taichang", 1620, 1620),
+    era(China, "Tianqi", 1621, 1627),
+    era(China, "Chongzhen", 1628, 1644),
+    era(China, "Shunzhi", 1644, 1661),
+    era(China, "Kangxi", 1662, 1722),
+    era(China, "Yongzheng", 1723, 1735),
+    era(China, "Qianlong", 1736, 1795),
+    era(China, "Jiaqing", 1796, 1820),
+    era(China, "Daoguang", 1821, 1850),
+    era(China, "Xianfeng", 1851, 1861),
+    era(China, "Tongzhi", 1862, 1874),
+    era(China, "Guangxu", 1875, 1908),
+    era(China, "Xuantong", 1909, 1911),
+    era(China, "Minguo", 1912, ONGOING),
+    era(Japan, "Taika", 645, 650),
+    era(Japan, "Hakuchi", 650, 654),
+    era(Japan, "Shuchō", 686, 686),
+    era(Japan, "Taihō", 701, 704),
+    era(Japan, "Keiun", 704, 708),
+    era(Japan, "Wadō", 708, 715),
+    era(Japan, "Reiki", 715, 717),
+    era(Japan, "Yōrō", 717, 724),
+    era(Japan, "Jinki", 724, 729),
+    era(Japan, "Tenpyō", 729, 749),
+    era(Japan, "Tenpyō-kanpō", 749, 749),
+    era(Japan, "Tenpyō-shōhō", 749, 757),
+    era(Japan, "Tenpyō-hōji", 757, 765),
+    era(Japan, "Tenpyō-jingo", 765, 767),
+    era(Japan, "Jingo-keiun", 767, 770),
+    era(Japan, "Hōki", 770, 781),
+    era(Japan, "Ten'ō", 781, 782),
+    era(Japan, "Enryaku", 782, 806),
+    era(Japan, "Daidō", 806, 810),
+    era(Japan, "Kōnin", 810, 824),
+    era(Japan, "Tenchō", 824, 834),
+    era(Japan, "Jōwa", 834, 848),
+    era(Japan, "Kashō", 848, 851),
+    era(Japan, "Ninju", 851, 854),
+    era(Japan, "Saikō", 854, 857),
+    era(Japan, "Ten'an", 857, 859),
+    era(Japan, "Jōgan", 859, 877),
+    era(Japan, "Gangyō", 877, 885),
+    era(Japan, "Ninna", 885, 889),
+    era(Japan, "Kanpyō", 889, 898),
+    era(Japan, "Shōtai", 898, 901),
+    era(Japan, "Engi", 901, 923),
+    era(Japan, "Enchō", 923, 931),
+    era(Japan, "Jōhei", 931, 938),
+    era(Japan, "Tengyō", 938, 947),
+    era(Japan, "Tenryaku", 947, 957),
+    era(Japan, "Tentoku", 957, 961),
+    era(Japan, "Ōwa", 961, 964),
+    era(Japan, "Kōhō", 964, 968),
+    era(Japan, "Anna", 968, 970),
+    era(Japan, "Tenroku", 970, 973),
+    era(Japan, "Ten'en", 973, 976),
+    era(Japan, "Jōgen", 976, 978),
+    era(Japan, "Tengen", 978, 983),
+    era(Japan, "Eikan", 983, 985),
+    era(Japan, "Kanna", 985, 987),
+    era(Japan, "Eien", 987, 989),
+    era(Japan, "Eiso", 989, 990),
+    era(Japan, "Shōryaku", 990, 995),
+    era(Japan, "Chōtoku", 995, 999),
+    era(Japan, "Chōhō", 999, 1004),
+    era(Japan, "Kankō", 1004, 1012),
+    era(Japan, "Chōwa", 1012, 1017),
+    era(Japan, "Kannin", 1017, 1021),
+    era(Japan, "Jian", 1021, 1024),
+    era(Japan, "Manju", 1024, 1028),
+    era(Japan, "Chōgen", 1028, 1037),
+    era(Japan, "Chōryaku", 1037, 1040),
+    era(Japan, "Chōkyū", 1040, 1044),
+    era(Japan, "Kantoku", 1044, 1046),
+    era(Japan, "Eishō", 1046, 1053),
+    era(Japan, "Tengi", 1053, 1058),
+    era(Japan, "Kōhei", 1058, 1065),
+    era(Japan, "Jiryaku", 1065, 1069),
+    era(Japan, "Enkyū", 1069, 1074),
+    era(Japan, "Jōhō", 1074, 1077),
+    era(Japan, "Jōryaku", 1077, 1081),
+    era(Japan, "Eihō", 1081, 1084),
+    era(Japan, "Ōtoku", 1084, 1087),
+    era(Japan, "Kanji", 1087, 1094),
+    era(Japan, "Kahō", 1094, 1096),
+    era(Japan, "Eichō", 1096, 1097),
+    era(Japan, "Jōtoku", 1097, 1099),
+    era(Japan, "Kōwa", 1099, 1104),
+    era(Japan, "Chōji", 1104, 1106),
+    era(Japan, "Kajō", 1106, 1108),
+    era(Japan, "Tennin", 1108, 1110),
+    era(Japan, "Ten'ei", 1110, 1113),
+    era(Japan, "Eikyū", 1113, 1118),
+    era(Japan, "Gen'ei", 1118, 1120),
+    era(Japan, "Hōan", 1120, 1124),
+    era(Japan, "Tenji", 1124, 1126),
+    era(Japan, "Daiji", 1126, 1131),
+    era(Japan, "Tenshō", 1131, 1132),
+    era(Japan, "Chōshō", 1132, 1135),
+    era(Japan, "Hōen", 1135, 1141),
+    era(Japan, "Eiji", 1141, 1142),
+    era(Japan, "Kōji", 1142, 1144),
+    era(Japan, "Ten'yō", 1144, 1145),
+    era(Japan, "Kyūan", 1145, 1151),
+    era(Japan, "Ninpei", 1151, 1154),
+    era(Japan, "Kyūju", 1154, 1156),
+    era(Japan, "Hōgen", 1156, 1159),
+    era(Japan, "Heiji", 1159, 1160),
+    era(Japan, "Eiryaku", 1160, 1161),
+    era(Japan, "Ōhō", 1161, 1163),
+    era(Japan, "Chōkan", 1163, 1165),
+    era(Japan, "Eiman", 1165, 1166),
+    era(Japan, "Nin'an", 1166, 1169),
+    era(Japan, "Kaō", 1169, 1171),
+    era(Japan, "Jōan", 1171, 1175),
+    era(Japan, "Angen", 1175, 1177),
+    era(Japan, "Jishō", 1177, 1181),
+    era(Japan, "Yōwa", 1181, 1182),
+    era(Japan, "Juei", 1182, 1184),
+    era(Japan, "Genryaku", 1184, 1185),
+    era(Japan, "Bunji", 1185, 1190),
+    era(Japan, "Kenkyū", 1190, 1199),
+    era(Japan, "Shōji", 1199, 1201),
+    era(Japan, "Kennin", 1201, 1204),
+    era(Japan, "Genkyū", 1204, 1206),
+    era(Japan, "Ken'ei", 1206, 1207),
+    era(Japan, "Jōgen", 1207, 1211),
+    era(Japan, "Kenryaku", 1211, 1213),
+    era(Japan, "Kenpō", 1213, 1219),
+    era(Japan, "Jōkyū", 1219, 1222),
+    era(Japan, "Jōō", 1222, 1224),
+    era(Japan, "Gennin", 1224, 1225),
+    era(Japan, "Karoku", 1225, 1227),
+    era(Japan, "Antei", 1227, 1229),
+    era(Japan, "Kangi", 1229, 1232),
+    era(Japan, "Jōei", 1232, 1233),
+    era(Japan, "Tenpuku", 1233, 1234),
+    era(Japan, "Bunryaku", 1234, 1235),
+    era(Japan, "Katei", 1235, 1238),
+    era(Japan, "Ryakunin", 1238, 1239),
+    era(Japan, "En'ō", 1239, 1240),
+    era(Japan, "Ninji", 1240, 1243),
+    era(Japan, "Kangen", 1243, 1247),
+    era(Japan, "Hōji", 1247, 1249),
+    era(Japan, "Kenchō", 1249, 1256),
+    era(Japan, "Kōgen", 1256, 1257),
+    era(Japan, "Shōka", 1257, 1259),
+    era(Japan, "Shōgen", 1259, 1260),
+    era(Japan, "Bun'ō", 1260, 1261),
+    era(Japan, "Kōchō", 1261, 1264),
+    era(Japan, "Bun'ei", 1264, 1275),
+    era(Japan, "Kenji", 1275, 1278),
+    era(Japan, "Kōan", 1278, 1288),
+    era(Japan, "Shōō", 1288, 1293),
+    era(Japan, "Einin", 1293, 1299),
+    era(Japan, "Shōan", 1299, 1302),
+    era(Japan, "Kengen", 1302, 1303),
+    era(Japan, "Kagen", 1303, 1306),
+    era(Japan, "Tokuji", 1306, 1308),
+    era(Japan, "Enkyō", 1308, 1311),
+    era(Japan, "Ōchō", 1311, 1312),
+    era(Japan, "Shōwa", 1312, 1317),
+    era(Japan, "Bunpō", 1317, 1319),
+    era(Japan, "Gen'ō", 1319, 1321),
+    era(Japan, "Genkō", 1321, 1324),
+    era(Japan, "Shōchū", 1324, 1326),
+    era(Japan, "Karyaku", 1326, 1329),
+    era(Japan, "Gentoku", 1329, 1331),
+    era(Japan, "Genkō", 1331, 1334),
+    era(Japan, "Kenmu", 1334, 1336),
+    era(Japan, "Engen", 1336, 1340),
+    era(Japan, "Kōkoku", 1340, 1346),
+    era(Japan, "Shōhei", 1346, 1370),
+    era(Japan, "Kentoku", 1370, 1372),
+    era(Japan, "Bunchū", 1372, 1375),
+    era(Japan, "Tenju", 1375, 1381),
+    era(Japan, "Kōwa", 1381, 1384),
+    era(Japan, "Genchū", 1384, 1392),
+    era(Japan, "Meitoku", 1390, 1394),
+    era(Japan, "Ōei", 1394, 1428),
+    era(Japan, "Shōchō", 1428, 1429),
+    era(Japan, "Eikyō", 1429, 1441),
+    era(Japan, "Kakitsu", 1441, 1444),
+    era(Japan, "Bun'an", 1444, 1449),
+    era(Japan, "Hōtoku", 1449, 1452),
+    era(Japan, "Kyōtoku", 1452, 1455),
+    era(Japan, "Kōshō", 1455, 1457),
+    era(Japan, "Chōroku", 1457, 1460),
+    era(Japan, "Kanshō", 1460, 1466),
+    era(Japan, "Bunshō", 1466, 1467),
+    era(Japan, "Ōnin", 1467, 1469),
+    era(Japan, "Bunmei", 1469, 1487),
+    era(Japan, "Chōkyō", 1487, 1489),
+    era(Japan, "Entoku", 1489, 1492),
+    era(Japan, "Meiō", 1492, 1501),
+    era(Japan, "Bunki", 1501, 1504),
+    era(Japan, "Eishō", 1504, 1521),
+    era(Japan, "Daiei", 1521, 1528),
+    era(Japan, "Kyōroku", 1528, 1532),
+    era(Japan, "Tenbun", 1532, 1555),
+    era(Japan, "Kōji", 1555, 1558),
+    era(Japan, "Eiroku", 1558, 1570),
+    era(Japan, "Genki", 1570, 1573),
+    era(Japan, "Tenshō", 1573, 1592),
+    era(Japan, "Bunroku", 1592, 1596),
+    era(Japan, "Keichō", 1596, 1615),
+    era(Japan, "Genna", 1615, 1624),
+    era(Japan, "Kan'ei", 1624, 1644),
+    era(Japan, "Shōhō", 1644, 1648),
+    era(Japan, "Keian", 1648, 1652),
+    era(Japan, "Jōō", 1652, 1655),
+    era(Japan, "Meireki", 1655, 1658),
+    era(Japan, "Manji", 1658, 1661),
+    era(Japan, "Kanbun", 1661, 1673),
+    era(Japan, "Enpō", 1673, 1681),
+    era(Japan, "Tenna", 1681, 1684),
+    era(Japan, "Jōkyō", 1684, 1688),
+    era(Japan, "Genroku", 1688, 1704),
+    era(Japan, "Hōei", 1704, 1711),
+    era(Japan, "Shōtoku", 1711, 1716),
+    era(Japan, "Kyōhō", 1716, 1736),
+    era(Japan, "Genbun", 1736, 1741),
+    era(Japan, "Kanpō", 1741, 1744),
+    era(Japan, "Enkyō", 1744, 1748),
+    era(Japan, "Kan'en", 1748, 1751),
+    era(Japan, "Hōreki", 1751, 1764),
+    era(Japan, "Meiwa", 1764, 1772),
+    era(Japan, "An'ei", 1772, 1781),
+    era(Japan, "Tenmei", 1781, 1789),
+    era(Japan, "Kansei", 1789, 1801),
+    era(Japan, "Kyōwa", 1801, 1804),
+    era(Japan, "Bunka", 1804, 1818),
+    era(Japan, "Bunsei", 1818, 1830),
+    era(Japan, "Tenpō", 1830, 1844),
+    era(Japan, "Kōka", 1844, 1848),
+    proclaimed(Japan, "Kaei", (1848, 4, 1), 1848, 1854),
+    proclaimed(Japan, "Ansei", (1855, 1, 15), 1854, 1860),
+    proclaimed(Japan, "Man'en", (1860, 4, 8), 1860, 1861),
+    proclaimed(Japan, "Bunkyū", (1861, 3, 29), 1861, 1864),
+    proclaimed(Japan, "Genji", (1864, 3, 27), 1864, 1865),
+    proclaimed(Japan, "Keiō", (1865, 5, 1), 1865, 1868),
+    // Proclaimed on 23 October 1868, but counted from the start of the year.
+    era(Japan, "Meiji", 1868, 1912),
+    proclaimed(Japan, "Taishō", (1912, 7, 30), 1912, 1926),
+    proclaimed(Japan, "Shōwa", (1926, 12, 25), 1926, 1989),
+    proclaimed(Japan, "Heisei", (1989, 1, 8), 1989, 2019),
+    proclaimed(Japan, "Reiwa", (2019, 5, 1), 2019, ONGOING),
+    era(Korea, "Dangi", -2332, ONGOING),
+    era(Korea, "Gaeguk", 1392, 1895),
+    era(Korea, "Geonyang", 1896, 1897),
+    proclaimed(Korea, "Gwangmu", (1897, 8, 17), 1897, 1907),
+    RegnalEra {
+        end: Some((1910, 8, 29)),
+        ..proclaimed(Korea, "Yunghui", (1907, 8, 12), 1907, 1910)
+    },
+];
+
+fn gregorian(year: i64, month: u8, day: u8) -> Geotime {
+    from_jdn(CalendarDate::new(year, month, day).jdn(true))
+}
+
+// Folds the macrons of Hepburn romanization, so that "Showa" finds "Shōwa".
+fn fold(name: &str) -> String {
+    let folded: String = name
+        .chars()
+        .map(|c| match c {
+            'ā' | 'Ā' => 'a',
+            'ē' | 'Ē' => 'e',
+            'ī' | 'Ī' => 'i',
+            'ō' | 'Ō' => 'o',
+            'ū' | 'Ū' => 'u',
+            c => c,
+        })
+        .collect();
+    normalize(&folded)
+}
+
+impl RegnalEra {
+    /// All eras in the tables, grouped by realm and in order within each.
+    pub fn all() -> &'static [RegnalEra] {
+        ERAS
+    }
+
+    /// The era called `name`, ignoring case, punctuation and macrons.  Where several eras share
+    /// a name, the longest is given, and of equally long eras the later.
+    pub fn find(name: &str) -> Result<&'static RegnalEra> {
+        let name = fold(name);
+        ERAS.iter()
+            .filter(|era| fold(era.name) == name)
+            .max_by_key(|era| (era.last_year - era.first_year, era.first_year))
+            .ok_or_else(|| Error::NotFound(format!("no era named {}", name)))
+    }
+
+    /// Parses an era name and year, such as "Kangxi 21" or "Meiji 5", into the interval of that
+    /// year.  The first year may also be written "gannen" or "yuannian".
+    pub fn parse(input: &str) -> Result<GeoInterval> {
+        let invalid = || Error::InvalidInput(format!("unable to parse era year: {}", input));
+        let (name, year) = input
+            .trim()
+            .rsplit_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let year = match normalize(year).as_str() {
+            "gannen" | "yuannian" | "元年" => 1,
+            year => year.parse().map_err(|_| invalid())?,
+        };
+        Self::find(name)?.year(year)
+    }
+
+    /// The era in use in `realm` at `time`, and the year of the era.  Where eras overlap, the
+    /// later one is given.
+    pub fn at(realm: Realm, time: Geotime) -> Result<Option<(&'static RegnalEra, i64)>> {
+        let mut year = CalendarDate::from_jdn(jdn(time)?, true).year;
+        if time < realm.new_year(year)? {
+            year -= 1;
+        }
+        let mut found = None;
+        for era in ERAS.iter().filter(|era| era.realm == realm) {
+            if (era.first_year..=era.last_year).contains(&year) {
+                let n = year - era.first_year + 1;
+                if era.year(n)?.contains(time) {
+                    found = Some((era, n));
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    pub fn realm(&self) -> Realm {
+        self.realm
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The Gregorian year in which the first year of the era began.
+    pub fn first_year(&self) -> i64 {
+        self.first_year
+    }
+
+    /// The Gregorian year in which the last year of the era began, or `None` for an era still
+    /// in use.
+    pub fn last_year(&self) -> Option<i64> {
+        match self.last_year {
+            ONGOING => None,
+            year => Some(year),
+        }
+    }
+
+    /// The interval spanned by year `n` of the era.
+    pub fn year(&self, n: i64) -> Result<GeoInterval> {
+        let year = n
+            .checked_sub(1)
+            .filter(|_| n >= 1)
+            .and_then(|n| self.first_year.checked_add(n));
+        let year = match year {
+            Some(year) if year <= self.last_year => year,
+            _ => {
+                return Err(Error::OutOfRange(format!(
+                    "{} has no year {}",
+                    self.name, n
+                )))
+            }
+        };
+        let start = match self.start {
+            Some((y, m, d)) if n == 1 => gregorian(y, m, d),
+            _ => self.realm.new_year(year)?,
+        };
+        let mut end = self.realm.new_year(year + 1)?;
+        let successors = ERAS
+            .iter()
+            .filter(|era| era.realm == self.realm && era.first_year == year)
+            .filter_map(|era| era.start);
+        for (y, m, d) in successors.chain(self.end.filter(|_| year == self.last_year)) {
+            let time = gregorian(y, m, d);
+            if start < time && time < end {
+                end = time;
+            }
+        }
+        GeoInterval::new(start.into(), end.into())
+    }
+
+    /// The interval spanned by the whole era, which ends now for an era still in use.
+    pub fn interval(&self) -> Result<GeoInterval> {
+        let start = self.year(1)?.start();
+        let end = match self.last_year() {
+            Some(last) => self.year(last - self.first_year + 1)?.end(),
+            None => Geotime::now().into(),
+        };
+        GeoInterval::new(start, end)
+    }
+}
+
+impl fmt::Display for RegnalEra {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReformCalendar;
+
+    fn format(time: Geotime) -> String {
+        ReformCalendar::GREGORIAN.format(time).unwrap()
+    }
+
+    #[test]
+    fn successions() {
+        // Each era of a realm starts no later than the next.
+        for pair in ERAS.windows(2).filter(|w| w[0].realm == w[1].realm) {
+            assert!(
+                pair[0].first_year <= pair[1].first_year,
+                "{} {}",
+                pair[0],
+                pair[1]
+            );
+        }
+        let taisho = RegnalEra::find("Taisho").unwrap();
+        let meiji = RegnalEra::find("Meiji").unwrap();
+        assert_eq!(
+            meiji.year(45).unwrap().end(),
+            taisho.year(1).unwrap().start()
+        );
+        assert_eq!(
+            format(taisho.year(1).unwrap().end().time()),
+            "1 January 1913"
+        );
+        assert_eq!(
+            format(RegnalEra::parse("Heisei 31").unwrap().end().time()),
+            "1 May 2019"
+        );
+        assert_eq!(
+            format(RegnalEra::parse("Yunghui 4").unwrap().end().time()),
+            "29 August 1910"
+        );
+        assert_eq!(
+            RegnalEra::parse("Meiji gannen").unwrap(),
+            RegnalEra::parse("meiji 1").unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            RegnalEra::parse("Kangxi 62"),
+            Err(Error::OutOfRange(_))
+        ));
+        assert!(matches!(
+            RegnalEra::parse("Kangxi 0"),
+            Err(Error::OutOfRange(_))
+        ));
+        assert!(matches!(RegnalEra::parse("Foo 1"), Err(Error::NotFound(_))));
+        assert!(matches!(
+            RegnalEra::parse("Kangxi"),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn early_eras() {
+        let year = RegnalEra::parse("Jianyuan 1").unwrap();
+        assert_eq!(
+            ChineseCalendar::CHINA
+                .date(year.start().time())
+                .unwrap()
+                .year,
+            -139
+        );
+        let korea = RegnalEra::find("Dangi").unwrap().year(1).unwrap();
+        assert!(korea.start().time() < Geotime::from_years_bp(4280.0));
+
+        let year = RegnalEra::parse("Kaiyuan 1").unwrap();
+        let date = ChineseCalendar::CHINA.date(year.midpoint()).unwrap();
+        assert_eq!(date.year, 713);
+        let year = RegnalEra::parse("Genroku 1").unwrap();
+        assert_eq!(
+            ChineseCalendar::JAPAN.date(year.midpoint()).unwrap().year,
+            1688
+        );
+        let taika = RegnalEra::find("Taika").unwrap();
+        assert_eq!(taika.first_year(), 645);
+    }
+
+    #[test]
+    fn shared_names() {
+        assert_eq!(RegnalEra::find("Showa").unwrap().first_year(), 1926);
+        assert_eq!(RegnalEra::find("Jianyuan").unwrap().first_year(), -139);
+        assert_eq!(RegnalEra::find("Yuanfeng").unwrap().first_year(), 1078);
+        assert_eq!(RegnalEra::find("Genko").unwrap().first_year(), 1331);
+    }
+
+    #[test]
+    fn ongoing_eras() {
+        for input in ["Minguo 100000000000000000", "Reiwa 9223372036854775807"] {
+            assert!(matches!(RegnalEra::parse(input), Err(Error::OutOfRange(_))));
+        }
+        assert!(matches!(
+            RegnalEra::find("Reiwa").unwrap().year(i64::MIN),
+            Err(Error::OutOfRange(_))
+        ));
+        assert!(RegnalEra::parse("Minguo 8088").is_ok());
+        assert!(RegnalEra::parse("Minguo 8089").is_err());
+        assert_eq!(RegnalEra::find("Minguo").unwrap().last_year(), None);
+    }
+
+    #[test]
+    fn lookup() {
+        let time = RegnalEra::parse("Qianlong 60").unwrap().midpoint();
+        let (era, year) = RegnalEra::at(Realm::China, time).unwrap().unwrap();
+        assert_eq!((era.name(), year), ("Qianlong", 60));
+        let time = RegnalEra::parse("Gwangmu 3").unwrap().midpoint();
+        let (era, year) = RegnalEra::at(Realm::Korea, time).unwrap().unwrap();
+        assert_eq!((era.name(), year), ("Gwangmu", 3));
+        let time = ReformCalendar::GREGORIAN.parse("1 June 2024").unwrap();
+        let (era, year) = RegnalEra::at(Realm::Japan, time).unwrap().unwrap();
+        assert_eq!((era.name(), year), ("Reiwa", 6));
+        let time = RegnalEra::parse("Tenpyo 1").unwrap().midpoint();
+        let (era, year) = RegnalEra::at(Realm::Japan, time).unwrap().unwrap();
+        assert_eq!((era.name(), year), ("Tenpyō", 1));
+        assert!(RegnalEra::at(Realm::Japan, Geotime::from_years_bp(1500.0))
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,252 @@
+//! Cosmological time: the redshift of light from a distant source, the lookback time to its
+//! emission and the age of the universe at that time, in a ΛCDM universe.
+//!
+//! A redshift of zero is taken to be [`Geotime::PRESENT`]; the decades between that and today are
+//! far below the precision of any cosmology.  Years are Julian years, as elsewhere in the crate.
+use crate::{Error, Geotime, Result};
+
+// Kilometres in a megaparsec, and seconds in a Julian year.
+const MEGAPARSEC: f64 = 3.085_677_581_491_367e19;
+const SECONDS_IN_JULIAN_YEAR: f64 = 31_557_600.0;
+
+// Intervals of the composite Simpson rule.
+const STEPS: usize = 2000;
+
+/// The parameters of a ΛCDM cosmology: the Hubble constant in km/s/Mpc, and the present density
+/// parameters of matter, of the cosmological constant and of radiation.  Curvature makes up the
+/// rest, Ωk = 1 − Ωm − ΩΛ − Ωr.
+///
+/// The fields are public, so that parameters can be overridden with struct update syntax.
+///
+/// ```
+/// use geotime::{Cosmology, Geotime};
+///
+/// let planck = Cosmology::PLANCK_2018;
+/// assert!((planck.age() / 1e9 - 13.79).abs() < 0.01);
+///
+/// // A quasar at redshift 7.
+/// let quasar = planck.time_at(7.0).unwrap();
+/// assert!((quasar.to_ma() / 1e3 - 13.0).abs() < 0.05);
+/// assert!((planck.redshift(quasar).unwrap() - 7.0).abs() < 1e-6);
+///
+/// let fast = Cosmology { h0: 73.0, ..Cosmology::PLANCK_2018 };
+/// assert!(fast.age() < planck.age());
+/// assert!(planck.time_at(1100.0).unwrap() > planck.big_bang().unwrap());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cosmology {
+    pub h0: f64,
+    pub omega_m: f64,
+    pub omega_lambda: f64,
+    pub omega_r: f64,
+}
+
+impl Cosmology {
+    /// Planck 2018 (TT,TE,EE+lowE+lensing+BAO), flat, with radiation from a CMB temperature of
+    /// 2.7255 K and three species of massless neutrinos.
+    pub const PLANCK_2018: Cosmology = Cosmology {
+        h0: 67.66,
+        omega_m: 0.3111,
+        omega_lambda: 0.688809,
+        omega_r: 9.1e-5,
+    };
+
+    /// The curvature density parameter, Ωk = 1 − Ωm − ΩΛ − Ωr.
+    pub fn omega_k(&self) -> f64 {
+        1.0 - self.omega_m - self.omega_lambda - self.omega_r
+    }
+
+    /// The Hubble time, 1/H0, in years.
+    pub fn hubble_time(&self) -> f64 {
+        MEGAPARSEC / self.h0 / SECONDS_IN_JULIAN_YEAR
+    }
+
+    /// The present age of the universe in years.  A universe without a big bang, such as one
+    /// dominated by Λ that bounced, has an age of NaN.
+    pub fn age(&self) -> f64 {
+        self.elapsed(0.0, 1.0).unwrap_or(f64::NAN)
+    }
+
+    /// The age of the universe in years when light emitted at redshift `z` set out.
+    pub fn age_at(&self, z: f64) -> Result<f64> {
+        self.elapsed(0.0, scale_factor(z)?)
+    }
+
+    /// The time in years that light received today at redshift `z` has travelled.  Negative
+    /// redshifts give negative times, in the future.
+    pub fn lookback_time(&self, z: f64) -> Result<f64> {
+        Ok(-self.elapsed(1.0, scale_factor(z)?)?)
+    }
+
+    /// The time at which light received at redshift `z` was emitted.
+    pub fn time_at(&self, z: f64) -> Result<Geotime> {
+        Geotime::try_from_years_bp(self.lookback_time(z)?)
+    }
+
+    /// The time of the big bang, the zero of cosmic time.  A universe without a big bang, or one
+    /// too old for a [`Geotime`], is an error.
+    pub fn big_bang(&self) -> Result<Geotime> {
+        Geotime::try_from_years_bp(self.age())
+    }
+
+    /// The redshift of light emitted at `time` and received at the present.
+    pub fn redshift(&self, time: Geotime) -> Result<f64> {
+        let age = self.age();
+        let target = age - time.to_years_bp();
+        if age.is_nan() || target <= 0.0 {
+            return Err(Error::OutOfRange(format!(
+                "{} is before the big bang",
+                time.display_string("%Y")
+            )));
+        }
+
+        // Bisection on the scale factor, whose age grows with it.
+        let (mut low, mut high) = (0.0, 1.0);
+        while self.elapsed(0.0, high)? < target {
+            low = high;
+            high *= 2.0;
+            if high > 1e6 {
+                return Err(Error::OutOfRange(format!(
+                    "{} is too far in the future",
+                    time.display_string("%Y")
+                )));
+            }
+        }
+        for _ in 0..100 {
+            let mid = (low + high) / 2.0;
+            match self.elapsed(0.0, mid)? < target {
+                true => low = mid,
+                false => high = mid,
+            }
+        }
+        Ok(1.0 / ((low + high) / 2.0) - 1.0)
+    }
+
+    // Years between the scale factors `a0` and `a1`.  With a = s², dt = 2s³ ds / (H0 √(Ωr +
+    // Ωm s² + Ωk s⁴ + ΩΛ s⁸)), which is smooth down to the big bang.
+    fn elapsed(&self, a0: f64, a1: f64) -> Result<f64> {
+        let (s0, s1) = (a0.sqrt(), a1.sqrt());
+        let integrand = |s: f64| -> Result<f64> {
+            let a = s * s;
+            let e2 = self.omega_r
+                + self.omega_m * a
+                + self.omega_k() * a * a
+                + self.omega_lambda * a * a * a * a;
+            match s == 0.0 || e2 > 0.0 {
+                true => Ok(2.0 * s * s * s / e2.sqrt().max(f64::MIN_POSITIVE)),
+                false => Err(Error::InvalidInput(format!(
+                    "{:?} does not expand continuously from a big bang",
+                    self
+                ))),
+            }
+        };
+        let h = (s1 - s0) / STEPS as f64;
+        let mut sum = integrand(s0)? + integrand(s1)?;
+        for i in 1..STEPS {
+            let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+            sum += weight * integrand(s0 + i as f64 * h)?;
+        }
+        Ok(sum * h / 3.0 * self.hubble_time())
+    }
+}
+
+impl Default for Cosmology {
+    fn default() -> Self {
+        Self::PLANCK_2018
+    }
+}
+
+fn scale_factor(z: f64) -> Result<f64> {
+    match z > -1.0 && z.is_finite() {
+        true => Ok(1.0 / (1.0 + z)),
+        false => Err(Error::InvalidInput(format!("invalid redshift: {}", z))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EINSTEIN_DE_SITTER: Cosmology = Cosmology {
+        h0: 70.0,
+        omega_m: 1.0,
+        omega_lambda: 0.0,
+        omega_r: 0.0,
+    };
+
+    #[test]
+    fn exact_solutions() {
+        let eds = EINSTEIN_DE_SITTER;
+        let t0 = 2.0 / 3.0 * eds.hubble_time();
+        assert!((eds.age() / t0 - 1.0).abs() < 1e-9);
+        for z in [0.5, 3.0, 1000.0] {
+            let expected = t0 * (1.0 - (1.0_f64 + z).powf(-1.5));
+            assert!((eds.lookback_time(z).unwrap() / expected - 1.0).abs() < 1e-9);
+        }
+
+        // An empty universe expands at a constant rate.
+        let milne = Cosmology {
+            omega_m: 0.0,
+            ..eds
+        };
+        assert!((milne.age() / milne.hubble_time() - 1.0).abs() < 1e-9);
+
+        // A flat universe of matter and Λ.
+        let flat = Cosmology {
+            omega_r: 0.0,
+            omega_lambda: 1.0 - 0.3,
+            omega_m: 0.3,
+            ..eds
+        };
+        let expected = 2.0 / (3.0 * 0.7_f64.sqrt()) * (0.7_f64 / 0.3).sqrt().asinh();
+        assert!((flat.age() / flat.hubble_time() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn planck() {
+        let planck = Cosmology::default();
+        assert!(planck.omega_k().abs() < 1e-9);
+        assert!((planck.hubble_time() / 1e9 - 14.45).abs() < 0.01);
+        assert!((planck.lookback_time(1.0).unwrap() / 1e9 - 7.93).abs() < 0.01);
+        // Recombination, about 370,000 years after the big bang.
+        let recombination = planck.age_at(1090.0).unwrap();
+        assert!((recombination / 1e3 - 372.0).abs() < 10.0);
+        assert_eq!(planck.lookback_time(0.0).unwrap(), 0.0);
+        assert_eq!(planck.time_at(0.0).unwrap(), Geotime::PRESENT);
+        assert!(planck.lookback_time(-0.5).unwrap() < 0.0);
+        assert!(planck.lookback_time(-1.0).is_err());
+    }
+
+    #[test]
+    fn inverse() {
+        let planck = Cosmology::PLANCK_2018;
+        for z in [0.01, 0.5, 2.0, 20.0, 1100.0, -0.3] {
+            let time = planck.time_at(z).unwrap();
+            let found = planck.redshift(time).unwrap();
+            assert!((found - z).abs() < 1e-6 * (1.0 + z), "{}: {}", z, found);
+        }
+        assert!(planck.redshift(Geotime::from_ma(14e3)).is_err());
+        assert!(planck.redshift(Geotime::from_ma(-1e9)).is_err());
+    }
+
+    #[test]
+    fn without_big_bang() {
+        let bounce = Cosmology {
+            h0: 70.0,
+            omega_m: 0.1,
+            omega_lambda: 2.0,
+            omega_r: 0.0,
+        };
+        assert!(bounce.age().is_nan());
+        assert!(bounce.big_bang().is_err());
+
+        let empty = Cosmology {
+            h0: 0.0,
+            ..Cosmology::PLANCK_2018
+        };
+        assert!(empty.big_bang().is_err());
+        assert!(empty.time_at(1.0).is_err());
+        assert!(bounce.lookback_time(10.0).is_err());
+        assert!(bounce.redshift(Geotime::from_ma(100.0)).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! The length of the day through deep time.  Tides raised by the Moon brake the rotation of the
+//! Earth, so the day has lengthened and the year, whose length in seconds has barely changed,
+//! has held fewer days: about 400 in the Devonian.
+//!
+//! The evidence comes from daily and monthly growth bands in corals and bivalves and from tidal
+//! rhythmites, laminated sediments that record the tidal cycle, and, in the Precambrian, from the
+//! precession cycles of cyclostratigraphy.  The values here are approximate and tabulated back to
+//! 2.46 Ga; older times are out of range.
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::interp::Curve;
+use crate::{Error, Geotime, Measurement, Result, MILLISECONDS_IN_JULIAN_YEAR, SECONDS_IN_DAY};
+
+// Age (Ma), length of day (hours) and its one-sigma uncertainty (hours).
+#[rustfmt::skip]
+const COMPILATION: [(f64, f64, f64); 8] = [
+    (0.0, 24.0, 0.0),
+    // Rudist bivalve, Oman, 372 days a year (de Winter et al., 2020).
+    (70.0, 23.5, 0.1),
+    // Pennsylvanian corals, 387 days a year (Wells, 1963).
+    (305.0, 22.65, 0.3),
+    // Middle Devonian corals, 400 days a year (Wells, 1963).
+    (385.0, 21.9, 0.3),
+    // Elatina tidal rhythmites, 400 ± 7 days a year (Williams, 2000).
+    (620.0, 21.9, 0.4),
+    // Big Cottonwood tidal rhythmites (Sonett et al., 1996).
+    (900.0, 18.9, 0.5),
+    // Xiamaling Formation, cyclostratigraphy (Meyers and Malinverno, 2018).
+    (1400.0, 18.68, 0.25),
+    // Joffre Member banded iron formation, cyclostratigraphy (Lantink et al., 2022).
+    (2460.0, 17.0, 0.5),
+];
+
+// The present rate of tidal lengthening of the day, in seconds per year (2.3 ms per century).
+const TIDAL_RATE: f64 = 2.3e-5;
+
+/// A model of the length of the day in the past.
+///
+/// - `Compilation`: a monotone curve through growth-band and tidal-rhythmite data, with their
+///   uncertainties.  The future follows the present tidal rate.
+/// - `MeanRate`: a steady lengthening from 21.9 h at 620 Ma, the Elatina rhythmites, to 24 h
+///   today, about 1.2 ms per century.
+/// - `PresentRate`: the present tidal rate of 2.3 ms per century, extrapolated.  It makes the
+///   Precambrian day far too short, and shows how much the rate has varied.
+///
+/// The linear models are taken to be exact; only the compilation carries an uncertainty.
+///
+/// ```
+/// use geotime::{DayLength, Geotime};
+///
+/// let devonian = Geotime::from_ma(385.0);
+/// let hours = DayLength::Compilation.seconds(devonian).unwrap().value / 3600.0;
+/// assert!((hours - 21.9).abs() < 1e-9);
+/// let days = DayLength::Compilation.days_per_year(devonian).unwrap();
+/// assert!((days.value - 400.0).abs() < 1.0);
+///
+/// assert!(DayLength::PresentRate.seconds(devonian).unwrap().value < 80_000.0);
+/// assert!(DayLength::default().seconds(Geotime::from_ma(3000.0)).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DayLength {
+    #[default]
+    Compilation,
+    MeanRate,
+    PresentRate,
+}
+
+impl DayLength {
+    /// The length of the solar day at `time` in seconds, and its uncertainty.
+    pub fn seconds(self, time: Geotime) -> Result<Measurement> {
+        let ma = time.to_ma();
+        let oldest = COMPILATION[COMPILATION.len() - 1];
+        if ma > oldest.0 {
+            return Err(Error::OutOfRange(format!(
+                "the length of day is not known before {} Ma",
+                oldest.0
+            )));
+        }
+        let years = ma * 1e6;
+        let day = SECONDS_IN_DAY as f64;
+        Ok(match self {
+            DayLength::Compilation if ma > 0.0 => {
+                let (hours, sigma) = compilation();
+                let at = |curve: &Curve| curve.eval(ma).unwrap_or_default() * 3600.0;
+                Measurement::new(at(hours), at(sigma))
+            }
+            DayLength::MeanRate => {
+                let (ma, hours, _) = COMPILATION[4];
+                let rate = (day - hours * 3600.0) / (ma * 1e6);
+                Measurement::exact(day - rate * years)
+            }
+            _ => Measurement::exact(day - TIDAL_RATE * years),
+        })
+    }
+
+    /// The number of solar days in a year at `time`.  The length of the year in seconds is taken
+    /// to be constant.
+    pub fn days_per_year(self, time: Geotime) -> Result<Measurement> {
+        let day = self.seconds(time)?;
+        let days = year_seconds() / day.value;
+        Ok(Measurement::new(days, days * day.relative()))
+    }
+
+    /// The day of the year and the time of day at `time`, counted in days of the length they had
+    /// then.  See [`PaleoDate`].
+    pub fn date(self, time: Geotime) -> Result<PaleoDate> {
+        let length_of_day = self.seconds(time)?.value;
+        let into_year = time.millis().rem_euclid(MILLISECONDS_IN_JULIAN_YEAR) as f64 / 1000.0;
+        let days = (into_year / length_of_day).floor();
+        Ok(PaleoDate {
+            day: days as u32 + 1,
+            days_in_year: year_seconds() / length_of_day,
+            seconds: into_year - days * length_of_day,
+            length_of_day,
+        })
+    }
+}
+
+/// A date in a local paleo-calendar: a year of Julian length divided into days of the length they
+/// had at the time.  Years begin with the Julian years counted from the Unix epoch, and each year
+/// begins at midnight, so the calendar counts days rather than following the Sun.  Times of day
+/// are in SI hours and minutes since midnight, so a Devonian clock stops short of 22:00.
+///
+/// ```
+/// use geotime::{DayLength, Geotime};
+///
+/// let date = DayLength::Compilation.date(Geotime::from_ma(385.0)).unwrap();
+/// assert_eq!(date.days_in_year().round(), 400.0);
+/// assert!(date.day() <= 401);
+/// assert!(date.to_string().ends_with("of 21:54"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PaleoDate {
+    day: u32,
+    days_in_year: f64,
+    seconds: f64,
+    length_of_day: f64,
+}
+
+impl PaleoDate {
+    /// The day of the year, from 1.  The last day of a year is usually cut short.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn days_in_year(&self) -> f64 {
+        self.days_in_year
+    }
+
+    /// Seconds since midnight.
+    pub fn seconds(&self) -> f64 {
+        self.seconds
+    }
+
+    /// The length of the day in seconds.
+    pub fn length_of_day(&self) -> f64 {
+        self.length_of_day
+    }
+}
+
+/// Formats the date as "day 123 of 400, 10:30 of 21:54".
+impl fmt::Display for PaleoDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let clock = |seconds: f64| {
+            let minutes = (seconds / 60.0).floor() as u64;
+            format!("{:02}:{:02}", minutes / 60, minutes % 60)
+        };
+        write!(
+            f,
+            "day {} of {:.0}, {} of {}",
+            self.day,
+            self.days_in_year,
+            clock(self.seconds),
+            clock(self.length_of_day)
+        )
+    }
+}
+
+fn year_seconds() -> f64 {
+    MILLISECONDS_IN_JULIAN_YEAR as f64 / 1000.0
+}
+
+// The length of day and its uncertainty in hours, by age in Ma.
+fn compilation() -> &'static (Curve, Curve) {
+    static CURVES: OnceLock<(Curve, Curve)> = OnceLock::new();
+    CURVES.get_or_init(|| {
+        let ages: Vec<f64> = COMPILATION.iter().map(|c| c.0).collect();
+        let hours: Vec<f64> = COMPILATION.iter().map(|c| c.1).collect();
+        let sigmas: Vec<f64> = COMPILATION.iter().map(|c| c.2).collect();
+        (
+            Curve::monotone(&ages, &hours),
+            Curve::linear(&ages, &sigmas),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODELS: [DayLength; 3] = [
+        DayLength::Compilation,
+        DayLength::MeanRate,
+        DayLength::PresentRate,
+    ];
+
+    fn hours(model: DayLength, ma: f64) -> f64 {
+        model.seconds(Geotime::from_ma(ma)).unwrap().value / 3600.0
+    }
+
+    #[test]
+    fn present() {
+        for model in MODELS {
+            assert!((hours(model, 0.0) - 24.0).abs() < 1e-9);
+            let days = model.days_per_year(Geotime::PRESENT).unwrap().value;
+            assert!((days - 365.25).abs() < 1e-6);
+            // The day goes on lengthening.
+            assert!(hours(model, -100.0) > 24.0);
+        }
+    }
+
+    #[test]
+    fn compilation_values() {
+        for (ma, expected, _) in COMPILATION {
+            assert!((hours(DayLength::Compilation, ma) - expected).abs() < 1e-9);
+        }
+        // The curve does not overshoot between the data.
+        let mut previous = 24.0;
+        for ma in (0..=2460).step_by(10) {
+            let h = hours(DayLength::Compilation, ma as f64);
+            assert!(h <= previous + 1e-9, "{} Ma: {}", ma, h);
+            previous = h;
+        }
+        let sigma = DayLength::Compilation
+            .seconds(Geotime::from_ma(500.0))
+            .unwrap()
+            .sigma;
+        assert!(sigma > 0.3 * 3600.0 && sigma < 0.4 * 3600.0);
+    }
+
+    #[test]
+    fn linear_models() {
+        assert!((hours(DayLength::MeanRate, 620.0) - 21.9).abs() < 1e-9);
+        assert!((hours(DayLength::MeanRate, 310.0) - 22.95).abs() < 1e-9);
+        let present = hours(DayLength::PresentRate, 1000.0);
+        assert!((present - (24.0 - 23_000.0 / 3600.0)).abs() < 1e-9);
+        for model in MODELS {
+            assert!(model.seconds(Geotime::from_ma(2461.0)).is_err());
+        }
+    }
+
+    #[test]
+    fn dates() {
+        let date = DayLength::Compilation.date(Geotime::from(0)).unwrap();
+        assert_eq!(date.day(), 1);
+        assert_eq!(date.seconds(), 0.0);
+        assert_eq!(date.to_string(), "day 1 of 365, 00:00 of 24:00");
+
+        let date = DayLength::MeanRate.date(Geotime::from_ma(620.0)).unwrap();
+        assert_eq!(date.days_in_year().round(), 400.0);
+        assert!(date.seconds() < date.length_of_day());
+        assert!(date.day() as f64 <= date.days_in_year().ceil());
+        let time = Geotime::from(-MILLISECONDS_IN_JULIAN_YEAR - 1);
+        let date = DayLength::Compilation.date(time).unwrap();
+        assert_eq!(date.day(), 366);
+        assert_eq!(date.to_string(), "day 366 of 365, 05:59 of 24:00");
+    }
+}
@@ -0,0 +1,356 @@
+//! ΔT, the difference TT − UT between uniform Terrestrial Time and Universal Time, which follows
+//! the irregular and slowing rotation of the Earth.  Historical records of eclipses are in local
+//! solar time, and so in UT, while the ephemerides that predict them are in TT: ΔT is about an
+//! hour in the Middle Ages and over three hours in antiquity.
+//!
+//! Three models are provided; each gives a value with an approximate standard error.  Before
+//! 1700 the error follows the fit of Morrison and Stephenson (2004), σ ≈ 0.8 t² s, where t is
+//! centuries from 1820; it is 1 s for telescopic observations and 0.1 s since atomic clocks
+//! (1955).  Predictions beyond 2025 are taken to be uncertain by 32 t² s, with t in centuries from
+//! 2025, the size of the tidal term itself.
+use std::sync::OnceLock;
+
+use crate::interp::Curve;
+use crate::standard::saturated;
+use crate::{Error, Geotime, Measurement, Result, MILLISECONDS_IN_JULIAN_YEAR};
+
+// ΔT in seconds from Morrison and Stephenson (2004), as tabulated by Espenak and Meeus (2006),
+// with observed values since 1955.
+#[rustfmt::skip]
+const MORRISON_STEPHENSON: [(f64, f64); 41] = [
+    (-1000.0, 25400.0), (-900.0, 23700.0), (-800.0, 22000.0), (-700.0, 21000.0),
+    (-600.0, 19040.0), (-500.0, 17190.0), (-400.0, 15530.0), (-300.0, 14080.0),
+    (-200.0, 12790.0), (-100.0, 11640.0), (0.0, 10580.0), (100.0, 9600.0), (200.0, 8640.0),
+    (300.0, 7680.0), (400.0, 6700.0), (500.0, 5710.0), (600.0, 4740.0), (700.0, 3810.0),
+    (800.0, 2960.0), (900.0, 2200.0), (1000.0, 1570.0), (1100.0, 1090.0), (1200.0, 740.0),
+    (1300.0, 490.0), (1400.0, 320.0), (1500.0, 200.0), (1600.0, 120.0), (1700.0, 9.0),
+    (1750.0, 13.0), (1800.0, 14.0), (1850.0, 7.0), (1900.0, -3.0), (1950.0, 29.0),
+    (1955.0, 31.1), (1960.0, 33.2), (1965.0, 35.7), (1970.0, 40.2), (1975.0, 45.5),
+    (1980.0, 50.5), (1990.0, 56.9), (2000.0, 63.8),
+];
+
+/// A model of ΔT = TT − UT.
+///
+/// - `EspenakMeeus`: the piecewise polynomials of Espenak and Meeus (2006), used for the NASA
+///   eclipse canons, from 1999 BC to AD 3000, and the long-term parabola outside that range.
+/// - `MorrisonStephenson`: a natural cubic spline through the values of Morrison and Stephenson
+///   (2004), from 1001 BC to AD 2000, and the long-term parabola outside that range.
+/// - `Parabola`: the long-term parabola of Morrison and Stephenson (2004), −20 + 32 t² s, with t
+///   in centuries from 1820, which reflects tidal braking alone.
+///
+/// ```
+/// use geotime::{Calendar, DeltaT, ReformCalendar};
+///
+/// // The eclipse of 15 April 136 BC, recorded at Babylon.
+/// let ut = ReformCalendar::JULIAN.parse("15 April 136 BC").unwrap();
+/// let dt = DeltaT::EspenakMeeus.seconds(ut);
+/// assert!((dt.value / 3600.0 - 3.3).abs() < 0.1);
+/// assert!(dt.sigma > 200.0);
+///
+/// let tt = DeltaT::EspenakMeeus.ut_to_tt(ut);
+/// assert_eq!(DeltaT::EspenakMeeus.tt_to_ut(tt), ut);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DeltaT {
+    #[default]
+    EspenakMeeus,
+    MorrisonStephenson,
+    Parabola,
+}
+
+impl DeltaT {
+    /// ΔT in seconds at the UT time `ut`, and its approximate standard error.
+    pub fn seconds(self, ut: Geotime) -> Measurement {
+        let year = decimal_year(ut);
+        let value = match self {
+            DeltaT::EspenakMeeus => espenak_meeus(year),
+            DeltaT::MorrisonStephenson => morrison_stephenson()
+                .eval(year)
+                .unwrap_or_else(|| parabola(year)),
+            DeltaT::Parabola => parabola(year),
+        };
+        Measurement::new(value, sigma(year))
+    }
+
+    /// The TT time at the UT time `ut`.  Times beyond the range of a `Geotime` saturate; see
+    /// [`DeltaT::try_ut_to_tt`].
+    pub fn ut_to_tt(self, ut: Geotime) -> Geotime {
+        self.try_ut_to_tt(ut).unwrap_or_else(|_| saturated(ut))
+    }
+
+    /// The TT time at the UT time `ut`, or an error if it lies beyond the range of a `Geotime`.
+    pub fn try_ut_to_tt(self, ut: Geotime) -> Result<Geotime> {
+        millis(self.seconds(ut).value)
+            .and_then(|dt| ut.millis().checked_add(dt))
+            .map(Geotime::from)
+            .ok_or_else(|| out_of_range(ut))
+    }
+
+    /// The UT time at the TT time `tt`, to the nearest millisecond.  Times beyond the range of a
+    /// `Geotime` saturate; see [`DeltaT::try_tt_to_ut`].
+    pub fn tt_to_ut(self, tt: Geotime) -> Geotime {
+        self.try_tt_to_ut(tt).unwrap_or_else(|_| saturated(tt))
+    }
+
+    /// The UT time at the TT time `tt`, to the nearest millisecond, or an error if it lies beyond
+    /// the range of a `Geotime`.
+    pub fn try_tt_to_ut(self, tt: Geotime) -> Result<Geotime> {
+        let mut ut = tt;
+        for _ in 0..4 {
+            ut = millis(self.seconds(ut).value)
+                .and_then(|dt| tt.millis().checked_sub(dt))
+                .map(Geotime::from)
+                .ok_or_else(|| out_of_range(tt))?;
+        }
+        Ok(ut)
+    }
+}
+
+// Seconds in whole milliseconds, or `None` beyond the range of a Geotime.
+fn millis(seconds: f64) -> Option<i128> {
+    let millis = (seconds * 1000.0).round();
+    // i128::MAX rounds up to 2^127 as an f64, which is itself out of range.
+    match millis.is_finite() && millis.abs() < i128::MAX as f64 {
+        true => Some(millis as i128),
+        false => None,
+    }
+}
+
+fn out_of_range(time: Geotime) -> Error {
+    Error::OutOfRange(format!(
+        "ΔT at {} is beyond the range of a Geotime",
+        time.display_string("%Y")
+    ))
+}
+
+// The year, with its fraction, from which the polynomials are evaluated.
+fn decimal_year(time: Geotime) -> f64 {
+    1970.0 + time.millis() as f64 / MILLISECONDS_IN_JULIAN_YEAR as f64
+}
+
+fn parabola(year: f64) -> f64 {
+    let u = (year - 1820.0) / 100.0;
+    -20.0 + 32.0 * u * u
+}
+
+fn sigma(year: f64) -> f64 {
+    match year {
+        y if y > 2025.0 => (32.0 * ((y - 2025.0) / 100.0).powi(2)).max(0.1),
+        y if y >= 1955.0 => 0.1,
+        y if y >= 1700.0 => 1.0,
+        y => (0.8 * ((y - 1820.0) / 100.0).powi(2)).max(20.0),
+    }
+}
+
+fn morrison_stephenson() -> &'static Curve {
+    static CURVE: OnceLock<Curve> = OnceLock::new();
+    CURVE.get_or_init(|| {
+        let (years, values): (Vec<f64>, Vec<f64>) = MORRISON_STEPHENSON.iter().copied().unzip();
+        Curve::natural_spline(&years, &values)
+    })
+}
+
+// Evaluates a polynomial with coefficients in increasing order of power.
+fn polynomial(t: f64, coefficients: &[f64]) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, c| sum * t + c)
+}
+
+fn espenak_meeus(y: f64) -> f64 {
+    match y {
+        y if y < -500.0 => parabola(y),
+        y if y < 500.0 => polynomial(
+            y / 100.0,
+            &[
+                10583.6,
+                -1014.41,
+                33.78311,
+                -5.952053,
+                -0.1798452,
+                0.022174192,
+                0.0090316521,
+            ],
+        ),
+        y if y < 1600.0 => polynomial(
+            (y - 1000.0) / 100.0,
+            &[
+                1574.2,
+                -556.01,
+                71.23472,
+                0.319781,
+                -0.8503463,
+                -0.005050998,
+                0.0083572073,
+            ],
+        ),
+        y if y < 1700.0 => polynomial(y - 1600.0, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0]),
+        y if y < 1800.0 => polynomial(
+            y - 1700.0,
+            &[8.83, 0.1603, -0.0059285, 0.00013336, -1.0 / 1174000.0],
+        ),
+        y if y < 1860.0 => polynomial(
+            y - 1800.0,
+            &[
+                13.72,
+                -0.332447,
+                0.0068612,
+                0.0041116,
+                -0.00037436,
+                0.0000121272,
+                -0.0000001699,
+                0.000000000875,
+            ],
+        ),
+        y if y < 1900.0 => polynomial(
+            y - 1860.0,
+            &[
+                7.62,
+                0.5737,
+                -0.251754,
+                0.01680668,
+                -0.0004473624,
+                1.0 / 233174.0,
+            ],
+        ),
+        y if y < 1920.0 => polynomial(
+            y - 1900.0,
+            &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197],
+        ),
+        y if y < 1941.0 => polynomial(y - 1920.0, &[21.20, 0.84493, -0.076100, 0.0020936]),
+        y if y < 1961.0 => polynomial(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+        y if y < 1986.0 => polynomial(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+        y if y < 2005.0 => polynomial(
+            y - 2000.0,
+            &[
+                63.86,
+                0.3345,
+                -0.060374,
+                0.0017275,
+                0.000651814,
+                0.00002373599,
+            ],
+        ),
+        y if y < 2050.0 => polynomial(y - 2000.0, &[62.92, 0.32217, 0.005589]),
+        y if y < 2150.0 => parabola(y) - 0.5628 * (2150.0 - y),
+        y => parabola(y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(year: f64) -> Geotime {
+        Geotime::from(((year - 1970.0) * MILLISECONDS_IN_JULIAN_YEAR as f64) as i128)
+    }
+
+    #[test]
+    fn espenak_meeus_values() {
+        // Values from the NASA tables of Espenak and Meeus.
+        for (year, expected, tolerance) in [
+            (-500.0, 17190.0, 20.0),
+            (0.0, 10580.0, 10.0),
+            (1000.0, 1570.0, 10.0),
+            (1600.0, 120.0, 1.0),
+            (1800.0, 13.7, 0.5),
+            (1900.0, -2.8, 0.5),
+            (1950.0, 29.1, 0.5),
+            (2000.0, 63.9, 0.5),
+        ] {
+            let dt = DeltaT::EspenakMeeus.seconds(at(year)).value;
+            assert!((dt - expected).abs() < tolerance, "{}: {}", year, dt);
+        }
+    }
+
+    #[test]
+    fn models_agree() {
+        for year in [-1000.0, -500.0, 0.0, 500.0, 1000.0, 1500.0, 1800.0, 1990.0] {
+            let em = DeltaT::EspenakMeeus.seconds(at(year));
+            let ms = DeltaT::MorrisonStephenson.seconds(at(year)).value;
+            assert!(
+                (em.value - ms).abs() < em.sigma,
+                "{}: {} {}",
+                year,
+                em.value,
+                ms
+            );
+        }
+        // Outside the tables, both follow the parabola.
+        let far = at(-5000.0);
+        assert_eq!(
+            DeltaT::MorrisonStephenson.seconds(far),
+            DeltaT::Parabola.seconds(far)
+        );
+        assert_eq!(
+            DeltaT::EspenakMeeus.seconds(far),
+            DeltaT::Parabola.seconds(far)
+        );
+    }
+
+    #[test]
+    fn continuity() {
+        // The polynomials join without large steps.
+        for boundary in [
+            500.0, 1600.0, 1700.0, 1800.0, 1860.0, 1900.0, 1920.0, 1941.0, 1961.0,
+        ] {
+            let before = espenak_meeus(boundary - 1e-6);
+            let after = espenak_meeus(boundary + 1e-6);
+            assert!(
+                (before - after).abs() < 5.0,
+                "{}: {} {}",
+                boundary,
+                before,
+                after
+            );
+        }
+    }
+
+    #[test]
+    fn uncertainty() {
+        assert!(DeltaT::Parabola.seconds(at(-2000.0)).sigma > 1000.0);
+        assert_eq!(DeltaT::Parabola.seconds(at(2000.0)).sigma, 0.1);
+        assert!(DeltaT::Parabola.seconds(at(2125.0)).sigma > 30.0);
+        assert!(DeltaT::Parabola.seconds(at(1850.0)).sigma < 2.0);
+    }
+
+    #[test]
+    fn conversions() {
+        for model in [
+            DeltaT::EspenakMeeus,
+            DeltaT::MorrisonStephenson,
+            DeltaT::Parabola,
+        ] {
+            for year in [-3000.0, -700.0, 1200.0, 1990.0, 2500.0] {
+                let ut = at(year);
+                let tt = model.ut_to_tt(ut);
+                assert!(tt > ut || year > 1850.0 && year < 1905.0);
+                assert_eq!(model.tt_to_ut(tt), ut, "{:?} {}", model, year);
+            }
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        for model in [
+            DeltaT::EspenakMeeus,
+            DeltaT::MorrisonStephenson,
+            DeltaT::Parabola,
+        ] {
+            for millis in [i128::MIN, i128::MIN / 2, i128::MAX / 2, i128::MAX] {
+                let time = Geotime::from(millis);
+                assert!(matches!(
+                    model.try_ut_to_tt(time),
+                    Err(Error::OutOfRange(_))
+                ));
+                assert!(matches!(
+                    model.try_tt_to_ut(time),
+                    Err(Error::OutOfRange(_))
+                ));
+            }
+            let time = Geotime::from(i128::MIN / 2);
+            assert_eq!(model.ut_to_tt(time), Geotime::from(i128::MIN));
+            assert_eq!(model.tt_to_ut(time), Geotime::from(i128::MIN));
+            let time = Geotime::from_ma(100.0);
+            assert!(model.try_ut_to_tt(time).unwrap() > time);
+        }
+    }
+}
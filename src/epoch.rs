@@ -0,0 +1,663 @@
+//! Timestamps of other systems, which count from other epochs in other units: NTP, TAI64, Windows
+//! FILETIME, .NET ticks, Excel serial dates, Apple Cocoa dates and GPS weeks.
+//!
+//! A `Geotime` resolves milliseconds, so a conversion from a `Geotime` and back is exact, while a
+//! finer foreign timestamp comes in to the nearest millisecond.  Values outside the range of the
+//! other system give an `OutOfRange` error rather than wrapping.  TAI64 labels and GPS weeks are
+//! in atomic time, and convert through the current table of leap seconds.
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Geotime, Result, TimeStandard, MILLISECONDS_IN_DAY};
+
+// Offsets of the epochs from the Unix epoch, in milliseconds: 1900-01-01 (NTP), 1601-01-01
+// (FILETIME), 0001-01-01 (.NET), 2001-01-01 (Cocoa) and 1980-01-06 (GPS, in GPS time).
+const NTP_EPOCH: i128 = -2_208_988_800_000;
+const FILETIME_EPOCH: i128 = -11_644_473_600_000;
+const DOTNET_EPOCH: i128 = -62_135_596_800_000;
+const COCOA_EPOCH: i128 = 978_307_200_000;
+const GPS_EPOCH: i128 = 315_964_800_000;
+
+// The last .NET tick, at the end of 9999-12-31.
+const DOTNET_MAX_TICKS: i64 = 3_155_378_975_999_999_999;
+
+const TAI64_OFFSET: i128 = 1 << 62;
+const MILLISECONDS_IN_WEEK: u32 = 604_800_000;
+
+// The largest number of milliseconds that a double holds exactly.
+const EXACT_F64: i128 = 1 << 53;
+
+fn out_of_range(system: &str, value: impl fmt::Display) -> Error {
+    Error::OutOfRange(format!("{} is out of range for {}", value, system))
+}
+
+/// An NTP timestamp: seconds since 1900-01-01 in a 32-bit field that rolls over every 136 years,
+/// the era that counts the rollovers, and a 32-bit binary fraction of a second.  Like Unix time,
+/// NTP time skips over leap seconds.
+///
+/// ```
+/// use geotime::{Geotime, NtpTimestamp};
+///
+/// let ntp = NtpTimestamp::try_from(Geotime::from(500)).unwrap();
+/// assert_eq!((ntp.era, ntp.seconds, ntp.fraction), (0, 2_208_988_800, 1 << 31));
+/// assert_eq!(Geotime::from(ntp), Geotime::from(500));
+///
+/// // Era 1 begins in 2036.
+/// let ntp = NtpTimestamp::from_bits(0, 1);
+/// assert_eq!(Geotime::from(ntp).display_string("%Y-%m-%dT%H:%M:%S"), "2036-02-07T06:28:16");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NtpTimestamp {
+    pub era: i32,
+    pub seconds: u32,
+    pub fraction: u32,
+}
+
+impl NtpTimestamp {
+    /// A timestamp from its 64-bit wire format, with the era supplied separately.
+    pub fn from_bits(bits: u64, era: i32) -> Self {
+        Self {
+            era,
+            seconds: (bits >> 32) as u32,
+            fraction: bits as u32,
+        }
+    }
+
+    /// The 64-bit wire format, without the era.
+    pub fn bits(&self) -> u64 {
+        ((self.seconds as u64) << 32) | self.fraction as u64
+    }
+}
+
+impl TryFrom<Geotime> for NtpTimestamp {
+    type Error = Error;
+
+    fn try_from(time: Geotime) -> Result<Self> {
+        let millis = time
+            .millis()
+            .checked_sub(NTP_EPOCH)
+            .ok_or_else(|| out_of_range("NTP", time.display_string("%Y")))?;
+        let seconds = millis.div_euclid(1000);
+        let era = i32::try_from(seconds >> 32).map_err(|_| out_of_range("NTP", seconds))?;
+        Ok(Self {
+            era,
+            seconds: seconds as u32,
+            fraction: ((millis.rem_euclid(1000) << 32) + 500).div_euclid(1000) as u32,
+        })
+    }
+}
+
+/// The nearest millisecond.
+impl From<NtpTimestamp> for Geotime {
+    fn from(ntp: NtpTimestamp) -> Self {
+        let seconds = ((ntp.era as i128) << 32) + ntp.seconds as i128;
+        let millis = (ntp.fraction as i128 * 1000 + (1 << 31)) >> 32;
+        Geotime::from(seconds * 1000 + millis + NTP_EPOCH)
+    }
+}
+
+/// A TAI64NA label: seconds of TAI offset by 2^62, so that 1970-01-01T00:00:00 TAI is label
+/// 2^62, with nanoseconds and attoseconds.  The TAI64 and TAI64N external formats are its first 8
+/// and 12 bytes, and TAI64NA is all 16, big-endian.
+///
+/// ```
+/// use geotime::{Geotime, Tai64};
+///
+/// let label: Tai64 = "@400000002a2b2c2d00000000".parse().unwrap();
+/// let utc = Geotime::from(label);
+/// // 1992-06-02T08:07:09 TAI, when TAI was 26 s ahead of UTC.
+/// assert_eq!(utc.display_string("%Y-%m-%dT%H:%M:%S"), "1992-06-02T08:06:43");
+///
+/// let label = Tai64::try_from(utc).unwrap();
+/// assert_eq!(label.to_string(), "@400000002a2b2c2d00000000");
+/// assert_eq!(label.tai64(), [0x40, 0, 0, 0, 0x2a, 0x2b, 0x2c, 0x2d]);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Tai64 {
+    label: u64,
+    nanoseconds: u32,
+    attoseconds: u32,
+}
+
+impl Tai64 {
+    /// A label, which must be below 2^63, with nanoseconds and attoseconds below 10^9.
+    pub fn new(label: u64, nanoseconds: u32, attoseconds: u32) -> Result<Self> {
+        if label >= 1 << 63 || nanoseconds >= 1_000_000_000 || attoseconds >= 1_000_000_000 {
+            return Err(Error::InvalidInput(format!(
+                "invalid TAI64NA label: {} {} {}",
+                label, nanoseconds, attoseconds
+            )));
+        }
+        Ok(Self {
+            label,
+            nanoseconds,
+            attoseconds,
+        })
+    }
+
+    /// Reads the TAI64, TAI64N or TAI64NA external format, of 8, 12 or 16 bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let word =
+            |i: usize| u32::from_be_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        let (nanoseconds, attoseconds) = match bytes.len() {
+            8 => (0, 0),
+            12 => (word(8), 0),
+            16 => (word(8), word(12)),
+            n => {
+                return Err(Error::InvalidInput(format!(
+                    "a TAI64 label has 8, 12 or 16 bytes, not {}",
+                    n
+                )))
+            }
+        };
+        let label = u64::from_be_bytes(bytes[..8].try_into().unwrap_or_default());
+        Self::new(label, nanoseconds, attoseconds)
+    }
+
+    pub fn label(&self) -> u64 {
+        self.label
+    }
+
+    pub fn nanoseconds(&self) -> u32 {
+        self.nanoseconds
+    }
+
+    pub fn attoseconds(&self) -> u32 {
+        self.attoseconds
+    }
+
+    pub fn tai64(&self) -> [u8; 8] {
+        self.label.to_be_bytes()
+    }
+
+    pub fn tai64n(&self) -> [u8; 12] {
+        let mut bytes = [0; 12];
+        bytes[..8].copy_from_slice(&self.tai64());
+        bytes[8..].copy_from_slice(&self.nanoseconds.to_be_bytes());
+        bytes
+    }
+
+    pub fn tai64na(&self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..12].copy_from_slice(&self.tai64n());
+        bytes[12..].copy_from_slice(&self.attoseconds.to_be_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<Geotime> for Tai64 {
+    type Error = Error;
+
+    fn try_from(utc: Geotime) -> Result<Self> {
+        let tai = TimeStandard::TAI
+            .try_at_utc(utc)
+            .map_err(|_| out_of_range("TAI64", utc.display_string("%Y")))?
+            .millis();
+        let label = tai.div_euclid(1000) + TAI64_OFFSET;
+        let label = u64::try_from(label)
+            .ok()
+            .filter(|l| *l < 1 << 63)
+            .ok_or_else(|| out_of_range("TAI64", utc.display_string("%Y")))?;
+        Self::new(label, tai.rem_euclid(1000) as u32 * 1_000_000, 0)
+    }
+}
+
+/// The nearest millisecond, in UTC.
+impl From<Tai64> for Geotime {
+    fn from(label: Tai64) -> Self {
+        let attoseconds = label.nanoseconds as i128 * 1_000_000_000 + label.attoseconds as i128;
+        let millis = (attoseconds + 500_000_000_000_000) / 1_000_000_000_000_000;
+        let tai = (label.label as i128 - TAI64_OFFSET) * 1000 + millis;
+        TimeStandard::TAI.to_utc(Geotime::from(tai))
+    }
+}
+
+/// Formats the label as TAI64N in hexadecimal after an "@", as daemontools does.
+impl fmt::Display for Tai64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", hex::encode(self.tai64n()))
+    }
+}
+
+/// Parses a TAI64, TAI64N or TAI64NA label in hexadecimal, with or without a leading "@".
+impl FromStr for Tai64 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let bytes = hex::decode(s.strip_prefix('@').unwrap_or(s))
+            .map_err(|_| Error::InvalidInput(format!("unable to parse TAI64 label: {}", s)))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// A GPS week and the milliseconds into it.  Weeks count from 1980-01-06 in GPS time, which is
+/// ahead of UTC by the leap seconds since then.  Receivers broadcast the week modulo 1024 or 8192;
+/// [`GpsWeek::resolve`] recovers the full week near a known time.
+///
+/// ```
+/// use geotime::{Geotime, GpsWeek};
+///
+/// let utc = Geotime::from(1_483_228_800_000_i64); // 2017-01-01T00:00:00Z
+/// let gps = GpsWeek::try_from(utc).unwrap();
+/// assert_eq!((gps.week(), gps.seconds()), (1930, 18.0));
+/// assert_eq!(gps.rolled_over(10), 906);
+/// assert_eq!(GpsWeek::resolve(906, 10, gps.millis(), utc).unwrap(), gps);
+/// assert_eq!(Geotime::from(gps), utc);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct GpsWeek {
+    week: u32,
+    millis: u32,
+}
+
+impl GpsWeek {
+    pub fn new(week: u32, millis: u32) -> Result<Self> {
+        match millis < MILLISECONDS_IN_WEEK {
+            true => Ok(Self { week, millis }),
+            false => Err(out_of_range("a GPS week", format!("{} ms", millis))),
+        }
+    }
+
+    /// The full week that is `week` modulo 2^`bits` and is nearest to `near`.  `bits` must be
+    /// from 1 to 31.
+    pub fn resolve(week: u32, bits: u32, millis: u32, near: Geotime) -> Result<Self> {
+        if !(1..=31).contains(&bits) {
+            return Err(Error::InvalidInput(format!(
+                "invalid GPS week field: {} bits",
+                bits
+            )));
+        }
+        let cycle = 1_i64 << bits;
+        let near = match GpsWeek::try_from(near) {
+            Ok(gps) => gps.week as i64,
+            Err(_) => 0,
+        };
+        let week = week as i64 % cycle;
+        let full = week + ((near - week) as f64 / cycle as f64).round() as i64 * cycle;
+        let full = u32::try_from(full.max(week)).map_err(|_| out_of_range("GPS", full))?;
+        Self::new(full, millis)
+    }
+
+    pub fn week(&self) -> u32 {
+        self.week
+    }
+
+    /// Milliseconds since the start of the week.
+    pub fn millis(&self) -> u32 {
+        self.millis
+    }
+
+    /// Seconds since the start of the week.
+    pub fn seconds(&self) -> f64 {
+        self.millis as f64 / 1000.0
+    }
+
+    /// The week as broadcast in a field of `bits` bits: 10 in the legacy navigation message and
+    /// 13 in the modernized ones.  A field of 32 bits or more holds the week unchanged.
+    pub fn rolled_over(&self, bits: u32) -> u32 {
+        match 1_u32.checked_shl(bits) {
+            Some(cycle) => self.week % cycle,
+            _ => self.week,
+        }
+    }
+}
+
+impl TryFrom<Geotime> for GpsWeek {
+    type Error = Error;
+
+    fn try_from(utc: Geotime) -> Result<Self> {
+        let out_of_range = || out_of_range("GPS", utc.display_string("%Y-%m-%d"));
+        let millis = TimeStandard::GPS
+            .try_at_utc(utc)
+            .ok()
+            .and_then(|gps| gps.millis().checked_sub(GPS_EPOCH))
+            .ok_or_else(out_of_range)?;
+        let week = millis.div_euclid(MILLISECONDS_IN_WEEK as i128);
+        let week = u32::try_from(week).map_err(|_| out_of_range())?;
+        Self::new(week, millis.rem_euclid(MILLISECONDS_IN_WEEK as i128) as u32)
+    }
+}
+
+/// In UTC.
+impl From<GpsWeek> for Geotime {
+    fn from(gps: GpsWeek) -> Self {
+        let millis = gps.week as i128 * MILLISECONDS_IN_WEEK as i128 + gps.millis as i128;
+        TimeStandard::GPS.to_utc(Geotime::from(millis + GPS_EPOCH))
+    }
+}
+
+/// The epoch of an Excel workbook.
+///
+/// - `Windows1900`: serial 1 is 1900-01-01.  Serial 60 is 29 February 1900, which did not exist
+///   but which Lotus 1-2-3 counted, and Excel after it, so later serials are a day ahead.
+/// - `Mac1904`: serial 0 is 1904-01-01, the default of early Excel for the Macintosh.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ExcelDateSystem {
+    #[default]
+    Windows1900,
+    Mac1904,
+}
+
+impl ExcelDateSystem {
+    // The day before serial 1 of 1900, or serial 0 of 1904, and the largest serial, the end of
+    // 9999-12-31.
+    fn epoch(self) -> (i128, f64) {
+        match self {
+            ExcelDateSystem::Windows1900 => (-2_209_075_200_000, 2_958_466.0),
+            ExcelDateSystem::Mac1904 => (-2_082_844_800_000, 2_957_004.0),
+        }
+    }
+}
+
+impl Geotime {
+    /// Timestamp for a Windows FILETIME, in 100 ns intervals since 1601-01-01, to the nearest
+    /// millisecond.
+    ///
+    /// ```
+    /// use geotime::Geotime;
+    ///
+    /// assert_eq!(Geotime::from_filetime(116_444_736_000_000_000), Geotime::from(0));
+    /// assert_eq!(Geotime::from(0).to_filetime().unwrap(), 116_444_736_000_000_000);
+    /// assert!(Geotime::from_ma(1.0).to_filetime().is_err());
+    /// ```
+    pub fn from_filetime(filetime: u64) -> Self {
+        Self::from((filetime as i128 + 5000) / 10_000 + FILETIME_EPOCH)
+    }
+
+    pub fn to_filetime(self) -> Result<u64> {
+        self.millis()
+            .checked_sub(FILETIME_EPOCH)
+            .and_then(|millis| millis.checked_mul(10_000))
+            .and_then(|ticks| u64::try_from(ticks).ok())
+            .ok_or_else(|| out_of_range("FILETIME", self.display_string("%Y")))
+    }
+
+    /// Timestamp for a .NET `DateTime` in ticks of 100 ns since 0001-01-01, to the nearest
+    /// millisecond.  `DateTime` runs to the end of 9999-12-31.
+    ///
+    /// ```
+    /// use geotime::Geotime;
+    ///
+    /// assert_eq!(Geotime::from(0).to_dotnet_ticks().unwrap(), 621_355_968_000_000_000);
+    /// let first = Geotime::from_dotnet_ticks(0).unwrap();
+    /// assert_eq!(first.display_string("%Y-%m-%d"), "0001-01-01");
+    /// assert!(Geotime::from_dotnet_ticks(-1).is_err());
+    /// ```
+    pub fn from_dotnet_ticks(ticks: i64) -> Result<Self> {
+        if !(0..=DOTNET_MAX_TICKS).contains(&ticks) {
+            return Err(out_of_range(".NET DateTime", ticks));
+        }
+        Ok(Self::from((ticks as i128 + 5000) / 10_000 + DOTNET_EPOCH))
+    }
+
+    pub fn to_dotnet_ticks(self) -> Result<i64> {
+        self.millis()
+            .checked_sub(DOTNET_EPOCH)
+            .and_then(|millis| millis.checked_mul(10_000))
+            .and_then(|ticks| i64::try_from(ticks).ok())
+            .filter(|ticks| (0..=DOTNET_MAX_TICKS).contains(ticks))
+            .ok_or_else(|| out_of_range(".NET DateTime", self.display_string("%Y")))
+    }
+
+    /// Timestamp for an Excel serial date, in days and fractions of a day from the epoch of
+    /// `system`, to the nearest millisecond.  Excel has no negative serials, and the fictitious
+    /// 29 February 1900 is an error.
+    ///
+    /// ```
+    /// use geotime::{ExcelDateSystem, Geotime};
+    ///
+    /// let system = ExcelDateSystem::Windows1900;
+    /// let time = Geotime::from_excel(45292.5, system).unwrap();
+    /// assert_eq!(time.display_string("%Y-%m-%dT%H:%M"), "2024-01-01T12:00");
+    /// assert_eq!(time.to_excel(ExcelDateSystem::Mac1904).unwrap(), 43830.5);
+    ///
+    /// let march = Geotime::from_excel(61.0, system).unwrap();
+    /// assert_eq!(march.display_string("%Y-%m-%d"), "1900-03-01");
+    /// assert!(Geotime::from_excel(60.0, system).is_err());
+    /// ```
+    pub fn from_excel(serial: f64, system: ExcelDateSystem) -> Result<Self> {
+        let (epoch, end) = system.epoch();
+        if !(0.0..end).contains(&serial) {
+            return Err(out_of_range("Excel", serial));
+        }
+        let days = match (system, serial) {
+            (ExcelDateSystem::Windows1900, s) if (60.0..61.0).contains(&s) => {
+                return Err(Error::InvalidInput(
+                    "serial 60 is 29 February 1900, which did not exist".to_string(),
+                ))
+            }
+            (ExcelDateSystem::Windows1900, s) if s >= 61.0 => s - 1.0,
+            (_, s) => s,
+        };
+        let millis = (days * MILLISECONDS_IN_DAY as f64).round() as i128;
+        Ok(Self::from(epoch + millis))
+    }
+
+    pub fn to_excel(self, system: ExcelDateSystem) -> Result<f64> {
+        let (epoch, end) = system.epoch();
+        let out_of_range = || out_of_range("Excel", self.display_string("%Y-%m-%d"));
+        let millis = self.millis().checked_sub(epoch).ok_or_else(out_of_range)?;
+        let days = millis as f64 / MILLISECONDS_IN_DAY as f64;
+        let serial = match system {
+            ExcelDateSystem::Windows1900 if days >= 60.0 => days + 1.0,
+            _ => days,
+        };
+        match (0.0..end).contains(&serial) {
+            true => Ok(serial),
+            false => Err(out_of_range()),
+        }
+    }
+
+    /// Timestamp for an Apple Cocoa date (`NSDate`, `CFAbsoluteTime`), in seconds since
+    /// 2001-01-01, to the nearest millisecond.  A double holds milliseconds exactly for about
+    /// 285,000 years either side, and further dates are out of range.
+    ///
+    /// ```
+    /// use geotime::Geotime;
+    ///
+    /// assert_eq!(Geotime::from(0).to_cocoa().unwrap(), -978_307_200.0);
+    /// let time = Geotime::from_cocoa(0.001).unwrap();
+    /// assert_eq!(time.display_string("%Y-%m-%dT%H:%M:%S%.3f"), "2001-01-01T00:00:00.001");
+    /// assert!(Geotime::from_cocoa(f64::NAN).is_err());
+    /// ```
+    pub fn from_cocoa(seconds: f64) -> Result<Self> {
+        let millis = (seconds * 1000.0).round();
+        if !millis.is_finite() || millis.abs() > EXACT_F64 as f64 {
+            return Err(out_of_range("Cocoa", seconds));
+        }
+        Ok(Self::from(millis as i128 + COCOA_EPOCH))
+    }
+
+    pub fn to_cocoa(self) -> Result<f64> {
+        match self.millis().checked_sub(COCOA_EPOCH) {
+            Some(millis) if millis.unsigned_abs() <= EXACT_F64 as u128 => {
+                Ok(millis as f64 / 1000.0)
+            }
+            _ => Err(out_of_range("Cocoa", self.display_string("%Y"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::chrono::{TimeZone, Utc};
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> Geotime {
+        Geotime::from(&Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap())
+    }
+
+    const TIMES: [i128; 6] = [
+        0,
+        1,
+        -1,
+        1_700_000_000_123,
+        -2_208_988_800_001,
+        2_085_978_496_999,
+    ];
+
+    #[test]
+    fn ntp() {
+        for millis in TIMES {
+            let time = Geotime::from(millis);
+            let ntp = NtpTimestamp::try_from(time).unwrap();
+            assert_eq!(Geotime::from(ntp), time);
+            assert_eq!(NtpTimestamp::from_bits(ntp.bits(), ntp.era), ntp);
+        }
+        let before = NtpTimestamp::try_from(Geotime::from(NTP_EPOCH - 1000)).unwrap();
+        assert_eq!((before.era, before.seconds), (-1, u32::MAX));
+        let last = NtpTimestamp::try_from(Geotime::from(NTP_EPOCH - 1)).unwrap();
+        assert_eq!(last.fraction, 4_290_672_329);
+        assert!(NtpTimestamp::try_from(Geotime::from_ma(1e6)).is_err());
+    }
+
+    #[test]
+    fn tai64() {
+        let epoch = Tai64::try_from(Geotime::from(0)).unwrap();
+        // UTC is taken to be 10 s behind TAI before 1972.
+        assert_eq!(epoch.label(), (1 << 62) + 10);
+        for millis in TIMES {
+            let time = Geotime::from(millis);
+            let label = Tai64::try_from(time).unwrap();
+            assert_eq!(Geotime::from(label), time);
+            assert_eq!(Tai64::from_bytes(&label.tai64na()).unwrap(), label);
+            assert_eq!(label.to_string().parse::<Tai64>().unwrap(), label);
+        }
+        let label = Tai64::new(1 << 62, 999_999, 600_000_000).unwrap();
+        assert_eq!(Geotime::from(label), Geotime::from(-9999));
+        assert_eq!(
+            "400000000000000a".parse::<Tai64>().unwrap(),
+            Tai64::new((1 << 62) + 10, 0, 0).unwrap()
+        );
+        assert!("@c000000000000000".parse::<Tai64>().is_err());
+        assert!("@4000000000000000ffffffff".parse::<Tai64>().is_err());
+        assert!("@40000000".parse::<Tai64>().is_err());
+        assert!("@40000000000000zz".parse::<Tai64>().is_err());
+        assert!(Tai64::try_from(Geotime::from_ma(1e6)).is_err());
+    }
+
+    #[test]
+    fn gps() {
+        let epoch = GpsWeek::try_from(utc(1980, 1, 6, 0, 0, 0)).unwrap();
+        assert_eq!((epoch.week(), epoch.millis()), (0, 0));
+        for millis in TIMES.into_iter().filter(|m| *m > GPS_EPOCH) {
+            let time = Geotime::from(millis);
+            assert_eq!(Geotime::from(GpsWeek::try_from(time).unwrap()), time);
+        }
+        assert!(GpsWeek::try_from(utc(1980, 1, 5, 23, 59, 59)).is_err());
+        assert!(GpsWeek::new(0, MILLISECONDS_IN_WEEK).is_err());
+
+        // The second rollover of the 10-bit week, in April 2019.
+        let near = utc(2019, 4, 7, 0, 0, 0);
+        assert_eq!(GpsWeek::try_from(near).unwrap().week(), 2048);
+        assert_eq!(GpsWeek::resolve(1023, 10, 0, near).unwrap().week(), 2047);
+        assert_eq!(GpsWeek::resolve(0, 10, 0, near).unwrap().week(), 2048);
+        assert_eq!(
+            GpsWeek::resolve(5, 10, 0, utc(1970, 1, 1, 0, 0, 0))
+                .unwrap()
+                .week(),
+            5
+        );
+        assert_eq!(GpsWeek::resolve(2048, 13, 0, near).unwrap().week(), 2048);
+        for bits in [0, 32, 64, u32::MAX] {
+            assert!(matches!(
+                GpsWeek::resolve(5, bits, 0, near),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+
+        let gps = GpsWeek::new(2048, 0).unwrap();
+        assert_eq!(gps.rolled_over(0), 0);
+        assert_eq!(gps.rolled_over(10), 0);
+        assert_eq!(gps.rolled_over(13), 2048);
+        assert_eq!(gps.rolled_over(32), 2048);
+        assert_eq!(gps.rolled_over(u32::MAX), 2048);
+    }
+
+    #[test]
+    fn windows_and_dotnet() {
+        for millis in TIMES {
+            let time = Geotime::from(millis);
+            assert_eq!(Geotime::from_filetime(time.to_filetime().unwrap()), time);
+            let ticks = time.to_dotnet_ticks().unwrap();
+            assert_eq!(Geotime::from_dotnet_ticks(ticks).unwrap(), time);
+        }
+        assert_eq!(utc(1601, 1, 1, 0, 0, 0).to_filetime().unwrap(), 0);
+        assert!(Geotime::from(FILETIME_EPOCH - 1).to_filetime().is_err());
+        assert!(Geotime::from_filetime(u64::MAX) > utc(60000, 1, 1, 0, 0, 0));
+
+        let last = Geotime::from_dotnet_ticks(DOTNET_MAX_TICKS - 5000).unwrap();
+        assert_eq!(
+            last.display_string("%Y-%m-%dT%H:%M:%S%.3f"),
+            "9999-12-31T23:59:59.999"
+        );
+        assert!(last.to_dotnet_ticks().is_ok());
+        assert!(Geotime::from(last.millis() + 1).to_dotnet_ticks().is_err());
+        assert!(Geotime::from_dotnet_ticks(DOTNET_MAX_TICKS + 1).is_err());
+    }
+
+    #[test]
+    fn excel() {
+        let system = ExcelDateSystem::Windows1900;
+        for (serial, time) in [
+            (0.0, utc(1899, 12, 31, 0, 0, 0)),
+            (1.0, utc(1900, 1, 1, 0, 0, 0)),
+            (59.0, utc(1900, 2, 28, 0, 0, 0)),
+            (59.75, utc(1900, 2, 28, 18, 0, 0)),
+            (61.0, utc(1900, 3, 1, 0, 0, 0)),
+            (45292.0, utc(2024, 1, 1, 0, 0, 0)),
+        ] {
+            assert_eq!(Geotime::from_excel(serial, system).unwrap(), time);
+            assert_eq!(time.to_excel(system).unwrap(), serial);
+        }
+        assert!(Geotime::from_excel(60.5, system).is_err());
+        assert!(Geotime::from_excel(-1.0, system).is_err());
+        assert!(Geotime::from_excel(f64::NAN, system).is_err());
+        assert!(utc(1899, 12, 30, 0, 0, 0).to_excel(system).is_err());
+        assert!(utc(9999, 12, 31, 23, 0, 0).to_excel(system).is_ok());
+
+        let mac = ExcelDateSystem::Mac1904;
+        assert_eq!(utc(1904, 1, 1, 0, 0, 0).to_excel(mac).unwrap(), 0.0);
+        assert!(utc(1903, 12, 31, 0, 0, 0).to_excel(mac).is_err());
+        for millis in TIMES.into_iter().filter(|m| *m > 0) {
+            let time = Geotime::from(millis);
+            for system in [system, mac] {
+                let serial = time.to_excel(system).unwrap();
+                assert_eq!(Geotime::from_excel(serial, system).unwrap(), time);
+            }
+        }
+    }
+
+    #[test]
+    fn cocoa() {
+        for millis in TIMES {
+            let time = Geotime::from(millis);
+            assert_eq!(Geotime::from_cocoa(time.to_cocoa().unwrap()).unwrap(), time);
+        }
+        assert_eq!(utc(2001, 1, 1, 0, 0, 0).to_cocoa().unwrap(), 0.0);
+        assert!(Geotime::from_ma(1.0).to_cocoa().is_err());
+        assert!(Geotime::from_cocoa(f64::INFINITY).is_err());
+        assert!(Geotime::from_cocoa(1e20).is_err());
+    }
+
+    #[test]
+    fn extremes() {
+        let out_of_range = |result: Result<()>| matches!(result, Err(Error::OutOfRange(_)));
+        for millis in [i128::MIN, i128::MAX, i128::MIN / 2, i128::MAX / 2] {
+            let time = Geotime::from(millis);
+            assert!(out_of_range(NtpTimestamp::try_from(time).map(drop)));
+            assert!(out_of_range(Tai64::try_from(time).map(drop)));
+            assert!(out_of_range(GpsWeek::try_from(time).map(drop)));
+            assert!(out_of_range(time.to_filetime().map(drop)));
+            assert!(out_of_range(time.to_dotnet_ticks().map(drop)));
+            assert!(out_of_range(
+                time.to_excel(ExcelDateSystem::Windows1900).map(drop)
+            ));
+            assert!(out_of_range(
+                time.to_excel(ExcelDateSystem::Mac1904).map(drop)
+            ));
+            assert!(out_of_range(time.to_cocoa().map(drop)));
+        }
+    }
+}
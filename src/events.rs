@@ -0,0 +1,415 @@
+//! A catalog of notable events, from the Big Bang to the projected end of the Sun's main-sequence
+//! life, with fuzzy search by name and lookups by time.
+//!
+//! The bundled events carry their published uncertainties where there are any.  Projected future
+//! events are given as negative ages, so they sort after the present like any other timestamp.
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::{GeoInterval, Geotime, UncertainGeotime};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Category {
+    Cosmic,
+    Geologic,
+    Biologic,
+    Extinction,
+    Human,
+    Historical,
+    Future,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Cosmic => "cosmic",
+            Self::Geologic => "geologic",
+            Self::Biologic => "biologic",
+            Self::Extinction => "extinction",
+            Self::Human => "human",
+            Self::Historical => "historical",
+            Self::Future => "future",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+enum When {
+    // Age and uncertainty in Ma.
+    Ma(f64, f64),
+    // Age and uncertainty in years before present.
+    YearsBp(f64, f64),
+    // A UTC date and time.
+    Date(DateTime<Utc>),
+}
+
+// A UTC date and time for the table, checked at compile time.
+const fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+    match (
+        NaiveDate::from_ymd_opt(year, month, day),
+        NaiveTime::from_hms_opt(hour, min, sec),
+    ) {
+        (Some(date), Some(time)) => date.and_time(time).and_utc(),
+        _ => panic!("invalid date"),
+    }
+}
+
+use Category::{Biologic, Cosmic, Extinction, Future, Geologic, Historical, Human};
+use When::{Date, Ma, YearsBp};
+
+#[rustfmt::skip]
+const EVENTS: &[(&str, &[&str], Category, When)] = &[
+    ("Big Bang", &[], Cosmic, Ma(13787.0, 20.0)),
+    ("Formation of the Solar System", &["CAI formation"], Cosmic, Ma(4567.3, 0.16)),
+    ("Moon-forming impact", &["Giant impact", "Theia impact"], Cosmic, Ma(4510.0, 10.0)),
+    ("Oldest zircon", &["Jack Hills zircon"], Geologic, Ma(4404.0, 8.0)),
+    ("Great Oxidation Event", &["GOE", "Great Oxygenation Event"], Geologic, Ma(2400.0, 100.0)),
+    ("Sturtian glaciation", &["Snowball Earth"], Geologic, Ma(717.4, 0.2)),
+    ("Cambrian explosion", &[], Biologic, Ma(538.8, 0.2)),
+    ("End-Ordovician extinction", &["Ordovician-Silurian extinction"], Extinction, Ma(443.8, 1.5)),
+    ("Late Devonian extinction", &["Kellwasser event", "Frasnian-Famennian extinction"], Extinction, Ma(372.2, 1.6)),
+    ("End-Permian extinction", &["Great Dying", "Permian-Triassic extinction"], Extinction, Ma(251.941, 0.037)),
+    ("End-Triassic extinction", &["Triassic-Jurassic extinction"], Extinction, Ma(201.564, 0.015)),
+    ("End-Cretaceous extinction", &["K-Pg extinction", "K-T extinction", "Chicxulub impact"], Extinction, Ma(66.043, 0.011)),
+    ("Paleocene-Eocene Thermal Maximum", &["PETM"], Geologic, Ma(56.0, 0.05)),
+    ("First hominins", &["Sahelanthropus"], Human, Ma(7.0, 0.5)),
+    ("Earliest Homo sapiens", &["Jebel Irhoud"], Human, YearsBp(315e3, 34e3)),
+    ("Invention of writing", &["Cuneiform"], Historical, YearsBp(5150.0, 100.0)),
+    ("Gregorian calendar reform", &["Inter gravissimas"], Historical, Date(utc(1582, 10, 15, 0, 0, 0))),
+    ("Trinity test", &["First nuclear explosion"], Historical, Date(utc(1945, 7, 16, 11, 29, 21))),
+    ("Apollo 11 Moon landing", &["First Moon landing"], Historical, Date(utc(1969, 7, 20, 20, 17, 40))),
+    ("Unix epoch", &[], Historical, Date(utc(1970, 1, 1, 0, 0, 0))),
+    ("Andromeda-Milky Way collision", &["Milkomeda"], Future, Ma(-4500.0, 500.0)),
+    ("Sun leaves the main sequence", &["Sun becomes a red giant"], Future, Ma(-5000.0, 500.0)),
+];
+
+/// A named event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    name: String,
+    aliases: Vec<String>,
+    category: Category,
+    time: UncertainGeotime,
+}
+
+impl Event {
+    pub fn new<T: Into<UncertainGeotime>>(name: &str, category: Category, time: T) -> Self {
+        Self {
+            name: name.to_string(),
+            aliases: Vec::new(),
+            category,
+            time: time.into(),
+        }
+    }
+
+    /// Another name by which the event can be found.
+    pub fn with_alias(mut self, alias: &str) -> Self {
+        self.aliases.push(alias.to_string());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    pub fn time(&self) -> UncertainGeotime {
+        self.time
+    }
+
+    fn labels(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+// Lowercase words, ignoring punctuation.
+fn normalize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+fn similarity(a: &str, b: &str) -> f64 {
+    let len = a.chars().count().max(b.chars().count());
+    if len == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / len as f64
+}
+
+// How well a normalized query matches a label, from 0.0 to 1.0.
+fn score(query: &str, label: &str) -> f64 {
+    let label = normalize(label);
+    if label == query {
+        return 1.0;
+    }
+    if label.contains(query) {
+        return 0.9;
+    }
+
+    let words: Vec<&str> = label.split(' ').collect();
+    let query_words: Vec<&str> = query.split(' ').collect();
+    let by_word = query_words
+        .iter()
+        .map(|q| words.iter().map(|w| similarity(q, w)).fold(0.0, f64::max))
+        .sum::<f64>()
+        / query_words.len() as f64;
+
+    similarity(query, &label).max(0.85 * by_word)
+}
+
+/// A collection of events.  Start from [`EventCatalog::bundled`] and [`add`](Self::add) your
+/// own, or build one from scratch.
+///
+/// ```
+/// use geotime::{EventCatalog, Geotime};
+///
+/// let catalog = EventCatalog::bundled();
+/// let results = catalog.search("chixculub");
+/// assert_eq!(results[0].name(), "End-Cretaceous extinction");
+///
+/// let near = catalog.near(Geotime::from_ma(250.0), 1);
+/// assert_eq!(near[0].name(), "End-Permian extinction");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventCatalog {
+    events: Vec<Event>,
+}
+
+impl EventCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bundled events.
+    pub fn bundled() -> Self {
+        static CATALOG: OnceLock<EventCatalog> = OnceLock::new();
+        CATALOG
+            .get_or_init(|| {
+                let mut catalog = Self::new();
+                for (name, aliases, category, when) in EVENTS {
+                    let time = match when {
+                        Ma(age, sigma) => UncertainGeotime::from_ma(*age, *sigma),
+                        YearsBp(age, sigma) => UncertainGeotime::from_years_bp(*age, *sigma),
+                        Date(date) => Geotime::from(date).into(),
+                    };
+                    let event = aliases
+                        .iter()
+                        .fold(Event::new(name, *category, time), |e, a| e.with_alias(a));
+                    catalog.add(event);
+                }
+                catalog
+            })
+            .clone()
+    }
+
+    /// Adds an event, keeping the catalog in chronological order.
+    pub fn add(&mut self, event: Event) {
+        let i = self
+            .events
+            .partition_point(|e| e.time.time() <= event.time.time());
+        self.events.insert(i, event);
+    }
+
+    /// All of the events, oldest first.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// The event with the given name or alias, ignoring case and punctuation.
+    pub fn get(&self, name: &str) -> Option<&Event> {
+        let name = normalize(name);
+        self.events
+            .iter()
+            .find(|e| e.labels().any(|l| normalize(l) == name))
+    }
+
+    /// Events whose names or aliases resemble `query`, best match first.  Misspellings and
+    /// partial names are tolerated.
+    pub fn search(&self, query: &str) -> Vec<&Event> {
+        const THRESHOLD: f64 = 0.6;
+
+        let query = normalize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scored: Vec<(f64, &Event)> = self
+            .events
+            .iter()
+            .map(|e| {
+                let best = e.labels().map(|l| score(&query, l)).fold(0.0, f64::max);
+                (best, e)
+            })
+            .filter(|(s, _)| *s >= THRESHOLD)
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.into_iter().map(|(_, e)| e).collect()
+    }
+
+    /// The `count` events closest to `time`, closest first.
+    pub fn near(&self, time: Geotime, count: usize) -> Vec<&Event> {
+        let mut events: Vec<&Event> = self.events.iter().collect();
+        events.sort_by_key(|e| e.time.time().millis().abs_diff(time.millis()));
+        events.truncate(count);
+        events
+    }
+
+    /// The events within `interval`, oldest first.
+    pub fn within(&self, interval: &GeoInterval) -> Vec<&Event> {
+        self.events
+            .iter()
+            .filter(|e| interval.contains(e.time.time()))
+            .collect()
+    }
+
+    pub fn in_category(&self, category: Category) -> Vec<&Event> {
+        self.events
+            .iter()
+            .filter(|e| e.category == category)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled() {
+        let catalog = EventCatalog::bundled();
+        assert_eq!(catalog.events().len(), EVENTS.len());
+        assert!(catalog
+            .events()
+            .windows(2)
+            .all(|e| e[0].time.time() <= e[1].time.time()));
+        assert_eq!(catalog.events()[0].name(), "Big Bang");
+        assert_eq!(catalog.in_category(Extinction).len(), 5);
+
+        let future = catalog.in_category(Future);
+        assert!(future.iter().all(|e| e.time().time() > Geotime::now()));
+    }
+
+    #[test]
+    fn get() {
+        let catalog = EventCatalog::bundled();
+        assert_eq!(
+            catalog.get("great dying").unwrap().name(),
+            "End-Permian extinction"
+        );
+        assert_eq!(
+            catalog.get("K/T extinction").unwrap().name(),
+            "End-Cretaceous extinction"
+        );
+        let apollo = catalog.get("Apollo 11 Moon landing").unwrap();
+        assert_eq!(
+            apollo.time().time().display_string("%Y-%m-%d %H:%M"),
+            "1969-07-20 20:17"
+        );
+        assert_eq!(catalog.get("Big Crunch"), None);
+    }
+
+    #[test]
+    fn search() {
+        let catalog = EventCatalog::bundled();
+        let names = |query| -> Vec<&str> {
+            catalog
+                .search(query)
+                .into_iter()
+                .map(|e| e.name())
+                .collect()
+        };
+        assert_eq!(names("Big Bang")[0], "Big Bang");
+        assert_eq!(names("permian extinction")[0], "End-Permian extinction");
+        assert_eq!(names("great oxydation")[0], "Great Oxidation Event");
+        assert_eq!(names("petm"), vec!["Paleocene-Eocene Thermal Maximum"]);
+        assert_eq!(names("extinction").len(), 5);
+        assert!(names("xyzzy").is_empty());
+        assert!(names("").is_empty());
+    }
+
+    #[test]
+    fn queries_by_time() {
+        let mut catalog = EventCatalog::bundled();
+        catalog.add(Event::new(
+            "Deccan Traps",
+            Geologic,
+            UncertainGeotime::from_ma(66.3, 0.1),
+        ));
+
+        let near: Vec<&str> = catalog
+            .near(Geotime::from_ma(66.1), 2)
+            .into_iter()
+            .map(|e| e.name())
+            .collect();
+        assert_eq!(near, vec!["End-Cretaceous extinction", "Deccan Traps"]);
+
+        let oldest = catalog.near(Geotime::from(i128::MIN), 1);
+        assert_eq!(oldest[0].name(), "Big Bang");
+        assert_eq!(catalog.near(Geotime::from(i128::MAX), 1).len(), 1);
+        let unix = catalog.get("Unix epoch").unwrap();
+        assert_eq!(unix.time().time(), Geotime::from(0));
+
+        let mesozoic = GeoInterval::new(
+            UncertainGeotime::from_ma(251.902, 0.0),
+            UncertainGeotime::from_ma(66.0, 0.0),
+        )
+        .unwrap();
+        let names: Vec<&str> = catalog
+            .within(&mesozoic)
+            .into_iter()
+            .map(|e| e.name())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "End-Triassic extinction",
+                "Deccan Traps",
+                "End-Cretaceous extinction"
+            ]
+        );
+    }
+
+    #[test]
+    fn levenshtein_distance() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", "abc"), 0);
+    }
+}
@@ -0,0 +1,362 @@
+//! Geomagnetic polarity time scales: the sequence of normal and reversed polarity chrons recorded
+//! by the seafloor and by magnetostratigraphic sections.  The Cande and Kent (1995) calibration is
+//! bundled.
+//!
+//! Chrons follow the usual naming.  The normal part of chron C29 is C29n and the reversed part
+//! that precedes it is C29r.  Subchrons are numbered from the youngest, so C2An.1r is the reversed
+//! interval between C2An.1n and C2An.2n.  The classical names of the Pliocene and Pleistocene
+//! chrons and subchrons, such as Brunhes, Matuyama, Jaramillo and Olduvai, are also understood.
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::{GeoInterval, Geotime, UncertainGeotime};
+
+mod ck95;
+
+use ck95::NormalData;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Polarity {
+    Normal,
+    Reversed,
+}
+
+impl fmt::Display for Polarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Reversed => write!(f, "reversed"),
+        }
+    }
+}
+
+/// A chron or subchron: an interval of constant polarity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chron {
+    name: String,
+    polarity: Polarity,
+    interval: GeoInterval,
+}
+
+impl Chron {
+    // An interval between `base` and `top` in Ma; a top of zero extends to the present.
+    fn new(name: &str, polarity: Polarity, base: f64, top: f64) -> Self {
+        let end = if top == 0.0 {
+            Geotime::now().into()
+        } else {
+            UncertainGeotime::from_ma(top, 0.0)
+        };
+        Self {
+            name: name.to_string(),
+            polarity,
+            interval: GeoInterval {
+                start: UncertainGeotime::from_ma(base, 0.0),
+                end,
+            },
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn polarity(&self) -> Polarity {
+        self.polarity
+    }
+
+    pub fn interval(&self) -> GeoInterval {
+        self.interval
+    }
+}
+
+/// A change of polarity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reversal {
+    pub time: Geotime,
+    /// The polarity after the reversal.
+    pub polarity: Polarity,
+}
+
+/// A sequence of chrons, each at its finest subdivision.
+///
+/// ```
+/// use geotime::{Geotime, Polarity, PolarityTimeScale};
+///
+/// let gpts = PolarityTimeScale::ck95();
+/// let chron = gpts.chron_at(Geotime::from_ma(65.0)).unwrap();
+/// assert_eq!(chron.name(), "C29r");
+/// assert_eq!(chron.polarity(), Polarity::Reversed);
+///
+/// let olduvai = gpts.chron("Olduvai").unwrap();
+/// assert_eq!(olduvai.name(), "C2n");
+/// assert!((olduvai.interval().start().to_ma() - 1.95).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolarityTimeScale {
+    name: String,
+    chrons: Vec<Chron>,
+}
+
+// Splits a chron name such as C3Br.2n into the chron (C3B), whether the name falls in its
+// reversed part, and the subchron number.
+fn parts(name: &str) -> (&str, bool, Option<u32>) {
+    let i = name.find(['n', 'r']).unwrap_or(name.len());
+    let reversed = name[i..].starts_with('r');
+    let sub = name
+        .get(i + 1..)
+        .and_then(|s| s.strip_prefix('.'))
+        .and_then(|s| s.trim_end_matches(['n', 'r']).parse().ok());
+    (&name[..i], reversed, sub)
+}
+
+// The name of the reversed interval between normal intervals `i` and `i + 1`.
+fn reversed_name(normals: &[NormalData], i: usize) -> String {
+    let (chron, reversed, sub) = parts(normals[i].0);
+    let (next_chron, next_reversed, _) = parts(normals[i + 1].0);
+
+    if !reversed && !next_reversed && chron == next_chron {
+        return format!("{}n.{}r", chron, sub.unwrap_or(0));
+    }
+
+    let subchrons = normals
+        .iter()
+        .map(|n| parts(n.0))
+        .filter(|(c, r, _)| *c == chron && *r)
+        .count();
+    if subchrons == 0 {
+        format!("{}r", chron)
+    } else {
+        let k = if reversed { sub.unwrap_or(0) + 1 } else { 1 };
+        format!("{}r.{}r", chron, k)
+    }
+}
+
+impl PolarityTimeScale {
+    // Builds a scale from its normal intervals, youngest first, filling the gaps between them
+    // with reversed intervals.
+    fn from_normals(name: &str, normals: &[NormalData]) -> Self {
+        let mut chrons = Vec::new();
+        for (i, (chron, young, old)) in normals.iter().enumerate() {
+            chrons.push(Chron::new(chron, Polarity::Normal, *old, *young));
+            if let Some((_, next, _)) = normals.get(i + 1) {
+                let name = reversed_name(normals, i);
+                chrons.push(Chron::new(&name, Polarity::Reversed, *next, *old));
+            }
+        }
+        chrons.reverse();
+
+        Self {
+            name: name.to_string(),
+            chrons,
+        }
+    }
+
+    /// Cande and Kent (1995), from the present to the Cretaceous Normal Superchron (C34n).
+    pub fn ck95() -> &'static PolarityTimeScale {
+        static SCALE: OnceLock<PolarityTimeScale> = OnceLock::new();
+        SCALE.get_or_init(|| Self::from_normals("CK95", ck95::CK95))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// All of the chrons at their finest subdivision, oldest first.
+    pub fn chrons(&self) -> &[Chron] {
+        &self.chrons
+    }
+
+    /// The finest chron containing `time`, if any.
+    pub fn chron_at(&self, time: Geotime) -> Option<&Chron> {
+        self.chrons.iter().find(|c| c.interval.contains(time))
+    }
+
+    pub fn polarity_at(&self, time: Geotime) -> Option<Polarity> {
+        self.chron_at(time).map(|c| c.polarity)
+    }
+
+    /// Looks up a chron by name, ignoring case.  Chrons that are divided into subchrons, such as
+    /// C2An or C1r, and the classical names such as Matuyama, span all of their parts.
+    ///
+    /// ```
+    /// use geotime::PolarityTimeScale;
+    ///
+    /// let gpts = PolarityTimeScale::ck95();
+    /// let gauss = gpts.chron("Gauss").unwrap();
+    /// assert_eq!(gauss.name(), "C2An");
+    /// assert!((gauss.interval().start().to_ma() - 3.58).abs() < 1e-9);
+    /// assert!((gauss.interval().end().to_ma() - 2.581).abs() < 1e-9);
+    /// ```
+    pub fn chron(&self, name: &str) -> Option<Chron> {
+        if let Some(chron) = self
+            .chrons
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+        {
+            return Some(chron.clone());
+        }
+
+        if let Some((_, young, old, normal)) = ck95::ALIASES
+            .iter()
+            .find(|(alias, ..)| alias.eq_ignore_ascii_case(name))
+        {
+            let (young, old) = (self.chron(young)?, self.chron(old)?);
+            let polarity = if *normal {
+                Polarity::Normal
+            } else {
+                Polarity::Reversed
+            };
+            let name = if young.name == old.name {
+                young.name
+            } else {
+                name.to_string()
+            };
+            return Some(Chron {
+                name,
+                polarity,
+                interval: GeoInterval {
+                    start: old.interval.start,
+                    end: young.interval.end,
+                },
+            });
+        }
+
+        let prefix = format!("{}.", name.to_ascii_lowercase());
+        let parts: Vec<&Chron> = self
+            .chrons
+            .iter()
+            .filter(|c| c.name.to_ascii_lowercase().starts_with(&prefix))
+            .collect();
+        let (oldest, youngest) = (parts.first()?, parts.last()?);
+        let polarity = if name.ends_with(['r', 'R']) {
+            Polarity::Reversed
+        } else {
+            Polarity::Normal
+        };
+        let name = oldest
+            .name
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Some(Chron {
+            name,
+            polarity,
+            interval: GeoInterval {
+                start: oldest.interval.start,
+                end: youngest.interval.end,
+            },
+        })
+    }
+
+    /// The reversals within `interval`, oldest first.
+    ///
+    /// ```
+    /// use geotime::{GeoInterval, Polarity, PolarityTimeScale, UncertainGeotime};
+    ///
+    /// let pleistocene = GeoInterval::new(
+    ///     UncertainGeotime::from_ma(2.58, 0.0),
+    ///     UncertainGeotime::from_ma(0.0117, 0.0),
+    /// )
+    /// .unwrap();
+    /// let reversals = PolarityTimeScale::ck95().reversals_in(&pleistocene);
+    /// assert_eq!(reversals.len(), 7);
+    /// assert_eq!(reversals[6].polarity, Polarity::Normal);
+    /// assert!((reversals[6].time.to_ma() - 0.78).abs() < 1e-9);
+    /// ```
+    pub fn reversals_in(&self, interval: &GeoInterval) -> Vec<Reversal> {
+        self.chrons
+            .iter()
+            .skip(1)
+            .map(|c| Reversal {
+                time: c.interval.start.time(),
+                polarity: c.polarity,
+            })
+            .filter(|r| interval.contains(r.time))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpts() -> &'static PolarityTimeScale {
+        PolarityTimeScale::ck95()
+    }
+
+    #[test]
+    fn alternating_and_contiguous() {
+        let chrons = gpts().chrons();
+        for pair in chrons.windows(2) {
+            assert_ne!(pair[0].polarity, pair[1].polarity);
+            assert_eq!(pair[0].interval.end, pair[1].interval.start);
+        }
+        assert_eq!(chrons.last().unwrap().name(), "C1n");
+        assert_eq!(chrons.first().unwrap().name(), "C34n");
+    }
+
+    #[test]
+    fn reversed_names() {
+        let names: Vec<&str> = gpts().chrons().iter().rev().map(|c| c.name()).collect();
+        assert_eq!(
+            names[..12],
+            [
+                "C1n", "C1r.1r", "C1r.1n", "C1r.2r", "C2n", "C2r.1r", "C2r.1n", "C2r.2r",
+                "C2An.1n", "C2An.1r", "C2An.2n", "C2An.2r"
+            ]
+        );
+        for name in [
+            "C2Ar", "C3Br.3r", "C13r", "C29r", "C32n.1r", "C32r.2r", "C33r",
+        ] {
+            assert!(names.contains(&name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let chron = gpts().chron_at(Geotime::from_ma(33.0)).unwrap();
+        assert_eq!(chron.name(), "C12r");
+        assert_eq!(
+            gpts().polarity_at(Geotime::from_ma(100.0)),
+            Some(Polarity::Normal)
+        );
+        assert_eq!(gpts().chron_at(Geotime::from_ma(130.0)), None);
+        assert_eq!(gpts().chron_at(Geotime::PRESENT).unwrap().name(), "C1n");
+    }
+
+    #[test]
+    fn names() {
+        let c29r = gpts().chron("c29r").unwrap();
+        assert!((c29r.interval().start().to_ma() - 65.578).abs() < 1e-9);
+        assert!((c29r.interval().end().to_ma() - 64.745).abs() < 1e-9);
+
+        let c1r = gpts().chron("C1r").unwrap();
+        assert_eq!(c1r.name(), "C1r");
+        assert_eq!(c1r.polarity(), Polarity::Reversed);
+        assert!((c1r.interval().start().to_ma() - 1.77).abs() < 1e-9);
+        assert!((c1r.interval().end().to_ma() - 0.78).abs() < 1e-9);
+
+        let matuyama = gpts().chron("matuyama").unwrap();
+        assert_eq!(matuyama.name(), "matuyama");
+        assert!((matuyama.interval().start().to_ma() - 2.581).abs() < 1e-9);
+        assert!((matuyama.interval().end().to_ma() - 0.78).abs() < 1e-9);
+
+        let gilbert = gpts().chron("Gilbert").unwrap();
+        assert!((gilbert.interval().start().to_ma() - 5.894).abs() < 1e-9);
+
+        assert_eq!(gpts().chron("C14n"), None);
+        assert_eq!(gpts().chron("Laschamp"), None);
+    }
+
+    #[test]
+    fn reversals() {
+        let all = GeoInterval {
+            start: UncertainGeotime::from_ma(200.0, 0.0),
+            end: Geotime::now().into(),
+        };
+        let reversals = gpts().reversals_in(&all);
+        assert_eq!(reversals.len(), gpts().chrons().len() - 1);
+        assert!(reversals.windows(2).all(|r| r[0].time < r[1].time));
+    }
+}
@@ -0,0 +1,123 @@
+//! Cande and Kent (1995), Revised calibration of the geomagnetic polarity timescale for the Late
+//! Cretaceous and Cenozoic, J. Geophys. Res. 100, 6093-6095.  The normal polarity intervals, with
+//! their young and old ends in Ma.  The old end of C34n, beyond the range of the calibration, is
+//! taken as the approximate top of M0r.
+
+// Name, young end and old end (Ma).
+pub(crate) type NormalData = (&'static str, f64, f64);
+
+#[rustfmt::skip]
+pub(crate) const CK95: &[NormalData] = &[
+    ("C1n", 0.0, 0.780),
+    ("C1r.1n", 0.990, 1.070),
+    ("C2n", 1.770, 1.950),
+    ("C2r.1n", 2.140, 2.150),
+    ("C2An.1n", 2.581, 3.040),
+    ("C2An.2n", 3.110, 3.220),
+    ("C2An.3n", 3.330, 3.580),
+    ("C3n.1n", 4.180, 4.290),
+    ("C3n.2n", 4.480, 4.620),
+    ("C3n.3n", 4.800, 4.890),
+    ("C3n.4n", 4.980, 5.230),
+    ("C3An.1n", 5.894, 6.137),
+    ("C3An.2n", 6.269, 6.567),
+    ("C3Bn", 6.935, 7.091),
+    ("C3Br.1n", 7.135, 7.170),
+    ("C3Br.2n", 7.341, 7.375),
+    ("C4n.1n", 7.432, 7.562),
+    ("C4n.2n", 7.650, 8.072),
+    ("C4r.1n", 8.225, 8.257),
+    ("C4An", 8.699, 9.025),
+    ("C4Ar.1n", 9.230, 9.308),
+    ("C4Ar.2n", 9.580, 9.642),
+    ("C5n.1n", 9.740, 9.880),
+    ("C5n.2n", 9.920, 10.949),
+    ("C5r.1n", 11.052, 11.099),
+    ("C5r.2n", 11.476, 11.531),
+    ("C5An.1n", 11.935, 12.078),
+    ("C5An.2n", 12.184, 12.401),
+    ("C5Ar.1n", 12.678, 12.708),
+    ("C5Ar.2n", 12.775, 12.819),
+    ("C5AAn", 12.991, 13.139),
+    ("C5ABn", 13.302, 13.510),
+    ("C5ACn", 13.703, 14.076),
+    ("C5ADn", 14.178, 14.612),
+    ("C5Bn.1n", 14.800, 14.888),
+    ("C5Bn.2n", 15.034, 15.155),
+    ("C5Cn.1n", 16.014, 16.293),
+    ("C5Cn.2n", 16.327, 16.488),
+    ("C5Cn.3n", 16.556, 16.726),
+    ("C5Dn", 17.277, 17.615),
+    ("C5En", 18.281, 18.781),
+    ("C6n", 19.048, 20.131),
+    ("C6An.1n", 20.518, 20.725),
+    ("C6An.2n", 20.996, 21.320),
+    ("C6AAn", 21.768, 21.859),
+    ("C6AAr.1n", 22.151, 22.248),
+    ("C6AAr.2n", 22.459, 22.493),
+    ("C6Bn.1n", 22.588, 22.750),
+    ("C6Bn.2n", 22.804, 23.069),
+    ("C6Cn.1n", 23.353, 23.535),
+    ("C6Cn.2n", 23.677, 23.800),
+    ("C6Cn.3n", 23.999, 24.118),
+    ("C7n.1n", 24.730, 24.781),
+    ("C7n.2n", 24.835, 25.183),
+    ("C7An", 25.496, 25.648),
+    ("C8n.1n", 25.823, 25.951),
+    ("C8n.2n", 25.992, 26.554),
+    ("C9n", 27.027, 27.972),
+    ("C10n.1n", 28.283, 28.512),
+    ("C10n.2n", 28.578, 28.745),
+    ("C11n.1n", 29.401, 29.662),
+    ("C11n.2n", 29.765, 30.098),
+    ("C12n", 30.479, 30.939),
+    ("C13n", 33.058, 33.545),
+    ("C15n", 34.655, 34.940),
+    ("C16n.1n", 35.343, 35.526),
+    ("C16n.2n", 35.685, 36.341),
+    ("C17n.1n", 36.618, 37.473),
+    ("C17n.2n", 37.604, 37.848),
+    ("C17n.3n", 37.920, 38.113),
+    ("C18n.1n", 38.426, 39.552),
+    ("C18n.2n", 39.631, 40.130),
+    ("C19n", 41.257, 41.521),
+    ("C20n", 42.536, 43.789),
+    ("C21n", 46.264, 47.906),
+    ("C22n", 49.037, 49.714),
+    ("C23n.1n", 50.778, 50.946),
+    ("C23n.2n", 51.047, 51.743),
+    ("C24n.1n", 52.364, 52.663),
+    ("C24n.2n", 52.757, 52.801),
+    ("C24n.3n", 52.903, 53.347),
+    ("C25n", 55.904, 56.391),
+    ("C26n", 57.554, 57.911),
+    ("C27n", 60.920, 61.276),
+    ("C28n", 62.499, 63.634),
+    ("C29n", 63.976, 64.745),
+    ("C30n", 65.578, 67.610),
+    ("C31n", 67.735, 68.737),
+    ("C32n.1n", 71.071, 71.338),
+    ("C32n.2n", 71.587, 73.004),
+    ("C32r.1n", 73.291, 73.374),
+    ("C33n", 73.619, 79.075),
+    ("C34n", 83.000, 121.0),
+];
+
+// Named polarity chrons and subchrons: name, youngest and oldest chron, and whether normal.
+#[rustfmt::skip]
+pub(crate) const ALIASES: &[(&str, &str, &str, bool)] = &[
+    ("Brunhes", "C1n", "C1n", true),
+    ("Matuyama", "C1r", "C2r", false),
+    ("Jaramillo", "C1r.1n", "C1r.1n", true),
+    ("Olduvai", "C2n", "C2n", true),
+    ("Reunion", "C2r.1n", "C2r.1n", true),
+    ("Réunion", "C2r.1n", "C2r.1n", true),
+    ("Gauss", "C2An", "C2An", true),
+    ("Kaena", "C2An.1r", "C2An.1r", false),
+    ("Mammoth", "C2An.2r", "C2An.2r", false),
+    ("Gilbert", "C2Ar", "C3r", false),
+    ("Cochiti", "C3n.1n", "C3n.1n", true),
+    ("Nunivak", "C3n.2n", "C3n.2n", true),
+    ("Sidufjall", "C3n.3n", "C3n.3n", true),
+    ("Thvera", "C3n.4n", "C3n.4n", true),
+];
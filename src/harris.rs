@@ -0,0 +1,503 @@
+//! Relative chronology from stratigraphic superposition, in the form of a Harris matrix.
+//!
+//! Contexts (layers, fills, cuts and so on) are related by "is above" statements recorded in the
+//! field.  A context above another is younger, so the statements form a directed graph that must
+//! be acyclic.  The graph can be reduced to its essential relations, ordered into phases, and
+//! combined with absolute dates: a date on one context constrains everything above and below it.
+use std::fmt;
+
+use crate::{Error, GeoInterval, Result, UncertainGeotime};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Context {
+    name: String,
+    // Indexes of the contexts directly below this one.
+    below: Vec<usize>,
+    earliest: Option<UncertainGeotime>,
+    latest: Option<UncertainGeotime>,
+}
+
+/// The absolute bounds on the date of a context, after propagation through the matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextBounds {
+    pub context: String,
+    /// The earliest the context can date from (its terminus post quem).
+    pub earliest: Option<UncertainGeotime>,
+    /// The latest the context can date from (its terminus ante quem).
+    pub latest: Option<UncertainGeotime>,
+}
+
+impl ContextBounds {
+    /// The interval the context falls in, when it is bounded on both sides.
+    pub fn interval(&self) -> Option<GeoInterval> {
+        match (self.earliest, self.latest) {
+            (Some(start), Some(end)) => Some(GeoInterval { start, end }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ContextBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |t: Option<UncertainGeotime>| match t {
+            Some(t) => t.time().display_string("%Y"),
+            None => "?".to_string(),
+        };
+        write!(
+            f,
+            "{}: {} to {}",
+            self.context,
+            show(self.earliest),
+            show(self.latest)
+        )
+    }
+}
+
+/// A directed graph of stratigraphic contexts, with edges from each context to those directly
+/// below it.
+///
+/// ```
+/// use geotime::HarrisMatrix;
+///
+/// let mut matrix = HarrisMatrix::new();
+/// matrix.above("1", "2").unwrap();
+/// matrix.above("2", "3").unwrap();
+/// matrix.above("1", "3").unwrap();
+/// matrix.above("4", "3").unwrap();
+///
+/// assert_eq!(matrix.phases().unwrap(), vec![vec!["3"], vec!["2", "4"], vec!["1"]]);
+/// assert_eq!(matrix.transitive_reduction().unwrap().relations().len(), 3);
+///
+/// matrix.above("3", "1").unwrap();
+/// assert!(matrix.phases().is_err());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HarrisMatrix {
+    contexts: Vec<Context>,
+}
+
+impl HarrisMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.contexts.iter().position(|c| c.name == name)
+    }
+
+    fn find(&self, name: &str) -> Result<usize> {
+        self.index(name)
+            .ok_or_else(|| Error::NotFound(format!("no context named {}", name)))
+    }
+
+    /// Adds a context with no relations, returning its index.  Adding an existing context does
+    /// nothing.
+    pub fn add_context(&mut self, name: &str) -> usize {
+        match self.index(name) {
+            Some(i) => i,
+            None => {
+                self.contexts.push(Context {
+                    name: name.to_string(),
+                    below: vec![],
+                    earliest: None,
+                    latest: None,
+                });
+                self.contexts.len() - 1
+            }
+        }
+    }
+
+    /// Records that `upper` lies above, and so is younger than, `lower`, adding either context if
+    /// needed.  Cycles are not checked here, since field records are often entered out of order;
+    /// see [`HarrisMatrix::cycle`].
+    pub fn above(&mut self, upper: &str, lower: &str) -> Result<()> {
+        if upper == lower {
+            return Err(Error::InvalidInput(format!(
+                "context {} cannot lie above itself",
+                upper
+            )));
+        }
+        let (u, l) = (self.add_context(upper), self.add_context(lower));
+        if !self.contexts[u].below.contains(&l) {
+            self.contexts[u].below.push(l);
+        }
+        Ok(())
+    }
+
+    /// Constrains a context to date from within `interval`, narrowing any earlier constraint.
+    pub fn date(&mut self, name: &str, interval: GeoInterval) -> Result<()> {
+        self.terminus_post_quem(name, interval.start)?;
+        self.terminus_ante_quem(name, interval.end)
+    }
+
+    /// Constrains a context to date from no earlier than `time`, as when it contains a coin
+    /// minted then.
+    pub fn terminus_post_quem(&mut self, name: &str, time: UncertainGeotime) -> Result<()> {
+        let i = self.find(name)?;
+        let earliest = &mut self.contexts[i].earliest;
+        if earliest.is_none_or(|e| e.time() < time.time()) {
+            *earliest = Some(time);
+        }
+        Ok(())
+    }
+
+    /// Constrains a context to date from no later than `time`.
+    pub fn terminus_ante_quem(&mut self, name: &str, time: UncertainGeotime) -> Result<()> {
+        let i = self.find(name)?;
+        let latest = &mut self.contexts[i].latest;
+        if latest.is_none_or(|l| l.time() > time.time()) {
+            *latest = Some(time);
+        }
+        Ok(())
+    }
+
+    /// The names of the contexts, in the order they were added.
+    pub fn contexts(&self) -> Vec<&str> {
+        self.contexts.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    /// The recorded relations, as (upper, lower) pairs.
+    pub fn relations(&self) -> Vec<(&str, &str)> {
+        self.contexts
+            .iter()
+            .flat_map(|c| {
+                c.below
+                    .iter()
+                    .map(move |&l| (c.name.as_str(), self.contexts[l].name.as_str()))
+            })
+            .collect()
+    }
+
+    /// Whether `upper` lies above `lower`, directly or through intervening contexts.
+    pub fn is_above(&self, upper: &str, lower: &str) -> Result<bool> {
+        let (u, l) = (self.find(upper)?, self.find(lower)?);
+        Ok(self.reachable(u, l, None))
+    }
+
+    // Whether `to` can be reached from `from` by following edges downwards, optionally ignoring
+    // the direct edge `skip`.
+    fn reachable(&self, from: usize, to: usize, skip: Option<(usize, usize)>) -> bool {
+        let mut seen = vec![false; self.contexts.len()];
+        let mut stack = vec![from];
+        while let Some(i) = stack.pop() {
+            for &j in &self.contexts[i].below {
+                if skip == Some((i, j)) || seen[j] {
+                    continue;
+                }
+                if j == to {
+                    return true;
+                }
+                seen[j] = true;
+                stack.push(j);
+            }
+        }
+        false
+    }
+
+    /// A cycle of contexts each recorded above the next, with the first repeated at the end, or
+    /// `None` when the records are consistent.
+    pub fn cycle(&self) -> Option<Vec<&str>> {
+        // 0: unvisited, 1: on the current path, 2: finished.
+        let mut state = vec![0u8; self.contexts.len()];
+        for root in 0..self.contexts.len() {
+            if state[root] != 0 {
+                continue;
+            }
+            let mut path = vec![(root, 0)];
+            state[root] = 1;
+            while let Some((i, next)) = path.last_mut() {
+                let i = *i;
+                match self.contexts[i].below.get(*next) {
+                    Some(&j) => {
+                        *next += 1;
+                        match state[j] {
+                            0 => {
+                                state[j] = 1;
+                                path.push((j, 0));
+                            }
+                            1 => {
+                                let start = path.iter().position(|(k, _)| *k == j).unwrap_or(0);
+                                let mut cycle: Vec<&str> = path[start..]
+                                    .iter()
+                                    .map(|(k, _)| self.contexts[*k].name.as_str())
+                                    .collect();
+                                cycle.push(&self.contexts[j].name);
+                                return Some(cycle);
+                            }
+                            _ => {}
+                        }
+                    }
+                    None => {
+                        state[i] = 2;
+                        path.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn check(&self) -> Result<()> {
+        match self.cycle() {
+            Some(cycle) => Err(Error::InvalidInput(format!(
+                "stratigraphic cycle: {}",
+                cycle.join(" above ")
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The matrix with every relation implied by others removed, as drawn in a Harris diagram.
+    pub fn transitive_reduction(&self) -> Result<Self> {
+        self.check()?;
+        let mut reduced = self.clone();
+        for (i, context) in self.contexts.iter().enumerate() {
+            for &j in &context.below {
+                if reduced.reachable(i, j, Some((i, j))) {
+                    reduced.contexts[i].below.retain(|&k| k != j);
+                }
+            }
+        }
+        Ok(reduced)
+    }
+
+    // Phase of each context: the length of the longest chain of contexts below it.
+    fn depths(&self) -> Result<Vec<usize>> {
+        self.check()?;
+        let mut depths: Vec<Option<usize>> = vec![None; self.contexts.len()];
+        for root in 0..self.contexts.len() {
+            let mut stack = vec![root];
+            while let Some(&i) = stack.last() {
+                if depths[i].is_some() {
+                    stack.pop();
+                    continue;
+                }
+                let pending: Vec<usize> = self.contexts[i]
+                    .below
+                    .iter()
+                    .copied()
+                    .filter(|&j| depths[j].is_none())
+                    .collect();
+                if pending.is_empty() {
+                    let below = self.contexts[i].below.iter().filter_map(|&j| depths[j]);
+                    depths[i] = Some(below.map(|d| d + 1).max().unwrap_or(0));
+                    stack.pop();
+                } else {
+                    stack.extend(pending);
+                }
+            }
+        }
+        Ok(depths.into_iter().map(|d| d.unwrap_or(0)).collect())
+    }
+
+    /// The contexts grouped into phases, oldest first.  Each context is placed in the phase
+    /// above the youngest context it lies on; contexts within a phase are in the order they were
+    /// added.
+    pub fn phases(&self) -> Result<Vec<Vec<&str>>> {
+        let depths = self.depths()?;
+        let count = depths.iter().max().map_or(0, |d| d + 1);
+        let mut phases = vec![vec![]; count];
+        for (context, depth) in self.contexts.iter().zip(depths) {
+            phases[depth].push(context.name.as_str());
+        }
+        Ok(phases)
+    }
+
+    /// The contexts in an order consistent with superposition, oldest first.
+    pub fn sequence(&self) -> Result<Vec<&str>> {
+        Ok(self.phases()?.concat())
+    }
+
+    /// Propagates absolute dates through the matrix, returning the bounds on every context in
+    /// [`HarrisMatrix::sequence`] order.  A context can be no older than the oldest date
+    /// possible for anything beneath it, and no younger than the youngest date possible for
+    /// anything above it.  Dates that contradict the stratigraphy are an error.
+    ///
+    /// ```
+    /// use geotime::{HarrisMatrix, UncertainGeotime};
+    ///
+    /// let year = |y| UncertainGeotime::from_years_bp(1950.0 - y, 0.0);
+    /// let mut matrix = HarrisMatrix::new();
+    /// matrix.above("topsoil", "destruction layer").unwrap();
+    /// matrix.above("destruction layer", "floor").unwrap();
+    /// matrix.terminus_post_quem("floor", year(43.0)).unwrap();
+    /// matrix.terminus_ante_quem("destruction layer", year(61.0)).unwrap();
+    ///
+    /// let bounds = matrix.bounds().unwrap();
+    /// assert_eq!(bounds[0].context, "floor");
+    /// let floor = bounds[0].interval().unwrap();
+    /// assert_eq!(floor.start(), year(43.0));
+    /// assert_eq!(floor.end(), year(61.0));
+    /// assert_eq!(bounds[1].earliest, Some(year(43.0)));
+    /// assert_eq!(bounds[2].latest, None);
+    /// ```
+    pub fn bounds(&self) -> Result<Vec<ContextBounds>> {
+        let depths = self.depths()?;
+        let mut order: Vec<usize> = (0..self.contexts.len()).collect();
+        order.sort_by_key(|&i| depths[i]);
+
+        let mut earliest: Vec<Option<UncertainGeotime>> =
+            self.contexts.iter().map(|c| c.earliest).collect();
+        let mut latest: Vec<Option<UncertainGeotime>> =
+            self.contexts.iter().map(|c| c.latest).collect();
+
+        // Upwards: a context is no older than the earliest date of anything beneath it.
+        for &i in &order {
+            for &j in &self.contexts[i].below {
+                if let Some(e) = earliest[j] {
+                    if earliest[i].is_none_or(|f| f.time() < e.time()) {
+                        earliest[i] = Some(e);
+                    }
+                }
+            }
+        }
+        // Downwards: a context is no younger than the latest date of anything above it.
+        for &i in order.iter().rev() {
+            for &j in &self.contexts[i].below {
+                if let Some(l) = latest[i] {
+                    if latest[j].is_none_or(|m| m.time() > l.time()) {
+                        latest[j] = Some(l);
+                    }
+                }
+            }
+        }
+
+        order
+            .into_iter()
+            .map(|i| {
+                let name = &self.contexts[i].name;
+                if let (Some(e), Some(l)) = (earliest[i], latest[i]) {
+                    if l.time() < e.time() {
+                        return Err(Error::InvalidInput(format!(
+                            "dates on context {} contradict the stratigraphy",
+                            name
+                        )));
+                    }
+                }
+                Ok(ContextBounds {
+                    context: name.clone(),
+                    earliest: earliest[i],
+                    latest: latest[i],
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Geotime;
+
+    fn ka(age: f64) -> UncertainGeotime {
+        UncertainGeotime::from_years_bp(age * 1e3, 0.0)
+    }
+
+    fn matrix() -> HarrisMatrix {
+        let mut matrix = HarrisMatrix::new();
+        for (upper, lower) in [
+            ("1", "2"),
+            ("1", "3"),
+            ("2", "4"),
+            ("3", "4"),
+            ("2", "5"),
+            ("4", "6"),
+            ("5", "6"),
+            ("1", "6"),
+        ] {
+            matrix.above(upper, lower).unwrap();
+        }
+        matrix
+    }
+
+    #[test]
+    fn relations() {
+        let mut matrix = matrix();
+        assert!(matrix.above("7", "7").is_err());
+        matrix.above("1", "2").unwrap();
+        assert_eq!(matrix.relations().len(), 8);
+        assert_eq!(matrix.add_context("8"), 6);
+        assert_eq!(matrix.contexts().len(), 7);
+
+        assert!(matrix.is_above("1", "6").unwrap());
+        assert!(matrix.is_above("3", "6").unwrap());
+        assert!(!matrix.is_above("6", "3").unwrap());
+        assert!(!matrix.is_above("3", "5").unwrap());
+        assert!(matches!(matrix.is_above("1", "9"), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn cycles() {
+        let mut matrix = matrix();
+        assert_eq!(matrix.cycle(), None);
+        matrix.above("6", "3").unwrap();
+        let cycle = matrix.cycle().unwrap();
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!(cycle.len(), 4);
+        for pair in cycle.windows(2) {
+            assert!(matrix.relations().contains(&(pair[0], pair[1])));
+        }
+        assert!(matrix.transitive_reduction().is_err());
+        assert!(matrix.bounds().is_err());
+    }
+
+    #[test]
+    fn reduction_and_phases() {
+        let reduced = matrix().transitive_reduction().unwrap();
+        let relations = reduced.relations();
+        assert_eq!(relations.len(), 7);
+        assert!(!relations.contains(&("1", "6")));
+        assert_eq!(
+            reduced.phases().unwrap(),
+            matrix().phases().unwrap(),
+            "reduction preserves the order"
+        );
+
+        assert_eq!(
+            matrix().phases().unwrap(),
+            vec![vec!["6"], vec!["4", "5"], vec!["2", "3"], vec!["1"]]
+        );
+        assert_eq!(
+            matrix().sequence().unwrap(),
+            vec!["6", "4", "5", "2", "3", "1"]
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let mut matrix = matrix();
+        assert!(matrix.terminus_post_quem("9", ka(1.0)).is_err());
+        matrix.terminus_post_quem("6", ka(12.0)).unwrap();
+        matrix.terminus_post_quem("5", ka(8.0)).unwrap();
+        matrix
+            .date("3", GeoInterval::new(ka(10.0), ka(5.0)).unwrap())
+            .unwrap();
+        matrix.terminus_ante_quem("1", ka(2.0)).unwrap();
+
+        let bounds = matrix.bounds().unwrap();
+        let get = |name| bounds.iter().find(|b| b.context == name).unwrap();
+        assert_eq!(get("6").earliest, Some(ka(12.0)));
+        assert_eq!(get("6").latest, Some(ka(5.0)));
+        assert_eq!(
+            get("4").interval(),
+            GeoInterval::new(ka(12.0), ka(5.0)).ok()
+        );
+        assert_eq!(get("3").earliest, Some(ka(10.0)));
+        assert_eq!(get("2").earliest, Some(ka(8.0)));
+        assert_eq!(get("2").latest, Some(ka(2.0)));
+        assert_eq!(get("1").earliest, Some(ka(8.0)));
+        assert!(get("1").to_string().starts_with("1: "));
+
+        matrix.terminus_ante_quem("5", ka(7.0)).unwrap();
+        assert!(matrix.bounds().is_ok());
+        matrix.terminus_ante_quem("4", ka(13.0)).unwrap();
+        assert!(matches!(matrix.bounds(), Err(Error::InvalidInput(_))));
+
+        let mut matrix = HarrisMatrix::new();
+        matrix.add_context("a");
+        matrix
+            .terminus_ante_quem("a", Geotime::PRESENT.into())
+            .unwrap();
+        assert_eq!(matrix.bounds().unwrap()[0].interval(), None);
+    }
+}
@@ -0,0 +1,240 @@
+//! Piecewise cubic Hermite curves through a set of nodes.  Linear interpolation, natural cubic
+//! splines and monotone (Fritsch-Carlson) interpolation only differ in how the derivatives at the
+//! ends of each segment are chosen, so they share one representation.
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Curve {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    // Derivatives at the left and right ends of each segment.
+    slopes: Vec<(f64, f64)>,
+}
+
+fn secants(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    xs.windows(2)
+        .zip(ys.windows(2))
+        .map(|(x, y)| (y[1] - y[0]) / (x[1] - x[0]))
+        .collect()
+}
+
+impl Curve {
+    /// Nodes must be sorted by strictly increasing x, and there must be at least two of them.
+    pub(crate) fn linear(xs: &[f64], ys: &[f64]) -> Self {
+        let slopes = secants(xs, ys).into_iter().map(|d| (d, d)).collect();
+        Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            slopes,
+        }
+    }
+
+    pub(crate) fn natural_spline(xs: &[f64], ys: &[f64]) -> Self {
+        let n = xs.len();
+        let h: Vec<f64> = xs.windows(2).map(|x| x[1] - x[0]).collect();
+        let d = secants(xs, ys);
+
+        // Second derivatives at the nodes, with m[0] = m[n - 1] = 0, by the Thomas algorithm.
+        let mut m = vec![0.0; n];
+        if n > 2 {
+            let mut diag = vec![0.0; n];
+            let mut rhs = vec![0.0; n];
+            for i in 1..n - 1 {
+                diag[i] = 2.0 * (h[i - 1] + h[i]);
+                rhs[i] = 6.0 * (d[i] - d[i - 1]);
+            }
+            for i in 2..n - 1 {
+                let w = h[i - 1] / diag[i - 1];
+                diag[i] -= w * h[i - 1];
+                rhs[i] -= w * rhs[i - 1];
+            }
+            for i in (1..n - 1).rev() {
+                m[i] = (rhs[i] - h[i] * m[i + 1]) / diag[i];
+            }
+        }
+
+        let slopes = (0..n - 1)
+            .map(|i| {
+                (
+                    d[i] - h[i] * (2.0 * m[i] + m[i + 1]) / 6.0,
+                    d[i] + h[i] * (m[i] + 2.0 * m[i + 1]) / 6.0,
+                )
+            })
+            .collect();
+
+        Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            slopes,
+        }
+    }
+
+    pub(crate) fn monotone(xs: &[f64], ys: &[f64]) -> Self {
+        let n = xs.len();
+        let h: Vec<f64> = xs.windows(2).map(|x| x[1] - x[0]).collect();
+        let d = secants(xs, ys);
+
+        let mut m = vec![0.0; n];
+        m[0] = d[0];
+        m[n - 1] = d[n - 2];
+        for k in 1..n - 1 {
+            if d[k - 1] * d[k] > 0.0 {
+                let w1 = 2.0 * h[k] + h[k - 1];
+                let w2 = h[k] + 2.0 * h[k - 1];
+                m[k] = (w1 + w2) / (w1 / d[k - 1] + w2 / d[k]);
+            }
+        }
+
+        let slopes = (0..n - 1).map(|i| (m[i], m[i + 1])).collect();
+        Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            slopes,
+        }
+    }
+
+    pub(crate) fn min_x(&self) -> f64 {
+        self.xs[0]
+    }
+
+    pub(crate) fn max_x(&self) -> f64 {
+        self.xs[self.xs.len() - 1]
+    }
+
+    fn segment(&self, x: f64) -> Option<usize> {
+        if !(self.min_x()..=self.max_x()).contains(&x) {
+            return None;
+        }
+        let i = self.xs.partition_point(|v| *v <= x);
+        Some(i.saturating_sub(1).min(self.xs.len() - 2))
+    }
+
+    fn eval_segment(&self, i: usize, x: f64) -> (f64, f64) {
+        let (x0, x1) = (self.xs[i], self.xs[i + 1]);
+        let (y0, y1) = (self.ys[i], self.ys[i + 1]);
+        let (m0, m1) = self.slopes[i];
+        let h = x1 - x0;
+        let t = (x - x0) / h;
+        let (t2, t3) = (t * t, t * t * t);
+
+        let y = (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+            + (t3 - 2.0 * t2 + t) * h * m0
+            + (-2.0 * t3 + 3.0 * t2) * y1
+            + (t3 - t2) * h * m1;
+        let dy = (6.0 * t2 - 6.0 * t) / h * y0
+            + (3.0 * t2 - 4.0 * t + 1.0) * m0
+            + (-6.0 * t2 + 6.0 * t) / h * y1
+            + (3.0 * t2 - 2.0 * t) * m1;
+        (y, dy)
+    }
+
+    /// Value of the curve at `x`, or `None` outside of the nodes.
+    pub(crate) fn eval(&self, x: f64) -> Option<f64> {
+        self.segment(x).map(|i| self.eval_segment(i, x).0)
+    }
+
+    /// First derivative of the curve at `x`, or `None` outside of the nodes.
+    pub(crate) fn derivative(&self, x: f64) -> Option<f64> {
+        self.segment(x).map(|i| self.eval_segment(i, x).1)
+    }
+
+    /// The smallest x at which the curve takes the value `y`, if any.
+    pub(crate) fn solve(&self, y: f64) -> Option<f64> {
+        const SAMPLES: usize = 16;
+
+        for i in 0..self.xs.len() - 1 {
+            let (x0, x1) = (self.xs[i], self.xs[i + 1]);
+            let step = (x1 - x0) / SAMPLES as f64;
+            let mut a = x0;
+            let mut fa = self.eval_segment(i, a).0 - y;
+
+            for k in 1..=SAMPLES {
+                let b = if k == SAMPLES {
+                    x1
+                } else {
+                    x0 + step * k as f64
+                };
+                let fb = self.eval_segment(i, b).0 - y;
+
+                if fa == 0.0 {
+                    return Some(a);
+                }
+                if fa * fb <= 0.0 {
+                    let (mut lo, mut hi, mut flo) = (a, b, fa);
+                    for _ in 0..200 {
+                        let mid = (lo + hi) / 2.0;
+                        let fmid = self.eval_segment(i, mid).0 - y;
+                        if flo * fmid <= 0.0 {
+                            hi = mid;
+                        } else {
+                            lo = mid;
+                            flo = fmid;
+                        }
+                        if hi - lo <= f64::EPSILON * hi.abs().max(1.0) {
+                            break;
+                        }
+                    }
+                    return Some((lo + hi) / 2.0);
+                }
+
+                a = b;
+                fa = fb;
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XS: [f64; 5] = [0.0, 1.0, 2.0, 4.0, 5.0];
+    const YS: [f64; 5] = [0.0, 1.0, 1.5, 4.0, 10.0];
+
+    #[test]
+    fn passes_through_nodes() {
+        for curve in [
+            Curve::linear(&XS, &YS),
+            Curve::natural_spline(&XS, &YS),
+            Curve::monotone(&XS, &YS),
+        ] {
+            for (x, y) in XS.iter().zip(YS.iter()) {
+                assert!((curve.eval(*x).unwrap() - y).abs() < 1e-12);
+            }
+            assert_eq!(curve.eval(-0.1), None);
+            assert_eq!(curve.eval(5.1), None);
+        }
+    }
+
+    #[test]
+    fn linear() {
+        let curve = Curve::linear(&XS, &YS);
+        assert_eq!(curve.eval(3.0), Some(2.75));
+        assert_eq!(curve.derivative(4.5), Some(6.0));
+        assert!((curve.solve(7.0).unwrap() - 4.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn natural_spline_reproduces_cubic_free_line() {
+        let xs = [0.0, 1.0, 3.0, 4.0];
+        let ys = [1.0, 3.0, 7.0, 9.0];
+        let curve = Curve::natural_spline(&xs, &ys);
+        assert!((curve.eval(2.0).unwrap() - 5.0).abs() < 1e-12);
+        assert!((curve.derivative(3.5).unwrap() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn monotone_does_not_overshoot() {
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let ys = [0.0, 0.0, 10.0, 10.0];
+        let curve = Curve::monotone(&xs, &ys);
+        let mut last = f64::MIN;
+        for i in 0..=300 {
+            let y = curve.eval(i as f64 / 100.0).unwrap();
+            assert!((-1e-12..=10.0 + 1e-12).contains(&y));
+            assert!(y >= last - 1e-12);
+            last = y;
+        }
+    }
+}
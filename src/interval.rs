@@ -0,0 +1,449 @@
+//! Intervals of geologic time, such as those given as free-text unit names in collection
+//! databases.
+//!
+//! Names are matched against a [`TimeScale`], and the parser is tolerant of the ways in which the
+//! same interval tends to be written:
+//!
+//! - chronostratigraphic and geochronologic wording: "Upper Cretaceous" and "Late Cretaceous",
+//!   "Lower Jurassic Series" and "Early Jurassic";
+//! - the usual abbreviations of periods and eras: "K", "Pg", "Ng", "Tr", "Cm" and so on;
+//! - informal subdivisions of units that have no formal early, middle or late part, such as
+//!   "middle Miocene" or "late Maastrichtian";
+//! - the Lower, Middle and Upper Pleistocene, which are made up of stages;
+//! - ranges: "Campanian-Maastrichtian" or "Aptian to Albian".
+use crate::{Error, Geotime, Result, TimeScale, UncertainGeotime, Unit};
+
+// Abbreviations used on geologic maps and in databases, matched case-sensitively.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("Cz", "Cenozoic"),
+    ("Q", "Quaternary"),
+    ("Ng", "Neogene"),
+    ("N", "Neogene"),
+    ("Pg", "Paleogene"),
+    ("Mz", "Mesozoic"),
+    ("K", "Cretaceous"),
+    ("J", "Jurassic"),
+    ("Tr", "Triassic"),
+    ("Pz", "Paleozoic"),
+    ("P", "Permian"),
+    ("C", "Carboniferous"),
+    ("D", "Devonian"),
+    ("S", "Silurian"),
+    ("O", "Ordovician"),
+    ("Cm", "Cambrian"),
+    ("Є", "Cambrian"),
+];
+
+// Words naming the rank of a unit, which may follow its name.
+const RANK_WORDS: &[&str] = &[
+    "eon",
+    "eonothem",
+    "era",
+    "erathem",
+    "period",
+    "system",
+    "subperiod",
+    "subsystem",
+    "epoch",
+    "series",
+    "age",
+    "stage",
+];
+
+const RANGE_SEPARATORS: &[&str] = &[" to ", "–", "—", "-"];
+
+// Formal subseries that are made up of stages rather than named "Early ...", "Middle ..." and so
+// on, with the oldest and youngest stage of each.
+const SUBSERIES: &[(&str, Part, &str, &str)] = &[
+    ("Pleistocene", Part::Early, "Gelasian", "Calabrian"),
+    ("Pleistocene", Part::Middle, "Chibanian", "Chibanian"),
+    (
+        "Pleistocene",
+        Part::Late,
+        "Late Pleistocene",
+        "Late Pleistocene",
+    ),
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Part {
+    Early,
+    Middle,
+    Late,
+}
+
+impl Part {
+    fn parse(word: &str) -> Option<Self> {
+        match word.to_ascii_lowercase().as_str() {
+            "early" | "lower" => Some(Self::Early),
+            "middle" | "mid" => Some(Self::Middle),
+            "late" | "upper" => Some(Self::Late),
+            _ => None,
+        }
+    }
+
+    fn formal(self) -> &'static str {
+        match self {
+            Self::Early => "Early",
+            Self::Middle => "Middle",
+            Self::Late => "Late",
+        }
+    }
+}
+
+/// A span of time between two uncertain boundaries, from the older `start` to the younger `end`.
+///
+/// ```
+/// use geotime::{GeoInterval, Lexical64};
+///
+/// let k = GeoInterval::from_unit_name("Upper Cretaceous").unwrap();
+/// assert!((k.start().to_ma() - 100.5).abs() < 1e-9);
+/// assert!((k.end().to_ma() - 66.0).abs() < 1e-9);
+///
+/// let mut names = vec!["Maastrichtian", "middle Miocene", "Lower Jurassic", "Pg"];
+/// names.sort_by_key(|name| {
+///     let interval = GeoInterval::from_unit_name(name).unwrap();
+///     Lexical64::from(interval.start().time()).to_string()
+/// });
+/// assert_eq!(names, vec!["Lower Jurassic", "Maastrichtian", "Pg", "middle Miocene"]);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GeoInterval {
+    pub(crate) start: UncertainGeotime,
+    pub(crate) end: UncertainGeotime,
+}
+
+/// The interval spanned by a unit.  Units that extend to the present end now.
+impl From<&Unit> for GeoInterval {
+    fn from(unit: &Unit) -> Self {
+        Self {
+            start: unit.base(),
+            end: unit.top().unwrap_or_else(|| Geotime::now().into()),
+        }
+    }
+}
+
+impl GeoInterval {
+    pub fn new(start: UncertainGeotime, end: UncertainGeotime) -> Result<Self> {
+        if end.time() < start.time() {
+            return Err(Error::InvalidInput(
+                "interval ends before it starts".to_string(),
+            ));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Parses the name of a unit of the default time scale into the interval it spans.  See the
+    /// [module documentation](self) for the names that are understood.
+    pub fn from_unit_name(name: &str) -> Result<Self> {
+        Self::from_unit_name_in(name, &TimeScale::default_scale())
+    }
+
+    /// Parses the name of a unit of `scale` into the interval it spans.
+    ///
+    /// ```
+    /// use geotime::{GeoInterval, TimeScale};
+    ///
+    /// let k = GeoInterval::from_unit_name_in("Late K", TimeScale::gts2012()).unwrap();
+    /// assert!((k.start().to_ma() - 100.5).abs() < 1e-9);
+    /// ```
+    pub fn from_unit_name_in(name: &str, scale: &TimeScale) -> Result<Self> {
+        let single = Self::parse(name, scale);
+        if single.is_ok() {
+            return single;
+        }
+
+        // Names such as pre-Nectarian contain hyphens, so every separator is tried in turn.
+        for separator in RANGE_SEPARATORS {
+            for (i, _) in name.match_indices(separator) {
+                let (older, younger) = (&name[..i], &name[i + separator.len()..]);
+                if let (Ok(older), Ok(younger)) =
+                    (Self::parse(older, scale), Self::parse(younger, scale))
+                {
+                    return Ok(older.span(&younger));
+                }
+            }
+        }
+
+        single
+    }
+
+    fn parse(name: &str, scale: &TimeScale) -> Result<Self> {
+        let not_found = || Error::NotFound(format!("no unit named {} in {}", name, scale.name()));
+
+        let mut words: Vec<&str> = name.split_whitespace().collect();
+        if words.len() > 1
+            && RANK_WORDS.contains(&words[words.len() - 1].to_ascii_lowercase().as_str())
+        {
+            words.pop();
+        }
+
+        let part = match words.split_first() {
+            Some((first, rest)) if !rest.is_empty() => Part::parse(first),
+            _ => None,
+        };
+        let rest = if part.is_some() {
+            &words[1..]
+        } else {
+            &words[..]
+        };
+
+        let rest = rest.join(" ");
+        let rest = ABBREVIATIONS
+            .iter()
+            .find(|(abbreviation, _)| *abbreviation == rest)
+            .map_or(rest.as_str(), |(_, name)| name);
+
+        let part = match part {
+            Some(part) => part,
+            None => return scale.unit(rest).map(Self::from).ok_or_else(not_found),
+        };
+
+        if let Some(unit) = scale.unit(&format!("{} {}", part.formal(), rest)) {
+            return Ok(unit.into());
+        }
+        let unit = scale.unit(rest).ok_or_else(not_found)?;
+        Ok(Self::subdivide(scale, unit, part))
+    }
+
+    // The early, middle or late part of a unit: its formal subseries if it has them, otherwise
+    // a third of its children when there are enough of them, or else a third of its duration.
+    fn subdivide(scale: &TimeScale, unit: &Unit, part: Part) -> Self {
+        let subseries = SUBSERIES
+            .iter()
+            .find(|(name, p, _, _)| *name == unit.name() && *p == part)
+            .and_then(|(_, _, oldest, youngest)| {
+                Some((scale.unit(oldest)?, scale.unit(youngest)?))
+            });
+        if let Some((oldest, youngest)) = subseries {
+            return Self::from(oldest).span(&Self::from(youngest));
+        }
+
+        let children = scale.children(unit);
+        let n = children.len();
+
+        let range = match (n, part) {
+            (0..=1, _) | (2, Part::Middle) => None,
+            (2, Part::Early) => Some(&children[..1]),
+            (2, Part::Late) => Some(&children[1..]),
+            (_, _) => {
+                let third = (n + 1) / 3;
+                Some(match part {
+                    Part::Early => &children[..third],
+                    Part::Middle => &children[third..n - third],
+                    Part::Late => &children[n - third..],
+                })
+            }
+        };
+
+        if let Some(range) = range {
+            let first = Self::from(range[0]);
+            let last = Self::from(range[range.len() - 1]);
+            return first.span(&last);
+        }
+
+        let whole = Self::from(unit);
+        let k = match part {
+            Part::Early => 0,
+            Part::Middle => 1,
+            Part::Late => 2,
+        };
+        Self {
+            start: whole.boundary(k, 3),
+            end: whole.boundary(k + 1, 3),
+        }
+    }
+
+    // The boundary `k` `n`ths of the way through the interval, with its uncertainty interpolated
+    // between those of the start and end.
+    fn boundary(&self, k: usize, n: usize) -> UncertainGeotime {
+        match k {
+            0 => self.start,
+            k if k == n => self.end,
+            _ => {
+                let f = k as f64 / n as f64;
+                let (start, end) = (self.start.time().millis(), self.end.time().millis());
+                let time = start + ((end - start) as f64 * f).round() as i128;
+                let sigma = self.start.sigma_millis() as f64 * (1.0 - f)
+                    + self.end.sigma_millis() as f64 * f;
+                UncertainGeotime::new(Geotime::from(time), sigma.round() as i128)
+            }
+        }
+    }
+
+    // The smallest interval containing both intervals.
+    fn span(&self, other: &Self) -> Self {
+        let start = if other.start.time() < self.start.time() {
+            other.start
+        } else {
+            self.start
+        };
+        let end = if other.end.time() > self.end.time() {
+            other.end
+        } else {
+            self.end
+        };
+        Self { start, end }
+    }
+
+    /// The older boundary.
+    pub fn start(&self) -> UncertainGeotime {
+        self.start
+    }
+
+    /// The younger boundary.
+    pub fn end(&self) -> UncertainGeotime {
+        self.end
+    }
+
+    /// The length of the interval in millions of (Julian) years.
+    pub fn duration_ma(&self) -> f64 {
+        self.start.time().to_ma() - self.end.time().to_ma()
+    }
+
+    /// Divides the interval into `n` consecutive intervals of equal length, oldest first.
+    ///
+    /// ```
+    /// use geotime::GeoInterval;
+    ///
+    /// let cretaceous = GeoInterval::from_unit_name("Cretaceous").unwrap();
+    /// let bins = cretaceous.split(79);
+    /// assert_eq!(bins.len(), 79);
+    /// assert!((bins[0].duration_ma() - 1.0).abs() < 1e-6);
+    /// assert_eq!(bins[78].end(), cretaceous.end());
+    /// ```
+    pub fn split(&self, n: usize) -> Vec<GeoInterval> {
+        (0..n)
+            .map(|k| Self {
+                start: self.boundary(k, n),
+                end: self.boundary(k + 1, n),
+            })
+            .collect()
+    }
+
+    pub fn midpoint(&self) -> Geotime {
+        let (start, end) = (self.start.time().millis(), self.end.time().millis());
+        Geotime::from(start + (end - start) / 2)
+    }
+
+    /// Whether `time` falls within the interval, including its start but not its end.
+    pub fn contains(&self, time: Geotime) -> bool {
+        self.start.time() <= time && time < self.end.time()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LunarChronology;
+
+    fn interval(name: &str) -> GeoInterval {
+        GeoInterval::from_unit_name_in(name, TimeScale::ics()).unwrap()
+    }
+
+    fn assert_ma(interval: GeoInterval, start: f64, end: f64) {
+        assert!(
+            (interval.start().to_ma() - start).abs() < 1e-6,
+            "{:?}",
+            interval
+        );
+        assert!(
+            (interval.end().to_ma() - end).abs() < 1e-6,
+            "{:?}",
+            interval
+        );
+    }
+
+    #[test]
+    fn formal_names() {
+        assert_ma(interval("Late Cretaceous"), 100.5, 66.0);
+        assert_ma(interval("late cretaceous"), 100.5, 66.0);
+        assert_ma(interval("Upper Cretaceous"), 100.5, 66.0);
+        assert_ma(interval("Upper Cretaceous Series"), 100.5, 66.0);
+        assert_ma(interval("Lower Jurassic"), 201.4, 174.7);
+        assert_ma(interval("Maastrichtian Stage"), 72.1, 66.0);
+        assert!((interval("Maastrichtian").start().sigma_ma() - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn abbreviations() {
+        assert_eq!(interval("K"), interval("Cretaceous"));
+        assert_eq!(interval("Pg"), interval("Paleogene"));
+        assert_eq!(interval("Ng"), interval("Neogene"));
+        assert_eq!(interval("Late K"), interval("Late Cretaceous"));
+        assert_eq!(interval("Lower Tr"), interval("Early Triassic"));
+        assert!(GeoInterval::from_unit_name_in("k", TimeScale::ics()).is_err());
+    }
+
+    #[test]
+    fn informal_subdivisions() {
+        assert_ma(interval("early Miocene"), 23.03, 15.98);
+        assert_ma(interval("middle Miocene"), 15.98, 11.63);
+        assert_ma(interval("late Miocene"), 11.63, 5.333);
+        assert_eq!(interval("early Pliocene"), interval("Zanclean"));
+        assert_eq!(interval("late Pliocene"), interval("Piacenzian"));
+        let third = (5.333 - 2.58) / 3.0;
+        assert_ma(interval("middle Pliocene"), 5.333 - third, 2.58 + third);
+
+        let late = interval("upper Maastrichtian");
+        assert_ma(late, 72.1 - 6.1 * 2.0 / 3.0, 66.0);
+        assert!((late.start().sigma_ma() - 0.2 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pleistocene_subseries() {
+        assert_ma(interval("Early Pleistocene"), 2.58, 0.774);
+        assert_ma(interval("Lower Pleistocene"), 2.58, 0.774);
+        assert_ma(interval("Middle Pleistocene"), 0.774, 0.129);
+        assert_ma(interval("Late Pleistocene"), 0.129, 0.0117);
+        assert_eq!(interval("upper Pleistocene"), interval("Late Pleistocene"));
+
+        let gts2012 = TimeScale::gts2012();
+        let early = GeoInterval::from_unit_name_in("Early Pleistocene", gts2012).unwrap();
+        assert_ma(early, 2.588, 0.781);
+    }
+
+    #[test]
+    fn ranges() {
+        assert_ma(interval("Campanian-Maastrichtian"), 83.6, 66.0);
+        assert_ma(interval("Maastrichtian–Campanian"), 83.6, 66.0);
+        assert_ma(interval("Late Cretaceous to Paleocene"), 100.5, 56.0);
+
+        let moon = TimeScale::moon(LunarChronology::Wilhelms1987);
+        let range = GeoInterval::from_unit_name_in("pre-Nectarian-Imbrian", moon).unwrap();
+        assert_ma(range, 4533.0, 3200.0);
+        let range = GeoInterval::from_unit_name_in("Nectarian-Imbrian", moon).unwrap();
+        assert_ma(range, 3920.0, 3200.0);
+        assert!(GeoInterval::from_unit_name_in("pre-Nectarian", moon).is_ok());
+    }
+
+    #[test]
+    fn open_ended() {
+        let holocene = interval("Holocene");
+        assert!(holocene.contains(Geotime::PRESENT));
+        assert!(holocene.end().time() > Geotime::PRESENT);
+    }
+
+    #[test]
+    fn errors() {
+        for name in ["", "Late", "Jurassic Park", "Campanian-Jurassic Park"] {
+            assert!(matches!(
+                GeoInterval::from_unit_name_in(name, TimeScale::ics()),
+                Err(Error::NotFound(_))
+            ));
+        }
+        let (a, b) = (
+            UncertainGeotime::from_ma(1.0, 0.0),
+            UncertainGeotime::from_ma(2.0, 0.0),
+        );
+        assert!(GeoInterval::new(a, b).is_err());
+        assert!(GeoInterval::new(b, a).is_ok());
+    }
+
+    #[test]
+    fn midpoint() {
+        let midpoint = interval("Maastrichtian").midpoint();
+        assert!((midpoint.to_ma() - 69.05).abs() < 1e-6);
+        assert!(interval("Maastrichtian").contains(midpoint));
+    }
+}
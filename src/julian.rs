@@ -0,0 +1,341 @@
+//! Julian Dates and the epochs of astronomy.
+//!
+//! A Julian Date counts days and fractions of a day from noon on 1 January 4713 BC in the Julian
+//! calendar.  Modern dates need seven digits before the point, which leaves a double precise only
+//! to tens of microseconds, and dates in deep time lose the fraction altogether; so a
+//! [`JulianDate`] keeps the day number as an integer and the fraction of the day separately.
+//!
+//! No time scale is implied: a Julian Date converts to a [`Geotime`] in the same time scale.
+//! Epochs such as J2000.0 are defined in Terrestrial Time, and are taken here at face value.
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Geotime, Result, MILLISECONDS_IN_DAY};
+
+// Milliseconds from JD 0.0 to the Unix epoch, JD 2440587.5.
+const UNIX_EPOCH_MILLIS: i128 = 2440587 * MILLISECONDS_IN_DAY + MILLISECONDS_IN_DAY / 2;
+
+// Julian Dates of the origins of the Modified and Truncated Julian Dates.
+const MJD_ORIGIN: (i128, f64) = (2400000, 0.5);
+const TJD_ORIGIN: (i128, f64) = (2440000, 0.5);
+
+const JULIAN_YEAR: f64 = 365.25;
+const TROPICAL_YEAR_1900: f64 = 365.242198781;
+// JD of B1900.0, the start of the Besselian year 1900.
+const B1900: f64 = 2415020.31352;
+
+/// A Julian Date, as a whole number of days and a fraction in [0, 1).
+///
+/// ```
+/// use geotime::{Geotime, JulianDate};
+///
+/// let jd = JulianDate::try_from(Geotime::from(0)).unwrap();
+/// assert_eq!(jd.to_string(), "2440587.5");
+/// assert_eq!(jd.mjd(), JulianDate::new(40587, 0.0));
+/// assert_eq!(Geotime::try_from(jd).unwrap(), Geotime::from(0));
+///
+/// let j2000: JulianDate = "2451545.0".parse().unwrap();
+/// assert_eq!(j2000, JulianDate::J2000);
+/// assert_eq!(j2000.julian_epoch(), 2000.0);
+/// assert_eq!(Geotime::try_from(j2000).unwrap().display_string("%Y-%m-%dT%H:%M"), "2000-01-01T12:00");
+///
+/// // 4.5 billion years ago, still to the millisecond.
+/// let time = Geotime::from_ma(4500.0);
+/// let jd = JulianDate::try_from(time).unwrap();
+/// assert_eq!(Geotime::try_from(jd).unwrap(), time);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct JulianDate {
+    day: i128,
+    fraction: f64,
+}
+
+impl JulianDate {
+    /// J2000.0, noon on 1 January 2000.
+    pub const J2000: Self = Self {
+        day: 2451545,
+        fraction: 0.0,
+    };
+
+    /// B1950.0, the start of the Besselian year 1950, JD 2433282.4235.
+    pub const B1950: Self = Self {
+        day: 2433282,
+        fraction: 0.4235,
+    };
+
+    /// The date `day + fraction`, normalized so that the fraction lies in [0, 1).
+    pub fn new(day: i128, fraction: f64) -> Self {
+        let whole = fraction.floor();
+        Self {
+            day: day + whole as i128,
+            fraction: fraction - whole,
+        }
+    }
+
+    /// The Julian Date nearest to `jd`, which is precise to about 40 μs for modern dates.
+    pub fn from_f64(jd: f64) -> Self {
+        Self::new(0, jd)
+    }
+
+    pub fn day(&self) -> i128 {
+        self.day
+    }
+
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// The date as a single number, losing precision far from JD 0.
+    pub fn to_f64(&self) -> f64 {
+        self.day as f64 + self.fraction
+    }
+
+    /// Days from `other` to `self`.
+    pub fn days_since(&self, other: &JulianDate) -> f64 {
+        (self.day - other.day) as f64 + (self.fraction - other.fraction)
+    }
+
+    fn minus(&self, (day, fraction): (i128, f64)) -> Self {
+        Self::new(self.day - day, self.fraction - fraction)
+    }
+
+    fn plus(&self, (day, fraction): (i128, f64)) -> Self {
+        Self::new(self.day + day, self.fraction + fraction)
+    }
+
+    /// The Modified Julian Date, JD − 2400000.5, which begins at midnight.
+    pub fn mjd(&self) -> JulianDate {
+        self.minus(MJD_ORIGIN)
+    }
+
+    pub fn from_mjd(mjd: JulianDate) -> Self {
+        mjd.plus(MJD_ORIGIN)
+    }
+
+    /// The Truncated Julian Date, JD − 2440000.5, as defined by NASA.
+    pub fn tjd(&self) -> JulianDate {
+        self.minus(TJD_ORIGIN)
+    }
+
+    pub fn from_tjd(tjd: JulianDate) -> Self {
+        tjd.plus(TJD_ORIGIN)
+    }
+
+    /// The Julian epoch: 2000.0 plus the Julian years of 365.25 days since J2000.0.
+    pub fn julian_epoch(&self) -> f64 {
+        2000.0 + self.days_since(&Self::J2000) / JULIAN_YEAR
+    }
+
+    pub fn from_julian_epoch(epoch: f64) -> Self {
+        Self::J2000.plus((0, (epoch - 2000.0) * JULIAN_YEAR))
+    }
+
+    /// The Besselian epoch: 1900.0 plus the tropical years of 1900 since B1900.0.
+    pub fn besselian_epoch(&self) -> f64 {
+        1900.0 + self.days_since(&Self::from_f64(B1900)) / TROPICAL_YEAR_1900
+    }
+
+    pub fn from_besselian_epoch(epoch: f64) -> Self {
+        Self::from_f64(B1900).plus((0, (epoch - 1900.0) * TROPICAL_YEAR_1900))
+    }
+
+    /// Parses an epoch written as "J2000.0", "J2024.5" or "B1950.0".
+    pub fn parse_epoch(input: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("unable to parse epoch: {}", input));
+        let input = input.trim();
+        let (kind, year) = input.split_at(input.find(|c: char| !c.is_alphabetic()).unwrap_or(0));
+        let year: f64 = year.parse().map_err(|_| invalid())?;
+        match kind {
+            "J" => Ok(Self::from_julian_epoch(year)),
+            "B" => Ok(Self::from_besselian_epoch(year)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Fails for the last few thousand years before the end of the range of a [`Geotime`].
+impl TryFrom<Geotime> for JulianDate {
+    type Error = Error;
+
+    fn try_from(time: Geotime) -> Result<Self> {
+        let millis = time
+            .millis()
+            .checked_add(UNIX_EPOCH_MILLIS)
+            .ok_or_else(|| out_of_range(&time))?;
+        Ok(Self {
+            day: millis.div_euclid(MILLISECONDS_IN_DAY),
+            fraction: millis.rem_euclid(MILLISECONDS_IN_DAY) as f64 / MILLISECONDS_IN_DAY as f64,
+        })
+    }
+}
+
+/// The nearest millisecond.
+impl TryFrom<JulianDate> for Geotime {
+    type Error = Error;
+
+    fn try_from(jd: JulianDate) -> Result<Self> {
+        let millis = (jd.fraction * MILLISECONDS_IN_DAY as f64).round() as i128;
+        jd.day
+            .checked_mul(MILLISECONDS_IN_DAY)
+            .and_then(|day| day.checked_add(millis))
+            .and_then(|millis| millis.checked_sub(UNIX_EPOCH_MILLIS))
+            .map(Geotime::from)
+            .ok_or_else(|| out_of_range(&jd))
+    }
+}
+
+fn out_of_range(value: &impl fmt::Debug) -> Error {
+    Error::OutOfRange(format!("{:?} is out of range for a Julian Date", value))
+}
+
+/// Formats the day number and the fraction to nine places, without trailing zeros.
+impl fmt::Display for JulianDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Negative dates are written as a magnitude, so that JD -0.25 is not "-1.75".
+        let (sign, day, fraction) = match (self.day < 0, self.fraction > 0.0) {
+            (true, true) => ("-", -self.day - 1, 1.0 - self.fraction),
+            (true, false) => ("-", -self.day, 0.0),
+            (false, _) => ("", self.day, self.fraction),
+        };
+        let fraction = format!("{:.9}", fraction);
+        let (day, fraction) = match fraction.strip_prefix("1.") {
+            // The fraction rounds up to a whole day.
+            Some(_) => (day + 1, "0".to_string()),
+            None => (day, fraction[2..].trim_end_matches('0').to_string()),
+        };
+        let fraction = if fraction.is_empty() { "0" } else { &fraction };
+        write!(f, "{}{}.{}", sign, day, fraction)
+    }
+}
+
+/// Parses a decimal Julian Date, such as "2451545.0" or "-1000000.25", without loss of range.
+impl FromStr for JulianDate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("unable to parse Julian Date: {}", s));
+        let s = s.trim();
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (whole, decimals) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() || !decimals.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let whole: i128 = whole.parse().map_err(|_| invalid())?;
+        let fraction: f64 = match decimals {
+            "" => 0.0,
+            decimals => format!("0.{}", decimals).parse().map_err(|_| invalid())?,
+        };
+        Ok(match negative {
+            true => Self::new(-whole, -fraction),
+            false => Self::new(whole, fraction),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReformCalendar;
+
+    #[test]
+    fn day_counts() {
+        // JD 0 is noon on 1 January 4713 BC in the Julian calendar.
+        let noon = ReformCalendar::JULIAN.parse("1 January 4713 BC").unwrap();
+        let jd =
+            JulianDate::try_from(Geotime::from(noon.millis() + MILLISECONDS_IN_DAY / 2)).unwrap();
+        assert_eq!(jd, JulianDate::new(0, 0.0));
+
+        let time = ReformCalendar::GREGORIAN.parse("17 November 1858").unwrap();
+        assert_eq!(
+            JulianDate::try_from(time).unwrap().mjd(),
+            JulianDate::new(0, 0.0)
+        );
+        let time = ReformCalendar::GREGORIAN.parse("24 May 1968").unwrap();
+        assert_eq!(
+            JulianDate::try_from(time).unwrap().tjd(),
+            JulianDate::new(0, 0.0)
+        );
+
+        let jd = JulianDate::new(2460000, 0.75);
+        assert_eq!(JulianDate::from_mjd(jd.mjd()), jd);
+        assert_eq!(JulianDate::from_tjd(jd.tjd()), jd);
+        assert_eq!(jd.mjd().to_string(), "60000.25");
+    }
+
+    #[test]
+    fn epochs() {
+        assert_eq!(JulianDate::J2000.julian_epoch(), 2000.0);
+        assert!((JulianDate::B1950.besselian_epoch() - 1950.0).abs() < 1e-6);
+        assert!((JulianDate::B1950.julian_epoch() - 1949.99979).abs() < 1e-5);
+        assert_eq!(
+            JulianDate::parse_epoch("J2000.0").unwrap(),
+            JulianDate::J2000
+        );
+        let b1950 = JulianDate::parse_epoch("B1950.0").unwrap();
+        // B1950.0 is conventionally rounded to JD 2433282.4235.
+        assert!(b1950.days_since(&JulianDate::B1950).abs() < 1e-4);
+        let j2050 = JulianDate::parse_epoch("J2050").unwrap();
+        assert_eq!(j2050, JulianDate::new(2469807, 0.5));
+        assert!(JulianDate::parse_epoch("X2000").is_err());
+        assert!(JulianDate::parse_epoch("2000").is_err());
+    }
+
+    #[test]
+    fn strings() {
+        for (s, day, fraction, shown) in [
+            ("2451545.0", 2451545, 0.0, "2451545.0"),
+            ("2451545", 2451545, 0.0, "2451545.0"),
+            ("2451545.25", 2451545, 0.25, "2451545.25"),
+            ("-0.25", -1, 0.75, "-0.25"),
+            ("-2", -2, 0.0, "-2.0"),
+            (
+                "-1000000000000000.5",
+                -1000000000000001,
+                0.5,
+                "-1000000000000000.5",
+            ),
+        ] {
+            let jd: JulianDate = s.parse().unwrap();
+            assert_eq!(jd, JulianDate::new(day, fraction), "{}", s);
+            assert_eq!(jd.to_string(), shown);
+        }
+        assert_eq!(JulianDate::new(0, 0.9999999999).to_string(), "1.0");
+        assert!("".parse::<JulianDate>().is_err());
+        assert!("1.2.3".parse::<JulianDate>().is_err());
+        assert!(".5".parse::<JulianDate>().is_err());
+    }
+
+    #[test]
+    fn range() {
+        let last = i128::MAX - UNIX_EPOCH_MILLIS;
+        for millis in [
+            i128::MIN,
+            i128::MIN / 2,
+            -1,
+            0,
+            1,
+            86_399_999,
+            i128::MAX / 2,
+            last,
+        ] {
+            let time = Geotime::from(millis);
+            let jd = JulianDate::try_from(time).unwrap();
+            assert_eq!(Geotime::try_from(jd).unwrap(), time);
+        }
+        for millis in [last + 1, i128::MAX] {
+            assert!(matches!(
+                JulianDate::try_from(Geotime::from(millis)),
+                Err(Error::OutOfRange(_))
+            ));
+        }
+        for day in [i128::MIN, i128::MAX] {
+            assert!(matches!(
+                Geotime::try_from(JulianDate::new(day, 0.0)),
+                Err(Error::OutOfRange(_))
+            ));
+        }
+    }
+}
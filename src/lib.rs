@@ -22,8 +22,9 @@
 //! A 128-bit timestamp allows us to represent times of events in astrophysical, geological,
 //! historical and present-day timescales to millisecond precision.  We go down to milliseconds as a
 //! convenience for handling timestamps for recent events.  In order to maintain a clean mapping to
-//! Unix timestamps, Geotime inherits whatever is going on with leap seconds.  Timestamps can
-//! represent any date within +- 5e27 years of 1970.
+//! Unix timestamps, Geotime inherits whatever is going on with leap seconds; [`TimeStandard`]
+//! converts to and from TAI, TT and GPS time with a table of them.  Timestamps can represent any
+//! date within +- 5e27 years of 1970.
 //!
 //! Several serialization formats are provided that preserve lexical ordering of timestamps.
 //!
@@ -35,15 +36,70 @@
 extern crate quick_error;
 extern crate human_format;
 
-use chrono::{DateTime, NaiveDateTime, Utc};
-use human_format::Formatter;
+use chrono::{DateTime, Utc};
+use human_format::{Formatter, Scales};
 
 const SECONDS_IN_DAY: i128 = 86400;
+const MILLISECONDS_IN_DAY: i128 = SECONDS_IN_DAY * 1000;
 const MILLISECONDS_IN_YEAR_APPROX: i128 = SECONDS_IN_DAY * 356 * 1000;
+const MILLISECONDS_IN_JULIAN_YEAR: i128 = MILLISECONDS_IN_DAY * 36525 / 100;
 const MAX_YEARS: f64 = 1000000000000.0;
 
+// 1950-01-01T00:00:00Z, the "present" of the before-present (BP) convention.
+const PRESENT_MILLIS: i128 = -631152000000;
+
+mod age_depth;
+mod astro;
+mod biostrat;
+mod calendar;
+mod cosmology;
+mod day_length;
+mod delta_t;
+mod epoch;
+mod events;
+mod gpts;
+mod harris;
+mod interp;
+mod interval;
+mod julian;
+mod mars;
+mod mis;
+mod orbital;
+mod radiometric;
 mod ser;
+mod standard;
+mod timescale;
+mod uncertain;
+pub use age_depth::{AgeDepthModel, Interpolation, TiePoint};
+pub use biostrat::{BinCounts, Bioevent, Biozone, RangeChart, TaxonRange};
+pub use calendar::{
+    Calendar, CalendarDate, CalendarRound, ChineseCalendar, ChineseDate, Era, Haab, HebrewCalendar,
+    IslamicCalendar, LongCount, MayaCorrelation, PersianCalendar, Realm, ReformCalendar, RegnalEra,
+    Sexagenary, Tzolkin,
+};
+pub use cosmology::Cosmology;
+pub use day_length::{DayLength, PaleoDate};
+pub use delta_t::DeltaT;
+pub use epoch::{ExcelDateSystem, GpsWeek, NtpTimestamp, Tai64};
+pub use events::{Category, Event, EventCatalog};
+pub use gpts::{Chron, Polarity, PolarityTimeScale, Reversal};
+pub use harris::{ContextBounds, HarrisMatrix};
+pub use interval::GeoInterval;
+pub use julian::JulianDate;
+pub use mars::{MarsMission, MarsTime};
+pub use mis::{IceCoreEvent, MarineIsotopeStage};
+pub use orbital::{OrbitalElements, OrbitalSolution, SOLAR_CONSTANT};
+pub use radiometric::{
+    ar_ar_age, concordia_age, k_ar_age, rb_sr_isochron_age, sm_nd_isochron_age, u_pb_206_238_age,
+    u_pb_207_235_age, DecayConstants, Isochron, IsochronPoint,
+};
 pub use ser::{Lexical16, Lexical32, Lexical64, LexicalGeohash};
+pub use standard::{LeapSeconds, TimeStandard};
+pub use timescale::{
+    AgeUnit, Color, Correlation, Gssp, Issue, LunarChronology, MartianChronology,
+    MercurianChronology, Rank, TimeScale, Unit,
+};
+pub use uncertain::{Measurement, UncertainGeotime};
 
 quick_error! {
     #[derive(Clone, Debug)]
@@ -54,6 +110,12 @@ quick_error! {
             from(err: data_encoding::DecodePartial) -> (format!("{:?}", err))
         }
 
+        InvalidInput(err: String) { }
+
+        NotFound(err: String) { }
+
+        OutOfRange(err: String) { }
+
         TryFromInt(err: std::num::TryFromIntError) {
             from()
         }
@@ -91,10 +153,70 @@ impl From<&DateTime<Utc>> for Geotime {
 }
 
 impl Geotime {
+    /// The "present" of the before-present convention used for deep-time ages, 1950-01-01.
+    pub const PRESENT: Geotime = Geotime(PRESENT_MILLIS);
+
     pub fn now() -> Self {
         Self::from(&Utc::now())
     }
 
+    /// Timestamp for an age given in millions of (Julian) years before present.
+    ///
+    /// ```
+    /// use geotime::Geotime;
+    ///
+    /// let dt = Geotime::from_ma(66.0);
+    /// assert!((dt.to_ma() - 66.0).abs() < 1e-9);
+    /// assert!(Geotime::try_from_ma(1e30).is_err());
+    /// ```
+    pub fn from_ma(ma: f64) -> Self {
+        Self::from_years_bp(ma * 1e6)
+    }
+
+    /// Timestamp for an age given in millions of years before present, or an error if the age is
+    /// not finite or lies beyond the range of a timestamp.
+    pub fn try_from_ma(ma: f64) -> Result<Self> {
+        Self::try_from_years_bp(ma * 1e6)
+    }
+
+    /// Age of the timestamp in millions of years before present.
+    pub fn to_ma(&self) -> f64 {
+        self.to_years_bp() / 1e6
+    }
+
+    /// Timestamp for an age given in (Julian) years before present.  Ages beyond the range of a
+    /// timestamp saturate at its ends; see [`Geotime::try_from_years_bp`].
+    pub fn from_years_bp(years: f64) -> Self {
+        let millis = (years * MILLISECONDS_IN_JULIAN_YEAR as f64).round() as i128;
+        Self(PRESENT_MILLIS.saturating_sub(millis))
+    }
+
+    /// Timestamp for an age given in (Julian) years before present, or an error if the age is not
+    /// finite or lies beyond the range of a timestamp.
+    pub fn try_from_years_bp(years: f64) -> Result<Self> {
+        let out_of_range =
+            || Error::OutOfRange(format!("{} years before present is out of range", years));
+        let millis = (years * MILLISECONDS_IN_JULIAN_YEAR as f64).round();
+        // i128::MAX as f64 rounds up to 2^127, the first value out of range.
+        if millis.is_nan() || millis.abs() >= i128::MAX as f64 {
+            return Err(out_of_range());
+        }
+        PRESENT_MILLIS
+            .checked_sub(millis as i128)
+            .map(Self)
+            .ok_or_else(out_of_range)
+    }
+
+    /// Age of the timestamp in years before present.
+    pub fn to_years_bp(&self) -> f64 {
+        PRESENT_MILLIS.saturating_sub(self.0) as f64 / MILLISECONDS_IN_JULIAN_YEAR as f64
+    }
+
+    /// Milliseconds since the Unix epoch, without loss of range.
+    pub fn millis(&self) -> i128 {
+        self.0
+    }
+
     /// A simple date formatting method is provided to render the timestamps in a human-friendly
     /// string.  If a timestamp is too large for `chrono` to render using the template provided,
     /// we fall back to the [human_format crate](https://docs.rs/human_format/latest/human_format/).
@@ -130,7 +252,9 @@ impl Geotime {
                 let years = years.abs();
 
                 let (desc, unit) = if years < MAX_YEARS {
-                    (Formatter::new().format(years), "years")
+                    let mut scales = Scales::new();
+                    scales.with_suffixes(vec!["", "k", "M", "B", "T"]);
+                    (Formatter::new().with_scales(scales).format(years), "years")
                 } else {
                     (format!("{:?}", self), "ms")
                 };
@@ -154,11 +278,8 @@ impl TryFrom<Geotime> for DateTime<Utc> {
 
     fn try_from(value: Geotime) -> std::result::Result<Self, Self::Error> {
         let n = i64::try_from(value.0)?;
-        let (secs, nsecs) = (n / 1000, ((n % 1000) * 1000) as u32);
-        let naive = NaiveDateTime::from_timestamp_opt(secs, nsecs)
-            .ok_or_else(|| Error::Chrono("unable to convert to chrono::DateTime".to_string()))?;
-        let dt: DateTime<Utc> = DateTime::from_utc(naive, Utc);
-        Ok(dt)
+        DateTime::from_timestamp_millis(n)
+            .ok_or_else(|| Error::Chrono("unable to convert to chrono::DateTime".to_string()))
     }
 }
 
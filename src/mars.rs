@@ -0,0 +1,376 @@
+//! Time on Mars, after the Mars24 algorithm of Allison and McEwen (2000).
+//!
+//! A sol, the mean solar day of Mars, lasts 1.0274912517 Earth days.  The Mars Sol Date counts
+//! sols from 29 December 1873, and Coordinated Mars Time (MTC) is the mean solar time at the
+//! prime meridian, in 24 Mars hours to the sol.  The season is given by the areocentric
+//! longitude of the Sun, Ls, which is 0° at the northern spring equinox; Mars Years begin there
+//! and are counted from the equinox of 11 April 1955 (Clancy et al., 2000).
+//!
+//! Longitudes are planetocentric and positive to the east.  Conversions go through Terrestrial
+//! Time with the current table of leap seconds.
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Geotime, Result, TimeStandard, MILLISECONDS_IN_DAY};
+
+// Earth days in a sol, and the Julian Date in TT of MSD 0.
+const SOL: f64 = 1.0274912517;
+const MSD_EPOCH: f64 = 2405522.0028779;
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+const J2000: f64 = 2451545.0;
+
+// Amplitude (degrees), period (Julian years) and phase (degrees) of the planetary perturbations.
+#[rustfmt::skip]
+const PERTURBATIONS: [(f64, f64, f64); 7] = [
+    (0.0071, 2.2353, 49.409), (0.0057, 2.7543, 168.173), (0.0039, 1.1177, 191.837),
+    (0.0037, 15.7866, 21.736), (0.0021, 2.1354, 15.704), (0.0020, 2.4694, 95.528),
+    (0.0018, 32.8493, 49.095),
+];
+
+// Julian Date of 1955-04-11T00:00Z, near the start of Mars Year 1, and the mean length of a Mars
+// Year in days.
+const MARS_YEAR_1: f64 = 2435208.5;
+const MARS_YEAR: f64 = 686.9726;
+
+/// An instant on the Mars Sol Date scale.
+///
+/// ```
+/// use geotime::{Geotime, MarsTime};
+///
+/// let time = Geotime::from(947_116_800_000_i64); // 2000-01-06T00:00:00Z
+/// let mars = MarsTime::from(time);
+/// assert!((mars.msd() - 44795.9998).abs() < 1e-4);
+/// assert_eq!(mars.to_string(), "MSD 44795.99976 MTC 23:59:39");
+/// assert!((mars.solar_longitude() - 277.1876).abs() < 1e-3);
+/// assert_eq!(mars.mars_year(), 24);
+///
+/// let parsed: MarsTime = "MSD 44795 23:59:39".parse().unwrap();
+/// assert!((Geotime::try_from(parsed).unwrap().millis() - time.millis()).abs() < 1000);
+/// assert!(Geotime::try_from(MarsTime::from_msd(-1e40)).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct MarsTime {
+    msd: f64,
+}
+
+impl MarsTime {
+    pub fn from_msd(msd: f64) -> Self {
+        Self { msd }
+    }
+
+    /// The Mars Sol Date.
+    pub fn msd(&self) -> f64 {
+        self.msd
+    }
+
+    /// Coordinated Mars Time, in Mars hours since midnight at the prime meridian.
+    pub fn mtc(&self) -> f64 {
+        self.msd.rem_euclid(1.0) * 24.0
+    }
+
+    /// The local mean solar time at `longitude` degrees east, in Mars hours.
+    pub fn local_mean_solar_time(&self, longitude: f64) -> f64 {
+        (self.mtc() + longitude / 15.0).rem_euclid(24.0)
+    }
+
+    /// The local true solar time at `longitude` degrees east, in Mars hours, which follows the
+    /// Sun and so differs from the mean time by the equation of time.
+    pub fn local_true_solar_time(&self, longitude: f64) -> f64 {
+        let (ls, nu_m) = self.orbit();
+        let ls = ls.to_radians();
+        let eot =
+            2.861 * (2.0 * ls).sin() - 0.071 * (4.0 * ls).sin() + 0.002 * (6.0 * ls).sin() - nu_m;
+        (self.local_mean_solar_time(longitude) + eot / 15.0).rem_euclid(24.0)
+    }
+
+    /// The areocentric longitude of the Sun, Ls, in degrees.
+    pub fn solar_longitude(&self) -> f64 {
+        self.orbit().0
+    }
+
+    /// The Mars Year, counted from the northern spring equinox of 11 April 1955.
+    pub fn mars_year(&self) -> i64 {
+        let years = (self.jd_tt() - MARS_YEAR_1) / MARS_YEAR;
+        let (year, fraction) = (years.floor(), years.rem_euclid(1.0));
+        // The mean year locates the equinox to within days; Ls settles which side of it we are.
+        let ls = self.solar_longitude();
+        let year = match (fraction, ls) {
+            (f, ls) if f < 0.25 && ls > 270.0 => year - 1.0,
+            (f, ls) if f > 0.75 && ls < 90.0 => year + 1.0,
+            _ => year,
+        };
+        year as i64 + 1
+    }
+
+    fn jd_tt(&self) -> f64 {
+        self.msd * SOL + MSD_EPOCH
+    }
+
+    // Ls and the equation of center, ν − M, in degrees.
+    fn orbit(&self) -> (f64, f64) {
+        let days = self.jd_tt() - J2000;
+        let m = (19.3871 + 0.52402073 * days).to_radians();
+        let fictitious_mean_sun = 270.3871 + 0.524038496 * days;
+        let perturbations: f64 = PERTURBATIONS
+            .iter()
+            .map(|(a, tau, phi)| a * (0.985626 * days / tau + phi).to_radians().cos())
+            .sum();
+        let nu_m = (10.691 + 3.0e-7 * days) * m.sin()
+            + 0.623 * (2.0 * m).sin()
+            + 0.050 * (3.0 * m).sin()
+            + 0.005 * (4.0 * m).sin()
+            + 0.0005 * (5.0 * m).sin()
+            + perturbations;
+        ((fictitious_mean_sun + nu_m).rem_euclid(360.0), nu_m)
+    }
+}
+
+impl From<Geotime> for MarsTime {
+    fn from(utc: Geotime) -> Self {
+        let tt = TimeStandard::TT.at_utc(utc);
+        let jd_tt = UNIX_EPOCH_JD + tt.millis() as f64 / MILLISECONDS_IN_DAY as f64;
+        Self::from_msd((jd_tt - MSD_EPOCH) / SOL)
+    }
+}
+
+/// The nearest millisecond, in UTC, or an error beyond the range of a `Geotime`.
+impl TryFrom<MarsTime> for Geotime {
+    type Error = Error;
+
+    fn try_from(mars: MarsTime) -> Result<Self> {
+        let millis = ((mars.jd_tt() - UNIX_EPOCH_JD) * MILLISECONDS_IN_DAY as f64).round();
+        // i128::MAX rounds up to 2^127 as an f64, which is itself out of range.
+        if !millis.is_finite() || millis.abs() >= i128::MAX as f64 {
+            return Err(Error::OutOfRange(format!(
+                "{:?} is beyond the range of a Geotime",
+                mars
+            )));
+        }
+        TimeStandard::TT.try_to_utc(Geotime::from(millis as i128))
+    }
+}
+
+/// Formats the time as "MSD 44795.99976 MTC 23:59:39".
+impl fmt::Display for MarsTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The sol date is truncated, like the clock, so that both name the same sol.
+        let msd = (self.msd * 1e5).floor() / 1e5;
+        write!(f, "MSD {:.5} MTC {}", msd, clock(self.mtc()))
+    }
+}
+
+/// Parses a Mars Sol Date, "MSD 44795.99976" or "44795.99976", or a sol and a time of day in
+/// MTC, "MSD 44795 23:59:39".  The rest of the formatted string is ignored.
+impl FromStr for MarsTime {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("unable to parse Mars Sol Date: {}", s));
+        let mut tokens = s
+            .split_whitespace()
+            .filter(|t| !t.eq_ignore_ascii_case("MSD"));
+        let msd: f64 = tokens
+            .next()
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?;
+        let hours = match tokens.find(|t| !t.eq_ignore_ascii_case("MTC")) {
+            Some(time) if msd.fract() == 0.0 => parse_clock(time).ok_or_else(invalid)?,
+            Some(_) => return Err(invalid()),
+            None => 0.0,
+        };
+        Ok(Self::from_msd(msd + hours / 24.0))
+    }
+}
+
+/// The sols of a mission on the surface, counted in local mean solar time at the landing site
+/// from the sol of landing, which is sol 0.
+///
+/// ```
+/// use geotime::{Geotime, MarsMission};
+///
+/// // A landing at 137.4° E at 2012-08-06T05:17:57Z.
+/// let landing = Geotime::from(1_344_230_277_000_i64);
+/// let mission = MarsMission::new(landing, 137.4);
+/// assert_eq!(mission.sol(landing), 0);
+/// assert_eq!(mission.format(landing), "Sol 0 15:03:04 LMST");
+///
+/// let time = mission.parse("Sol 1000 12:00").unwrap();
+/// assert_eq!(mission.sol(time), 1000);
+/// assert_eq!(mission.format(time), "Sol 1000 12:00:00 LMST");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MarsMission {
+    landing: Geotime,
+    longitude: f64,
+}
+
+impl MarsMission {
+    /// A mission that landed at `landing` at `longitude` degrees east.
+    pub fn new(landing: Geotime, longitude: f64) -> Self {
+        Self { landing, longitude }
+    }
+
+    pub fn landing(&self) -> Geotime {
+        self.landing
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    // Sols since MSD 0 in local mean solar time.
+    fn local_sols(&self, time: Geotime) -> f64 {
+        MarsTime::from(time).msd() + self.longitude / 360.0
+    }
+
+    /// The mission sol at `time`; sols before landing are negative.
+    pub fn sol(&self, time: Geotime) -> i64 {
+        (self.local_sols(time).floor() - self.local_sols(self.landing).floor()) as i64
+    }
+
+    /// Formats the time as the mission sol and the local mean solar time, such as
+    /// "Sol 1000 12:00:00 LMST".
+    pub fn format(&self, time: Geotime) -> String {
+        let hours = self.local_sols(time).rem_euclid(1.0) * 24.0;
+        format!("Sol {} {} LMST", self.sol(time), clock(hours))
+    }
+
+    /// Parses a mission sol with an optional local mean solar time, such as "Sol 1000",
+    /// "sol 1000 14:30" or "Sol 1000 14:30:05 LMST".
+    pub fn parse(&self, input: &str) -> Result<Geotime> {
+        let invalid = || Error::InvalidInput(format!("unable to parse mission sol: {}", input));
+        let tokens: Vec<&str> = input.split_whitespace().collect();
+        let (sol, time) = match tokens[..] {
+            [word, sol, ref rest @ ..] if word.eq_ignore_ascii_case("sol") => match rest {
+                [] => (sol, None),
+                [time] => (sol, Some(*time)),
+                [time, zone] if zone.eq_ignore_ascii_case("LMST") => (sol, Some(*time)),
+                _ => return Err(invalid()),
+            },
+            _ => return Err(invalid()),
+        };
+        let sol: i64 = sol.parse().map_err(|_| invalid())?;
+        let hours = match time {
+            Some(time) => parse_clock(time).ok_or_else(invalid)?,
+            None => 0.0,
+        };
+        let local = self.local_sols(self.landing).floor() + sol as f64 + hours / 24.0;
+        let time = Geotime::try_from(MarsTime::from_msd(local - self.longitude / 360.0))?;
+        // Rounding to the millisecond may fall just before midnight.
+        match self.sol(time) < sol {
+            true => Ok(Geotime::from(time.millis() + 1)),
+            false => Ok(time),
+        }
+    }
+}
+
+// Formats hours in [0, 24) as hh:mm:ss, truncated to the second so that the clock does not run
+// into the next sol.  The hours are first rounded to the millisecond, the precision of a Geotime.
+fn clock(hours: f64) -> String {
+    let seconds = ((hours * 3_600_000.0).round() as i64 / 1000).min(86399);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+// Parses hh:mm or hh:mm:ss into hours.
+fn parse_clock(text: &str) -> Option<f64> {
+    let parts: Vec<f64> = text
+        .split(':')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    let (h, m, s) = match parts[..] {
+        [h, m] => (h, m, 0.0),
+        [h, m, s] => (h, m, s),
+        _ => return None,
+    };
+    let valid = (0.0..24.0).contains(&h) && (0.0..60.0).contains(&m) && (0.0..60.0).contains(&s);
+    valid.then_some(h + m / 60.0 + s / 3600.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2000-01-06T00:00:00Z, the worked example of Allison and McEwen.
+    const EXAMPLE: i64 = 947_116_800_000;
+
+    #[test]
+    fn mars24_example() {
+        let mars = MarsTime::from(Geotime::from(EXAMPLE));
+        assert!((mars.jd_tt() - 2451549.50074287).abs() < 1e-6);
+        assert!((mars.solar_longitude() - 277.18758).abs() < 1e-4);
+        assert!((mars.mtc() - 23.99425).abs() < 1e-4);
+        assert!((mars.local_mean_solar_time(0.0) - mars.mtc()).abs() < 1e-9);
+        // The true solar time at the prime meridian is about 21 minutes behind the mean.
+        assert!((mars.local_true_solar_time(0.0) - 23.64840).abs() < 1e-4);
+        assert!((mars.local_mean_solar_time(-90.0) - 17.99425).abs() < 1e-4);
+    }
+
+    #[test]
+    fn round_trip() {
+        for millis in [EXAMPLE, 0, -2_000_000_000_000, 1_700_000_000_123] {
+            let time = Geotime::from(millis);
+            assert_eq!(Geotime::try_from(MarsTime::from(time)).unwrap(), time);
+        }
+        for msd in [-1e40, 1e40, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                Geotime::try_from(MarsTime::from_msd(msd)),
+                Err(Error::OutOfRange(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn mars_years() {
+        // The northern spring equinox that began Mars Year 1.
+        let before = MarsTime::from(Geotime::from(-464_572_800_000_i64)); // 1955-04-13
+        let ls = before.solar_longitude();
+        assert!(ls < 2.0, "{}", ls);
+        assert_eq!(before.mars_year(), 1);
+        let earlier = MarsTime::from_msd(before.msd() - 3.0);
+        assert!(earlier.solar_longitude() > 358.0);
+        assert_eq!(earlier.mars_year(), 0);
+        // Mars Year 36 began on 7 February 2021.
+        let my36 = MarsTime::from(Geotime::from(1_612_742_400_000_i64)); // 2021-02-08
+        assert_eq!(my36.mars_year(), 36);
+        assert!(my36.solar_longitude() < 1.0);
+    }
+
+    #[test]
+    fn strings() {
+        let mars: MarsTime = "MSD 44795.5".parse().unwrap();
+        assert_eq!(mars.to_string(), "MSD 44795.50000 MTC 12:00:00");
+        assert_eq!("44795.5".parse::<MarsTime>().unwrap(), mars);
+        assert_eq!("MSD 44795 MTC 12:00".parse::<MarsTime>().unwrap(), mars);
+        assert!("MSD 44795.5 12:00".parse::<MarsTime>().is_err());
+        assert!("MSD 44795 25:00".parse::<MarsTime>().is_err());
+        assert!("MTC".parse::<MarsTime>().is_err());
+
+        // Just before the end of a sol.
+        let late = MarsTime::from_msd(44796.0 - 1e-7);
+        assert_eq!(late.to_string(), "MSD 44795.99999 MTC 23:59:59");
+    }
+
+    #[test]
+    fn missions() {
+        let landing = Geotime::from(EXAMPLE);
+        let mission = MarsMission::new(landing, -90.0);
+        // 17:59:39 LMST on the sol of landing.
+        assert_eq!(mission.format(landing), "Sol 0 17:59:39 LMST");
+        let midnight = mission.parse("sol 1").unwrap();
+        assert_eq!(mission.format(midnight), "Sol 1 00:00:00 LMST");
+        assert_eq!(mission.sol(Geotime::from(midnight.millis() - 1)), 0);
+        let before = mission.parse("Sol -2 06:30").unwrap();
+        assert_eq!(mission.format(before), "Sol -2 06:30:00 LMST");
+        let late = Geotime::from(midnight.millis() - 10);
+        assert_eq!(mission.format(late), "Sol 0 23:59:59 LMST");
+        assert!(mission.parse("Sol one").is_err());
+        assert!(mission.parse("1000").is_err());
+        assert!(mission.parse("Sol 1 12:00 UTC").is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Quaternary chronology: Marine Isotope Stages and the ice-core conventions used alongside them.
+//!
+//! Stage boundaries follow the LR04 benthic stack (Lisiecki and Raymo, 2005), back to MIS 104
+//! at the base of the Quaternary.
+//! Odd-numbered stages, and the lettered substages a, c and e, are warm; the others are cold.
+//!
+//! Greenland ice-core ages are usually counted in years before 2000 (b2k) on the GICC05
+//! timescale, with a maximum counting error (MCE) that accumulates with depth.  Following
+//! Rasmussen et al. (2006), the MCE is treated as a two-sigma uncertainty.
+use std::sync::OnceLock;
+
+use crate::{GeoInterval, Geotime, UncertainGeotime};
+
+// Stage and the age of its base (ka), youngest first.
+#[rustfmt::skip]
+const LR04: &[(&str, f64)] = &[
+    ("1", 14.0), ("2", 29.0), ("3", 57.0), ("4", 71.0), ("5", 130.0), ("6", 191.0),
+    ("7", 243.0), ("8", 300.0), ("9", 337.0), ("10", 374.0), ("11", 424.0), ("12", 478.0),
+    ("13", 533.0), ("14", 563.0), ("15", 621.0), ("16", 676.0), ("17", 712.0), ("18", 761.0),
+    ("19", 790.0), ("20", 814.0), ("21", 866.0), ("22", 900.0), ("23", 917.0), ("24", 936.0),
+    ("25", 959.0), ("26", 970.0), ("27", 982.0), ("28", 1014.0), ("29", 1031.0), ("30", 1062.0),
+    ("31", 1081.0), ("32", 1104.0), ("33", 1114.0), ("34", 1141.0), ("35", 1190.0), ("36", 1215.0),
+    ("37", 1244.0), ("38", 1264.0), ("39", 1286.0), ("40", 1304.0), ("41", 1320.0), ("42", 1343.0),
+    ("43", 1362.0), ("44", 1383.0), ("45", 1405.0), ("46", 1424.0), ("47", 1452.0), ("48", 1469.0),
+    ("49", 1492.0), ("50", 1510.0), ("51", 1530.0), ("52", 1547.0), ("53", 1570.0), ("54", 1585.0),
+    ("55", 1608.0), ("56", 1628.0), ("57", 1642.0), ("58", 1670.0), ("59", 1697.0), ("60", 1715.0),
+    ("61", 1743.0), ("62", 1758.0), ("63", 1782.0), ("64", 1802.0), ("65", 1820.0), ("66", 1832.0),
+    ("67", 1849.0), ("68", 1862.0), ("69", 1875.0), ("70", 1890.0), ("71", 1905.0), ("72", 1920.0),
+    ("73", 1950.0), ("74", 1970.0), ("75", 1992.0), ("76", 2015.0), ("77", 2043.0), ("78", 2067.0),
+    ("79", 2087.0), ("80", 2116.0), ("81", 2139.0), ("82", 2171.0), ("83", 2186.0), ("84", 2213.0),
+    ("85", 2241.0), ("86", 2264.0), ("87", 2285.0), ("88", 2309.0), ("89", 2322.0), ("90", 2337.0),
+    ("91", 2353.0), ("92", 2372.0), ("93", 2385.0), ("94", 2402.0), ("95", 2422.0), ("96", 2446.0),
+    ("97", 2476.0), ("98", 2496.0), ("99", 2515.0), ("100", 2535.0), ("101", 2555.0),
+    ("102", 2575.0), ("103", 2595.0), ("104", 2614.0),
+];
+
+// Substages of MIS 5 and the ages of their bases (ka), youngest first.
+#[rustfmt::skip]
+const LR04_MIS_5: &[(&str, f64)] = &[
+    ("5a", 82.0), ("5b", 87.0), ("5c", 96.0), ("5d", 109.0), ("5e", 130.0),
+];
+
+// Event, age (b2k) and maximum counting error (years), youngest first.  Stadials and
+// interstadials are dated by their onsets (Rasmussen et al., 2014) over the counted part of
+// GICC05, back to 60 ka.
+#[rustfmt::skip]
+const GICC05: &[(&str, f64, f64)] = &[
+    ("8.2 ka event", 8236.0, 47.0),
+    ("Holocene", 11703.0, 99.0),
+    ("GS-1", 12896.0, 138.0),
+    ("GI-1a", 13099.0, 143.0),
+    ("GI-1b", 13311.0, 149.0),
+    ("GI-1c", 13954.0, 165.0),
+    ("GI-1d", 14075.0, 169.0),
+    ("GI-1", 14692.0, 186.0),
+    ("GI-2.1", 23340.0, 596.0),
+    ("GI-2.2", 23780.0, 614.0),
+    ("GI-3", 27780.0, 832.0),
+    ("GI-4", 28900.0, 898.0),
+    ("GI-5.1", 30840.0, 1008.0),
+    ("GI-5.2", 32500.0, 1132.0),
+    ("GI-6", 33740.0, 1180.0),
+    ("GI-7", 35480.0, 1321.0),
+    ("GI-8", 38220.0, 1449.0),
+    ("GI-9", 40160.0, 1580.0),
+    ("GI-10", 41460.0, 1633.0),
+    ("GI-11", 43340.0, 1736.0),
+    ("GI-12", 46860.0, 1973.0),
+    ("GI-13", 49280.0, 2122.0),
+    ("GI-14", 54220.0, 2301.0),
+    ("GI-15.1", 55000.0, 2318.0),
+    ("GI-15.2", 55800.0, 2376.0),
+    ("GI-16.1", 58040.0, 2466.0),
+    ("GI-16.2", 58280.0, 2479.0),
+    ("GI-17.1", 59080.0, 2519.0),
+    ("GI-17.2", 59440.0, 2533.0),
+];
+
+/// A Marine Isotope Stage or substage.
+///
+/// ```
+/// use geotime::{Geotime, MarineIsotopeStage};
+///
+/// let eemian = MarineIsotopeStage::named("MIS 5e").unwrap();
+/// assert!(eemian.is_warm());
+/// assert!((eemian.interval().start().to_years_bp() - 130e3).abs() < 1e-6);
+///
+/// let stage = Geotime::from_years_bp(20e3).marine_isotope_stage().unwrap();
+/// assert_eq!(stage.name(), "MIS 2");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarineIsotopeStage {
+    name: String,
+    interval: GeoInterval,
+}
+
+impl MarineIsotopeStage {
+    fn from_table(table: &[(&str, f64)], top: Option<f64>) -> Vec<Self> {
+        let mut stages: Vec<Self> = table
+            .iter()
+            .enumerate()
+            .map(|(i, (name, base))| {
+                let top = match i {
+                    0 => top,
+                    _ => Some(table[i - 1].1),
+                };
+                Self {
+                    name: format!("MIS {}", name),
+                    interval: GeoInterval {
+                        start: UncertainGeotime::from_years_bp(base * 1e3, 0.0),
+                        end: match top {
+                            Some(top) => UncertainGeotime::from_years_bp(top * 1e3, 0.0),
+                            None => Geotime::now().into(),
+                        },
+                    },
+                }
+            })
+            .collect();
+        stages.reverse();
+        stages
+    }
+
+    /// The stages of LR04, with the substages of MIS 5 following it, oldest first.
+    pub fn lr04() -> &'static [MarineIsotopeStage] {
+        static STAGES: OnceLock<Vec<MarineIsotopeStage>> = OnceLock::new();
+        STAGES.get_or_init(|| {
+            let mut stages = Self::from_table(LR04, None);
+            let i = stages.iter().position(|s| s.name == "MIS 5").unwrap_or(0);
+            let substages = Self::from_table(LR04_MIS_5, Some(71.0));
+            stages.splice(i + 1..i + 1, substages);
+            stages
+        })
+    }
+
+    /// Looks up a stage by name, with or without the "MIS" prefix and ignoring case: "MIS 5e",
+    /// "MIS5e" and "5e" are the same substage.
+    pub fn named(name: &str) -> Option<&'static MarineIsotopeStage> {
+        let name = name.trim();
+        let name = match name.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("mis") => name[3..].trim_start(),
+            _ => name,
+        };
+        Self::lr04()
+            .iter()
+            .find(|s| s.name[4..].eq_ignore_ascii_case(name))
+    }
+
+    /// The finest stage containing `time`: a substage within MIS 5, otherwise a stage.
+    pub fn at(time: Geotime) -> Option<&'static MarineIsotopeStage> {
+        Self::lr04()
+            .iter()
+            .filter(|s| s.interval.contains(time))
+            .max_by_key(|s| s.name.len())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn interval(&self) -> GeoInterval {
+        self.interval
+    }
+
+    /// Whether the stage is a warm (interglacial or interstadial) one.
+    pub fn is_warm(&self) -> bool {
+        match self.name.chars().last() {
+            Some(c @ 'a'..='e') => c != 'b' && c != 'd',
+            Some(c) => c.to_digit(10).is_some_and(|d| d % 2 == 1),
+            None => false,
+        }
+    }
+}
+
+/// A dated event in the Greenland ice cores.
+///
+/// ```
+/// use geotime::IceCoreEvent;
+///
+/// let holocene = IceCoreEvent::named("Holocene").unwrap();
+/// assert_eq!(holocene.age().time().to_b2k().round(), 11703.0);
+/// assert_eq!(holocene.max_counting_error(), 99.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct IceCoreEvent {
+    name: String,
+    age: UncertainGeotime,
+    max_counting_error: f64,
+}
+
+impl IceCoreEvent {
+    /// Events dated on the GICC05 timescale, youngest first: the 8.2 ka event, the onset of the
+    /// Holocene, and the onsets of the Greenland Stadial GS-1 (the Younger Dryas) and of the
+    /// Greenland Interstadials from GI-1 (the Bølling warming, with its substages) to GI-17.2.
+    pub fn gicc05() -> &'static [IceCoreEvent] {
+        static EVENTS: OnceLock<Vec<IceCoreEvent>> = OnceLock::new();
+        EVENTS.get_or_init(|| {
+            GICC05
+                .iter()
+                .map(|(name, b2k, mce)| Self {
+                    name: name.to_string(),
+                    age: UncertainGeotime::new(
+                        Geotime::from_b2k(*b2k),
+                        UncertainGeotime::from_years_bp(0.0, mce / 2.0).sigma_millis(),
+                    ),
+                    max_counting_error: *mce,
+                })
+                .collect()
+        })
+    }
+
+    /// Looks up an event by name, ignoring case.
+    pub fn named(name: &str) -> Option<&'static IceCoreEvent> {
+        Self::gicc05()
+            .iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The age of the event, with half the maximum counting error as its uncertainty.
+    pub fn age(&self) -> UncertainGeotime {
+        self.age
+    }
+
+    /// The maximum counting error, in years.
+    pub fn max_counting_error(&self) -> f64 {
+        self.max_counting_error
+    }
+}
+
+impl Geotime {
+    /// Timestamp for an age given in (Julian) years before 2000, the convention of the ice-core
+    /// chronologies.  An age in b2k is 50 years more than the same age before present (1950).
+    ///
+    /// ```
+    /// use geotime::Geotime;
+    ///
+    /// assert_eq!(Geotime::from_b2k(11703.0), Geotime::from_years_bp(11653.0));
+    /// ```
+    pub fn from_b2k(years: f64) -> Self {
+        Self::from_years_bp(years - 50.0)
+    }
+
+    /// Age of the timestamp in years before 2000.
+    pub fn to_b2k(&self) -> f64 {
+        self.to_years_bp() + 50.0
+    }
+
+    /// The Marine Isotope Stage containing the timestamp.  See [`MarineIsotopeStage::at`].
+    pub fn marine_isotope_stage(&self) -> Option<&'static MarineIsotopeStage> {
+        MarineIsotopeStage::at(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contiguous() {
+        let stages: Vec<&MarineIsotopeStage> = MarineIsotopeStage::lr04()
+            .iter()
+            .filter(|s| !s.name.ends_with(|c: char| c.is_ascii_lowercase()))
+            .collect();
+        assert_eq!(stages.len(), 104);
+        for pair in stages.windows(2) {
+            assert_eq!(pair[0].interval.end, pair[1].interval.start);
+        }
+        assert_eq!(stages[0].name(), "MIS 104");
+        assert!(stages[103].interval.contains(Geotime::PRESENT));
+    }
+
+    #[test]
+    fn substages() {
+        let mis5 = MarineIsotopeStage::named("5").unwrap().interval();
+        let substages: Vec<&MarineIsotopeStage> = MarineIsotopeStage::lr04()
+            .iter()
+            .filter(|s| {
+                s.name.starts_with("MIS 5") && s.name.ends_with(|c: char| c.is_ascii_lowercase())
+            })
+            .collect();
+        assert_eq!(substages.len(), 5);
+        assert_eq!(substages[0].name(), "MIS 5e");
+        assert_eq!(substages[0].interval.start, mis5.start);
+        assert_eq!(substages[4].interval.end, mis5.end);
+        for pair in substages.windows(2) {
+            assert_eq!(pair[0].interval.end, pair[1].interval.start);
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let stage = |ka: f64| MarineIsotopeStage::at(Geotime::from_years_bp(ka * 1e3)).unwrap();
+        assert_eq!(stage(0.0).name(), "MIS 1");
+        assert_eq!(stage(125.0).name(), "MIS 5e");
+        assert_eq!(stage(100.0).name(), "MIS 5d");
+        assert_eq!(stage(140.0).name(), "MIS 6");
+        assert_eq!(stage(420.0).name(), "MIS 11");
+        assert_eq!(stage(1000.0).name(), "MIS 28");
+        assert_eq!(stage(2520.0).name(), "MIS 100");
+        assert_eq!(stage(2580.0).name(), "MIS 103");
+        assert_eq!(MarineIsotopeStage::at(Geotime::from_years_bp(2.7e6)), None);
+
+        assert_eq!(MarineIsotopeStage::named("mis5E").unwrap().name(), "MIS 5e");
+        assert_eq!(
+            MarineIsotopeStage::named(" MIS 11 ").unwrap().name(),
+            "MIS 11"
+        );
+        assert_eq!(
+            MarineIsotopeStage::named("MIS 104").unwrap().name(),
+            "MIS 104"
+        );
+        assert_eq!(MarineIsotopeStage::named("MIS 105"), None);
+    }
+
+    #[test]
+    fn warm_and_cold() {
+        let warm = |name| MarineIsotopeStage::named(name).unwrap().is_warm();
+        assert!(warm("1") && warm("5") && warm("5a") && warm("5e") && warm("11"));
+        assert!(!warm("2") && !warm("5b") && !warm("5d") && !warm("6") && !warm("20"));
+        assert!(warm("103") && !warm("104"));
+    }
+
+    #[test]
+    fn ice_cores() {
+        let events = IceCoreEvent::gicc05();
+        assert!(events.windows(2).all(|e| e[0].age.time() > e[1].age.time()));
+
+        let yd = IceCoreEvent::named("gs-1").unwrap();
+        assert!((yd.age().time().to_years_bp() - 12846.0).abs() < 1e-6);
+        assert!((yd.age().sigma_years() - 69.0).abs() < 1e-6);
+        assert_eq!(
+            MarineIsotopeStage::at(yd.age().time()).unwrap().name(),
+            "MIS 1"
+        );
+
+        assert_eq!(events.len(), 29);
+        assert!(events
+            .windows(2)
+            .all(|e| e[0].max_counting_error < e[1].max_counting_error));
+        let gi8 = IceCoreEvent::named("GI-8").unwrap();
+        assert_eq!(gi8.age().time().to_b2k().round(), 38220.0);
+        assert_eq!(
+            MarineIsotopeStage::at(gi8.age().time()).unwrap().name(),
+            "MIS 3"
+        );
+        assert!(IceCoreEvent::named("gi-17.2").is_some());
+
+        let t = Geotime::from_b2k(50.0);
+        assert_eq!(t, Geotime::PRESENT);
+        assert!((Geotime::from_years_bp(1000.0).to_b2k() - 1050.0).abs() < 1e-9);
+    }
+}
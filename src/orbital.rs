@@ -0,0 +1,324 @@
+//! Orbital solutions for the Milankovitch parameters of the Earth: eccentricity, obliquity and
+//! climatic precession, and the daily insolation that follows from them.
+//!
+//! Cyclostratigraphy tunes sections to the numerical solutions of Laskar et al.  The La2004
+//! solution gives all three parameters back to 101 Ma (and is reliable to about 50 Ma), and La2010
+//! gives eccentricity back to 250 Ma.  The tables run to tens of megabytes and are distributed by
+//! the IMCCE, so they are not bundled: download them and load them with
+//! [`OrbitalSolution::from_laskar`].
+use std::f64::consts::{PI, TAU};
+
+use crate::interp::Curve;
+use crate::{
+    Error, GeoInterval, Geotime, JulianDate, Result, UncertainGeotime, MILLISECONDS_IN_JULIAN_YEAR,
+};
+
+/// The total solar irradiance, in W/m², used for insolation.  Insolation scales linearly with it.
+pub const SOLAR_CONSTANT: f64 = 1361.0;
+
+/// The orbital elements that set the insolation of the Earth.  Angles are in radians.
+///
+/// - `eccentricity` of the orbit.
+/// - `obliquity`, the tilt of the axis.
+/// - `perihelion`, ϖ, the longitude of the perihelion measured from the moving vernal equinox, as
+///   tabulated by Laskar et al.  The Sun is at perihelion when its longitude is ϖ + 180°.
+///
+/// ```
+/// use geotime::OrbitalElements;
+///
+/// let today = OrbitalElements::J2000;
+/// assert!((today.climatic_precession() - 0.0163).abs() < 1e-4);
+///
+/// // Insolation at 65° N on the day of the June solstice.
+/// let june = today.daily_insolation(65.0, 90.0);
+/// assert!((june - 477.0).abs() < 5.0);
+/// // Polar night in the south.
+/// assert_eq!(today.daily_insolation(-70.0, 90.0), 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitalElements {
+    pub eccentricity: f64,
+    pub obliquity: f64,
+    pub perihelion: f64,
+}
+
+impl OrbitalElements {
+    /// The elements at J2000.0 (Simon et al., 1994).
+    pub const J2000: Self = Self {
+        eccentricity: 0.016708634,
+        obliquity: 0.409092804,
+        perihelion: 1.796593,
+    };
+
+    /// The climatic precession index, e sin ϖ.
+    pub fn climatic_precession(&self) -> f64 {
+        self.eccentricity * self.perihelion.sin()
+    }
+
+    /// The insolation at the top of the atmosphere, averaged over the day, in W/m², at
+    /// `latitude` in degrees on the day when the true longitude of the Sun is `solar_longitude`
+    /// degrees: 0 at the March equinox and 90 at the June solstice (Berger, 1978).
+    pub fn daily_insolation(&self, latitude: f64, solar_longitude: f64) -> f64 {
+        let (phi, lambda) = (latitude.to_radians(), solar_longitude.to_radians());
+        let e = self.eccentricity;
+        let declination = (self.obliquity.sin() * lambda.sin()).asin();
+        // The hour angle of sunset, which is zero in polar night and π in polar day.
+        let h0 = (-phi.tan() * declination.tan()).clamp(-1.0, 1.0).acos();
+        let distance = (1.0 + e * (lambda - self.perihelion - PI).cos()) / (1.0 - e * e);
+        let geometry =
+            h0 * phi.sin() * declination.sin() + phi.cos() * declination.cos() * h0.sin();
+        (SOLAR_CONSTANT / PI * distance * distance * geometry).max(0.0)
+    }
+}
+
+/// A table of orbital elements through time, interpolated with natural cubic splines.
+///
+/// ```
+/// use geotime::{Geotime, OrbitalSolution};
+///
+/// // Illustrative rows in the format of the La2004 files: kyr from J2000, then eccentricity,
+/// // obliquity and ϖ in radians.
+/// let table = "
+///        0.0  0.1670863D-01  0.4090928D+00  0.1796593D+01
+///       -1.0  0.1736346D-01  0.4112405D+00  0.1492436D+01
+///       -2.0  0.1802170D-01  0.4133526D+00  0.1189124D+01
+/// ";
+/// let solution = OrbitalSolution::from_laskar("example", table).unwrap();
+/// let time = Geotime::from_years_bp(1000.0);
+/// let e = solution.eccentricity(time).unwrap();
+/// assert!(e > 0.0167 && e < 0.0174);
+/// assert!(solution.insolation(time, 65.0, 90.0).unwrap() > 450.0);
+/// assert!(solution.eccentricity(Geotime::from_ma(1.0)).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrbitalSolution {
+    name: String,
+    eccentricity: Curve,
+    obliquity: Option<Curve>,
+    perihelion: Option<Curve>,
+}
+
+impl OrbitalSolution {
+    /// Parses a solution in the ASCII format of Laskar et al.: whitespace-separated columns of
+    /// time in kyr from J2000 (negative in the past), eccentricity, and optionally obliquity and
+    /// the longitude of the perihelion in radians.  Exponents may be written with "D", as
+    /// Fortran does.  Lines that are blank or begin with "#" are skipped.
+    pub fn from_laskar(name: &str, text: &str) -> Result<Self> {
+        let mut rows: Vec<Vec<f64>> = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || Error::InvalidInput(format!("line {}: {}", i + 1, line));
+            let row = line
+                .split_whitespace()
+                .map(|v| v.replace(['D', 'd'], "E").parse::<f64>())
+                .collect::<std::result::Result<Vec<f64>, _>>()
+                .map_err(|_| invalid())?;
+            if ![2, 4].contains(&row.len()) || rows.first().is_some_and(|r| r.len() != row.len()) {
+                return Err(invalid());
+            }
+            rows.push(row);
+        }
+        rows.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        if rows.len() < 2 || rows.windows(2).any(|w| w[0][0] == w[1][0]) {
+            return Err(Error::InvalidInput(format!(
+                "{}: at least two rows with distinct times are needed",
+                name
+            )));
+        }
+
+        let kyr: Vec<f64> = rows.iter().map(|r| r[0]).collect();
+        let column = |i: usize| -> Vec<f64> { rows.iter().map(|r| r[i]).collect() };
+        let (obliquity, perihelion) = match rows[0].len() {
+            4 => (
+                Some(Curve::natural_spline(&kyr, &column(2))),
+                Some(Curve::natural_spline(&kyr, &unwrap(&column(3)))),
+            ),
+            _ => (None, None),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            eccentricity: Curve::natural_spline(&kyr, &column(1)),
+            obliquity,
+            perihelion,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The interval covered by the table.
+    pub fn interval(&self) -> GeoInterval {
+        GeoInterval {
+            start: UncertainGeotime::from(from_kyr(self.eccentricity.min_x())),
+            end: UncertainGeotime::from(from_kyr(self.eccentricity.max_x())),
+        }
+    }
+
+    pub fn eccentricity(&self, time: Geotime) -> Result<f64> {
+        self.eval(Some(&self.eccentricity), time)
+    }
+
+    /// The obliquity in radians.
+    pub fn obliquity(&self, time: Geotime) -> Result<f64> {
+        self.eval(self.obliquity.as_ref(), time)
+    }
+
+    /// The longitude of the perihelion from the moving equinox, ϖ, in radians in [0, 2π).
+    pub fn perihelion(&self, time: Geotime) -> Result<f64> {
+        Ok(self.eval(self.perihelion.as_ref(), time)?.rem_euclid(TAU))
+    }
+
+    pub fn climatic_precession(&self, time: Geotime) -> Result<f64> {
+        Ok(self.elements(time)?.climatic_precession())
+    }
+
+    pub fn elements(&self, time: Geotime) -> Result<OrbitalElements> {
+        Ok(OrbitalElements {
+            eccentricity: self.eccentricity(time)?,
+            obliquity: self.obliquity(time)?,
+            perihelion: self.perihelion(time)?,
+        })
+    }
+
+    /// The daily insolation in W/m² at `time`; see [`OrbitalElements::daily_insolation`].
+    pub fn insolation(&self, time: Geotime, latitude: f64, solar_longitude: f64) -> Result<f64> {
+        Ok(self
+            .elements(time)?
+            .daily_insolation(latitude, solar_longitude))
+    }
+
+    fn eval(&self, curve: Option<&Curve>, time: Geotime) -> Result<f64> {
+        let curve = curve.ok_or_else(|| {
+            Error::NotFound(format!(
+                "{} has no obliquity or precession, only eccentricity",
+                self.name
+            ))
+        })?;
+        curve.eval(to_kyr(time)).ok_or_else(|| {
+            Error::OutOfRange(format!(
+                "{} is outside {}",
+                time.display_string("%Y-%m-%d"),
+                self.name
+            ))
+        })
+    }
+}
+
+// Removes the jumps of 2π from a sequence of angles, so that it can be interpolated.
+fn unwrap(angles: &[f64]) -> Vec<f64> {
+    let mut offset = 0.0;
+    let mut unwrapped: Vec<f64> = Vec::with_capacity(angles.len());
+    for (i, angle) in angles.iter().enumerate() {
+        if i > 0 {
+            let step = angle + offset - unwrapped[i - 1];
+            offset -= (step / TAU).round() * TAU;
+        }
+        unwrapped.push(angle + offset);
+    }
+    unwrapped
+}
+
+fn j2000() -> Geotime {
+    Geotime::try_from(JulianDate::J2000).expect("J2000 is in range")
+}
+
+fn to_kyr(time: Geotime) -> f64 {
+    (time.millis() - j2000().millis()) as f64 / MILLISECONDS_IN_JULIAN_YEAR as f64 / 1e3
+}
+
+fn from_kyr(kyr: f64) -> Geotime {
+    let millis = (kyr * 1e3 * MILLISECONDS_IN_JULIAN_YEAR as f64).round() as i128;
+    Geotime::from(j2000().millis() + millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A smooth synthetic solution, every 1 kyr back to 500 kyr, with ϖ circulating every 23 kyr.
+    fn synthetic(t: f64) -> (f64, f64, f64) {
+        (
+            0.03 + 0.02 * (TAU * t / 405.0).sin(),
+            0.41 + 0.01 * (TAU * t / 41.0).cos(),
+            (1.8 + TAU * t / 23.0).rem_euclid(TAU),
+        )
+    }
+
+    fn table() -> String {
+        (0..=500)
+            .map(|k| {
+                let t = -(k as f64);
+                let (e, eps, varpi) = synthetic(t);
+                format!("{} {:.12e} {:.12e} {:.12e}\n", t, e, eps, varpi)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn interpolation() {
+        let solution = OrbitalSolution::from_laskar("synthetic", &table()).unwrap();
+        // Away from the ends of the table, where the natural end conditions cost some accuracy.
+        for t in [-5.25, -12.5, -100.3, -494.9] {
+            let time = from_kyr(t);
+            let (e, eps, varpi) = synthetic(t);
+            assert!((solution.eccentricity(time).unwrap() - e).abs() < 1e-6);
+            assert!((solution.obliquity(time).unwrap() - eps).abs() < 1e-6);
+            let error = (solution.perihelion(time).unwrap() - varpi).rem_euclid(TAU);
+            assert!(error.min(TAU - error) < 1e-4, "{}", t);
+            assert!((solution.climatic_precession(time).unwrap() - e * varpi.sin()).abs() < 1e-5);
+        }
+        let interval = solution.interval();
+        assert_eq!(interval.start().time(), from_kyr(-500.0));
+        assert_eq!(interval.end().time(), j2000());
+        assert!(solution.eccentricity(from_kyr(-501.0)).is_err());
+        assert!(solution.eccentricity(from_kyr(1.0)).is_err());
+    }
+
+    #[test]
+    fn formats() {
+        let solution =
+            OrbitalSolution::from_laskar("La2010", "# t e\n0 0.0167D0\n-1 0.0174d0\n").unwrap();
+        let e = solution.eccentricity(from_kyr(-0.5)).unwrap();
+        assert!((e - 0.01705).abs() < 1e-9);
+        assert!(matches!(
+            solution.obliquity(from_kyr(-0.5)),
+            Err(Error::NotFound(_))
+        ));
+        assert!(OrbitalSolution::from_laskar("x", "0 0.0167").is_err());
+        assert!(OrbitalSolution::from_laskar("x", "0 0.0167\n-1 0.0174 0.4 1.0").is_err());
+        assert!(OrbitalSolution::from_laskar("x", "0 0.0167\n0 0.0174").is_err());
+        assert!(OrbitalSolution::from_laskar("x", "0 e\n-1 0.0174").is_err());
+    }
+
+    #[test]
+    fn insolation() {
+        let circular = OrbitalElements {
+            eccentricity: 0.0,
+            ..OrbitalElements::J2000
+        };
+        // The equator at an equinox receives S/π.
+        let equinox = circular.daily_insolation(0.0, 0.0);
+        assert!((equinox - SOLAR_CONSTANT / PI).abs() < 1e-9);
+        // The pole at the solstice receives S sin ε all day.
+        let pole = circular.daily_insolation(90.0, 90.0);
+        assert!((pole - SOLAR_CONSTANT * circular.obliquity.sin()).abs() < 1e-6);
+        assert_eq!(circular.daily_insolation(90.0, 270.0), 0.0);
+
+        // Today the Earth is nearest the Sun in January.
+        let today = OrbitalElements::J2000;
+        assert!(today.daily_insolation(-45.0, 270.0) > today.daily_insolation(45.0, 90.0));
+    }
+
+    #[test]
+    fn unwrapping() {
+        let angles = [6.0, 0.2, 0.7, 6.1, 5.5];
+        let unwrapped = unwrap(&angles);
+        assert!((unwrapped[1] - (0.2 + TAU)).abs() < 1e-12);
+        assert!((unwrapped[3] - 6.1).abs() < 1e-12);
+        assert!((unwrapped[4] - 5.5).abs() < 1e-12);
+    }
+}
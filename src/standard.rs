@@ -0,0 +1,538 @@
+//! Time standards and leap seconds.
+//!
+//! A [`Geotime`] counts milliseconds as Unix time does, with every day 86,400 seconds long, so a
+//! UTC timestamp skips over leap seconds.  Atomic and dynamical time standards have no leap
+//! seconds.  Here a time in one of those standards is also a `Geotime`: its reading on a calendar
+//! that ticks in that standard, counted from 1970-01-01T00:00:00 of that standard.  Converting
+//! between UTC and the others needs a table of leap seconds; one is bundled, and newer ones can be
+//! read from the IERS `leap-seconds.list` file and installed with [`LeapSeconds::set_current`].
+//!
+//! UTC before 1972 used rubber seconds and fractional steps, and is not modelled: the offset of
+//! 1 January 1972, 10 s, is used for all earlier times.
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use chrono::NaiveDate;
+
+use crate::{Error, Geotime, Result};
+
+// TT - TAI, and TAI - GPS time, in milliseconds.
+const TT_MINUS_TAI: i128 = 32184;
+const TAI_MINUS_GPS: i128 = 19000;
+
+// Seconds from the NTP epoch, 1900-01-01, to the Unix epoch.
+const NTP_UNIX_OFFSET: i128 = 2208988800;
+
+// The steps in TAI - UTC since 1972: the year and month of the day they took effect, and the new
+// offset in seconds.
+#[rustfmt::skip]
+const LEAP_SECONDS: [(i32, u32, i64); 28] = [
+    (1972, 1, 10), (1972, 7, 11), (1973, 1, 12), (1974, 1, 13), (1975, 1, 14), (1976, 1, 15),
+    (1977, 1, 16), (1978, 1, 17), (1979, 1, 18), (1980, 1, 19), (1981, 7, 20), (1982, 7, 21),
+    (1983, 7, 22), (1985, 7, 23), (1988, 1, 24), (1990, 1, 25), (1991, 1, 26), (1992, 7, 27),
+    (1993, 7, 28), (1994, 7, 29), (1996, 1, 30), (1997, 7, 31), (1999, 1, 32), (2006, 1, 33),
+    (2009, 1, 34), (2012, 7, 35), (2015, 7, 36), (2017, 1, 37),
+];
+
+// The expiry of the IERS list from which the bundled table was last updated.
+const BUNDLED_EXPIRY: (i32, u32, u32) = (2025, 12, 28);
+
+/// A standard for counting time.
+///
+/// - `UTC`: Coordinated Universal Time, with leap seconds, as `Geotime` counts it.
+/// - `TAI`: International Atomic Time.
+/// - `TT`: Terrestrial Time, TAI + 32.184 s, the successor of Ephemeris Time.
+/// - `GPS`: GPS time, TAI − 19 s, equal to UTC in January 1980.
+/// - `TDB`: Barycentric Dynamical Time, which differs from TT by a periodic term of under 2 ms,
+///   here to the accuracy of the two leading terms.
+///
+/// ```
+/// use geotime::{Geotime, TimeStandard};
+///
+/// let utc = Geotime::from(1_483_228_800_000_i64); // 2017-01-01T00:00:00Z
+/// let tai = TimeStandard::TAI.at_utc(utc);
+/// assert_eq!(tai.millis() - utc.millis(), 37_000);
+/// assert_eq!(TimeStandard::TAI.to_utc(tai), utc);
+/// assert_eq!(TimeStandard::GPS.format(TimeStandard::GPS.at_utc(utc)), "2017-01-01T00:00:18.000 GPS");
+///
+/// // The leap second before it.
+/// let leap = Geotime::from(tai.millis() - 500);
+/// assert_eq!(TimeStandard::TAI.format_utc(leap), "2016-12-31T23:59:60.500Z");
+/// ```
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum TimeStandard {
+    UTC,
+    TAI,
+    TT,
+    GPS,
+    TDB,
+}
+
+impl TimeStandard {
+    /// The reading of this standard at the instant `utc`, with the current leap-second table.
+    /// Readings beyond the range of a `Geotime` saturate; see [`TimeStandard::try_at_utc`].
+    pub fn at_utc(self, utc: Geotime) -> Geotime {
+        self.try_at_utc(utc).unwrap_or_else(|_| saturated(utc))
+    }
+
+    /// The reading of this standard at the instant `utc`, or an error if it lies beyond the range
+    /// of a `Geotime`.
+    pub fn try_at_utc(self, utc: Geotime) -> Result<Geotime> {
+        self.try_at_tai(LeapSeconds::current().try_to_tai(utc)?)
+    }
+
+    /// The UTC time at the instant this standard reads `time`.  An instant within a leap second,
+    /// which a `Geotime` in UTC cannot represent, gives the end of the leap second.  Times beyond
+    /// the range of a `Geotime` saturate; see [`TimeStandard::try_to_utc`].
+    pub fn to_utc(self, time: Geotime) -> Geotime {
+        self.try_to_utc(time).unwrap_or_else(|_| saturated(time))
+    }
+
+    /// The UTC time at the instant this standard reads `time`, or an error if it lies beyond the
+    /// range of a `Geotime`.
+    pub fn try_to_utc(self, time: Geotime) -> Result<Geotime> {
+        LeapSeconds::current().try_to_utc(self.try_to_tai(time)?)
+    }
+
+    /// The reading of `to` at the instant this standard reads `time`.
+    pub fn convert(self, time: Geotime, to: TimeStandard) -> Geotime {
+        self.try_convert(time, to)
+            .unwrap_or_else(|_| saturated(time))
+    }
+
+    /// The reading of `to` at the instant this standard reads `time`, or an error if it lies
+    /// beyond the range of a `Geotime`.
+    pub fn try_convert(self, time: Geotime, to: TimeStandard) -> Result<Geotime> {
+        match (self, to) {
+            (TimeStandard::UTC, _) => to.try_at_utc(time),
+            (_, TimeStandard::UTC) => self.try_to_utc(time),
+            _ => to.try_at_tai(self.try_to_tai(time)?),
+        }
+    }
+
+    // From TAI to this standard, with the current table for UTC.
+    fn try_at_tai(self, tai: Geotime) -> Result<Geotime> {
+        match self {
+            TimeStandard::UTC => LeapSeconds::current().try_to_utc(tai),
+            TimeStandard::TAI => Ok(tai),
+            TimeStandard::TT => shift(tai, TT_MINUS_TAI),
+            TimeStandard::GPS => shift(tai, -TAI_MINUS_GPS),
+            TimeStandard::TDB => {
+                let tt = shift(tai, TT_MINUS_TAI)?;
+                shift(tt, tdb_minus_tt(tt.millis()))
+            }
+        }
+    }
+
+    fn try_to_tai(self, time: Geotime) -> Result<Geotime> {
+        match self {
+            TimeStandard::UTC => LeapSeconds::current().try_to_tai(time),
+            TimeStandard::TAI => Ok(time),
+            TimeStandard::TT => shift(time, -TT_MINUS_TAI),
+            TimeStandard::GPS => shift(time, TAI_MINUS_GPS),
+            TimeStandard::TDB => shift(time, -tdb_minus_tt(time.millis()) - TT_MINUS_TAI),
+        }
+    }
+
+    /// Formats a reading of this standard as an ISO 8601 date and time, followed by the name of
+    /// the standard, or "Z" for UTC.
+    pub fn format(self, time: Geotime) -> String {
+        let text = time.display_string("%Y-%m-%dT%H:%M:%S%.3f");
+        match self {
+            TimeStandard::UTC => text + "Z",
+            _ => format!("{} {}", text, self),
+        }
+    }
+
+    /// Formats the UTC time at the instant this standard reads `time`, writing a leap second as
+    /// 23:59:60.
+    pub fn format_utc(self, time: Geotime) -> String {
+        let tai = self.try_to_tai(time).unwrap_or_else(|_| saturated(time));
+        LeapSeconds::current().format_utc(tai)
+    }
+}
+
+impl fmt::Display for TimeStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for TimeStandard {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "UTC" | "Z" => Ok(TimeStandard::UTC),
+            "TAI" => Ok(TimeStandard::TAI),
+            "TT" | "TDT" => Ok(TimeStandard::TT),
+            "GPS" | "GPST" => Ok(TimeStandard::GPS),
+            "TDB" => Ok(TimeStandard::TDB),
+            _ => Err(Error::InvalidInput(format!("unknown time standard: {}", s))),
+        }
+    }
+}
+
+// `time` moved by `millis`, or an error beyond the range of a Geotime.
+fn shift(time: Geotime, millis: i128) -> Result<Geotime> {
+    time.millis()
+        .checked_add(millis)
+        .map(Geotime::from)
+        .ok_or_else(|| {
+            Error::OutOfRange(format!(
+                "{} is out of range for a change of time standard",
+                time.display_string("%Y")
+            ))
+        })
+}
+
+// The end of the range of a Geotime nearest to `time`, where a shift out of range saturates.
+pub(crate) fn saturated(time: Geotime) -> Geotime {
+    match time.millis() < 0 {
+        true => Geotime::from(i128::MIN),
+        false => Geotime::from(i128::MAX),
+    }
+}
+
+// TDB - TT in milliseconds at `tt`, after Fairhead and Bretagnon (1990).
+fn tdb_minus_tt(tt: i128) -> i128 {
+    let days = (tt as f64 - 946_728_000_000.0) / 86_400_000.0;
+    let g = (357.53 + 0.985_600_28 * days).to_radians();
+    ((1.657 * g.sin() + 0.014 * (2.0 * g).sin()).round()) as i128
+}
+
+/// A table of the steps in TAI − UTC.
+///
+/// ```
+/// use geotime::{Geotime, LeapSeconds};
+///
+/// let table = LeapSeconds::bundled();
+/// let start = Geotime::from(1_435_708_799_000_i64); // 2015-06-30T23:59:59Z
+/// let end = Geotime::from(1_435_708_800_000_i64); // 2015-07-01T00:00:00Z
+/// assert_eq!(table.elapsed(start, end), 2_000);
+/// assert_eq!(table.tai_minus_utc(end), 37 - 1);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LeapSeconds {
+    // The UTC times at which each offset took effect, and the offset in seconds, in order.
+    steps: Vec<(Geotime, i64)>,
+    expires: Option<Geotime>,
+}
+
+impl LeapSeconds {
+    /// The table bundled with the crate.
+    pub fn bundled() -> &'static LeapSeconds {
+        static TABLE: OnceLock<LeapSeconds> = OnceLock::new();
+        TABLE.get_or_init(|| {
+            let mut table = LeapSeconds::default();
+            for (year, month, offset) in LEAP_SECONDS {
+                table.steps.push((utc_date(year, month, 1), offset));
+            }
+            let (year, month, day) = BUNDLED_EXPIRY;
+            table.expires = Some(utc_date(year, month, day));
+            table
+        })
+    }
+
+    /// The table used by [`TimeStandard`], which is the bundled one until replaced.
+    pub fn current() -> Arc<LeapSeconds> {
+        current().read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replaces the table used by [`TimeStandard`].
+    pub fn set_current(table: LeapSeconds) {
+        *current().write().unwrap_or_else(|e| e.into_inner()) = Arc::new(table);
+    }
+
+    /// Reads the IERS `leap-seconds.list` file, in which each line gives the NTP time (seconds
+    /// since 1900) of a step and the new value of TAI − UTC, and a line starting "#@" gives the
+    /// time at which the file expires.
+    pub fn from_iers(text: &str) -> Result<Self> {
+        let mut table = LeapSeconds::default();
+        for line in text.lines() {
+            let invalid = || Error::InvalidInput(format!("invalid leap-second entry: {}", line));
+            let ntp = |field: &str| -> Result<Geotime> {
+                let seconds: i128 = field.parse().map_err(|_| invalid())?;
+                seconds
+                    .checked_sub(NTP_UNIX_OFFSET)
+                    .and_then(|seconds| seconds.checked_mul(1000))
+                    .map(Geotime::from)
+                    .ok_or_else(invalid)
+            };
+            if let Some(expiry) = line.strip_prefix("#@") {
+                table.expires = Some(ntp(expiry.trim())?);
+                continue;
+            }
+            let data = line.split('#').next().unwrap_or("").trim();
+            if data.is_empty() {
+                continue;
+            }
+            match data.split_whitespace().collect::<Vec<_>>()[..] {
+                [time, offset] => {
+                    table.insert(ntp(time)?, offset.parse().map_err(|_| invalid())?);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(table)
+    }
+
+    /// Records that TAI − UTC became `offset` seconds at `utc`, replacing any step at that time.
+    pub fn insert(&mut self, utc: Geotime, offset: i64) {
+        match self.steps.binary_search_by_key(&utc, |(time, _)| *time) {
+            Ok(i) => self.steps[i].1 = offset,
+            Err(i) => self.steps.insert(i, (utc, offset)),
+        }
+    }
+
+    /// The steps in order, as the UTC time each took effect and the new offset in seconds.
+    pub fn steps(&self) -> &[(Geotime, i64)] {
+        &self.steps
+    }
+
+    /// The time until which the table is known to be complete.
+    pub fn expires(&self) -> Option<Geotime> {
+        self.expires
+    }
+
+    /// TAI − UTC, in seconds, at the UTC time `utc`.
+    pub fn tai_minus_utc(&self, utc: Geotime) -> i64 {
+        let i = self.steps.partition_point(|(time, _)| *time <= utc);
+        match i {
+            0 => self.steps.first().map_or(0, |(_, offset)| *offset),
+            i => self.steps[i - 1].1,
+        }
+    }
+
+    /// The TAI reading at the UTC time `utc`, saturating at the ends of the range of a
+    /// `Geotime`.
+    pub fn to_tai(&self, utc: Geotime) -> Geotime {
+        self.try_to_tai(utc).unwrap_or_else(|_| saturated(utc))
+    }
+
+    /// The TAI reading at the UTC time `utc`, or an error beyond the range of a `Geotime`.
+    pub fn try_to_tai(&self, utc: Geotime) -> Result<Geotime> {
+        shift(utc, self.tai_minus_utc(utc) as i128 * 1000)
+    }
+
+    /// The UTC time at the TAI reading `tai`, or the end of the leap second that contains it,
+    /// saturating at the ends of the range of a `Geotime`.
+    pub fn to_utc(&self, tai: Geotime) -> Geotime {
+        self.try_to_utc(tai).unwrap_or_else(|_| saturated(tai))
+    }
+
+    /// The UTC time at the TAI reading `tai`, or the end of the leap second that contains it, or
+    /// an error beyond the range of a `Geotime`.
+    pub fn try_to_utc(&self, tai: Geotime) -> Result<Geotime> {
+        match self.leap_second(tai) {
+            Some((end, _)) => Ok(end),
+            None => {
+                let i = self
+                    .steps
+                    .partition_point(|(time, offset)| self.shift(*time, *offset) <= tai);
+                let offset = match i {
+                    0 => self.steps.first().map_or(0, |(_, offset)| *offset),
+                    i => self.steps[i - 1].1,
+                };
+                shift(tai, -(offset as i128) * 1000)
+            }
+        }
+    }
+
+    /// The SI milliseconds elapsed from the UTC time `start` to `end`, counting leap seconds.
+    pub fn elapsed(&self, start: Geotime, end: Geotime) -> i128 {
+        let (start, end) = (self.to_tai(start).millis(), self.to_tai(end).millis());
+        end.saturating_sub(start)
+    }
+
+    /// Formats the UTC time at the TAI reading `tai`, writing a leap second as 23:59:60.
+    pub fn format_utc(&self, tai: Geotime) -> String {
+        match self.leap_second(tai) {
+            Some((end, into)) => {
+                let before = Geotime::from(end.millis().saturating_sub(1000));
+                format!(
+                    "{}:60.{:03}Z",
+                    before.display_string("%Y-%m-%dT%H:%M"),
+                    into
+                )
+            }
+            None => TimeStandard::UTC.format(self.to_utc(tai)),
+        }
+    }
+
+    fn shift(&self, utc: Geotime, offset: i64) -> Geotime {
+        Geotime::from(utc.millis().saturating_add(offset as i128 * 1000))
+    }
+
+    // The UTC time at the end of the leap second containing `tai`, if any, and the milliseconds
+    // into it.
+    fn leap_second(&self, tai: Geotime) -> Option<(Geotime, i128)> {
+        self.steps.windows(2).find_map(|pair| {
+            let ((_, before), (time, after)) = (pair[0], pair[1]);
+            let start = self.shift(time, before).millis();
+            let end = self.shift(time, after).millis();
+            (start..end)
+                .contains(&tai.millis())
+                .then(|| (time, tai.millis() - start))
+        })
+    }
+}
+
+fn current() -> &'static RwLock<Arc<LeapSeconds>> {
+    static CURRENT: OnceLock<RwLock<Arc<LeapSeconds>>> = OnceLock::new();
+    CURRENT.get_or_init(|| RwLock::new(Arc::new(LeapSeconds::bundled().clone())))
+}
+
+fn utc_date(year: i32, month: u32, day: u32) -> Geotime {
+    let date = NaiveDate::from_ymd_opt(year, month, day).expect("bundled dates are valid");
+    Geotime::from(&date.and_time(Default::default()).and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IERS: &str = "\
+#	Updated through IERS Bulletin C 70
+#$	 3945434800
+#@	3960057600
+#
+2272060800	10	# 1 Jan 1972
+2287785600	11	# 1 Jul 1972
+3692217600	37	# 1 Jan 2017
+";
+
+    fn utc(s: &str) -> Geotime {
+        let date = chrono::DateTime::parse_from_rfc3339(s).unwrap();
+        Geotime::from(&date.with_timezone(&chrono::Utc))
+    }
+
+    #[test]
+    fn offsets() {
+        let table = LeapSeconds::bundled();
+        assert_eq!(table.tai_minus_utc(utc("1960-01-01T00:00:00Z")), 10);
+        assert_eq!(table.tai_minus_utc(utc("1972-06-30T23:59:59Z")), 10);
+        assert_eq!(table.tai_minus_utc(utc("1972-07-01T00:00:00Z")), 11);
+        assert_eq!(table.tai_minus_utc(utc("2024-01-01T00:00:00Z")), 37);
+        assert!(table.expires().unwrap() > utc("2025-01-01T00:00:00Z"));
+
+        // GPS time was UTC at its epoch.
+        let epoch = utc("1980-01-06T00:00:00Z");
+        assert_eq!(TimeStandard::GPS.at_utc(epoch), epoch);
+        let j2000 = utc("2000-01-01T11:58:55.816Z");
+        assert_eq!(
+            TimeStandard::TT.format(TimeStandard::TT.at_utc(j2000)),
+            "2000-01-01T12:00:00.000 TT"
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let times = [
+            "1975-03-01T12:00:00Z",
+            "2016-12-31T23:59:59.999Z",
+            "2017-01-01T00:00:00Z",
+            "2024-06-01T00:00:00.123Z",
+        ];
+        for standard in [
+            TimeStandard::UTC,
+            TimeStandard::TAI,
+            TimeStandard::TT,
+            TimeStandard::GPS,
+            TimeStandard::TDB,
+        ] {
+            for time in times {
+                let t = utc(time);
+                let converted = standard.at_utc(t);
+                assert_eq!(standard.to_utc(converted), t, "{} {}", standard, time);
+                assert_eq!(
+                    TimeStandard::TAI
+                        .convert(standard.convert(converted, TimeStandard::TAI), standard),
+                    converted
+                );
+            }
+        }
+        let t = utc("2024-06-01T00:00:00Z");
+        let tdb = TimeStandard::TT.convert(TimeStandard::TT.at_utc(t), TimeStandard::TDB);
+        assert!((tdb.millis() - TimeStandard::TT.at_utc(t).millis()).abs() <= 2);
+    }
+
+    #[test]
+    fn leap_second() {
+        let table = LeapSeconds::bundled();
+        let end = utc("2017-01-01T00:00:00Z");
+        let tai = table.to_tai(end);
+        assert_eq!(
+            table.format_utc(Geotime::from(tai.millis() - 1000)),
+            "2016-12-31T23:59:60.000Z"
+        );
+        assert_eq!(
+            table.format_utc(Geotime::from(tai.millis() - 1001)),
+            "2016-12-31T23:59:59.999Z"
+        );
+        assert_eq!(table.format_utc(tai), "2017-01-01T00:00:00.000Z");
+        assert_eq!(table.to_utc(Geotime::from(tai.millis() - 300)), end);
+
+        let start = utc("2016-12-31T23:59:59Z");
+        assert_eq!(table.elapsed(start, end), 2000);
+        assert_eq!(table.elapsed(utc("1972-01-01T00:00:00Z"), end), {
+            (end.millis() - utc("1972-01-01T00:00:00Z").millis()) + 27_000
+        });
+    }
+
+    #[test]
+    fn iers_file() {
+        let table = LeapSeconds::from_iers(IERS).unwrap();
+        assert_eq!(table.steps().len(), 3);
+        assert_eq!(table.steps()[1], (utc("1972-07-01T00:00:00Z"), 11));
+        assert_eq!(table.expires(), Some(utc("2025-06-28T00:00:00Z")));
+        assert!(LeapSeconds::from_iers("2272060800 ten").is_err());
+        for line in [
+            "999999999999999999999999999999999999 10",
+            "-999999999999999999999999999999999999 10",
+            "#@ 999999999999999999999999999999999999",
+        ] {
+            assert!(matches!(
+                LeapSeconds::from_iers(line),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+
+        let mut table = table;
+        table.insert(utc("2030-01-01T00:00:00Z"), 38);
+        assert_eq!(table.tai_minus_utc(utc("2031-01-01T00:00:00Z")), 38);
+    }
+
+    #[test]
+    fn extremes() {
+        let (min, max) = (Geotime::from(i128::MIN), Geotime::from(i128::MAX));
+        for standard in [TimeStandard::TAI, TimeStandard::TT, TimeStandard::TDB] {
+            assert!(matches!(
+                standard.try_at_utc(max),
+                Err(Error::OutOfRange(_))
+            ));
+            assert!(standard.try_to_utc(min).is_err());
+            assert_eq!(standard.at_utc(max), max);
+            assert_eq!(standard.to_utc(min), min);
+            let tai = standard.try_at_utc(min).unwrap();
+            assert_eq!(standard.try_to_utc(tai).unwrap(), min);
+        }
+        assert!(TimeStandard::GPS.try_at_utc(max).is_err());
+        assert!(TimeStandard::TT.try_convert(max, TimeStandard::TAI).is_ok());
+        assert!(TimeStandard::TAI
+            .try_convert(max, TimeStandard::TT)
+            .is_err());
+        assert_eq!(TimeStandard::TAI.convert(max, TimeStandard::TT), max);
+        assert_eq!(LeapSeconds::bundled().elapsed(min, max), i128::MAX);
+        assert!(TimeStandard::TAI.format_utc(max).ends_with("Z"));
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!("tai".parse::<TimeStandard>().unwrap(), TimeStandard::TAI);
+        assert_eq!("GPST".parse::<TimeStandard>().unwrap(), TimeStandard::GPS);
+        assert!("TCG".parse::<TimeStandard>().is_err());
+    }
+}
@@ -0,0 +1,819 @@
+//! Geologic time scales: hierarchies of named units (eons, eras, periods, ...) and the ages of
+//! their boundaries.  The International Chronostratigraphic Chart is bundled.
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::{Error, Geotime, Result, UncertainGeotime};
+
+mod custom;
+mod ics;
+mod planetary;
+mod versions;
+
+pub use custom::{AgeUnit, Correlation, Issue};
+pub use planetary::{LunarChronology, MartianChronology, MercurianChronology};
+
+/// The rank of a unit in the hierarchy, from the most to the least inclusive.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Rank {
+    Supereon,
+    Eon,
+    Era,
+    Period,
+    Subperiod,
+    Epoch,
+    Age,
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Supereon => "supereon",
+            Self::Eon => "eon",
+            Self::Era => "era",
+            Self::Period => "period",
+            Self::Subperiod => "subperiod",
+            Self::Epoch => "epoch",
+            Self::Age => "age",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for Rank {
+    type Err = Error;
+
+    /// Parses a geochronologic rank, or the equivalent chronostratigraphic rank (e.g., "stage"
+    /// for "age").
+    fn from_str(s: &str) -> Result<Self> {
+        let rank = match s.trim().to_ascii_lowercase().as_str() {
+            "supereon" | "supereonothem" => Self::Supereon,
+            "eon" | "eonothem" => Self::Eon,
+            "era" | "erathem" => Self::Era,
+            "period" | "system" => Self::Period,
+            "subperiod" | "subsystem" => Self::Subperiod,
+            "epoch" | "series" => Self::Epoch,
+            "age" | "stage" => Self::Age,
+            _ => return Err(Error::InvalidInput(format!("unknown rank: {}", s))),
+        };
+        Ok(rank)
+    }
+}
+
+/// How the base of a unit is defined.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Gssp {
+    /// Defined by a ratified Global Boundary Stratotype Section and Point.
+    Ratified,
+    /// A GSSP has not yet been ratified.
+    Pending,
+    /// Defined chronometrically by a Global Standard Stratigraphic Age.
+    Gssa,
+    /// An informal unit or boundary.
+    Informal,
+}
+
+/// An RGB color, such as the official chart color of a unit.
+///
+/// ```
+/// use geotime::Color;
+///
+/// assert_eq!(Color::from(0x7FC64E).to_string(), "#7FC64E");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl From<u32> for Color {
+    fn from(rgb: u32) -> Self {
+        Self {
+            r: (rgb >> 16) as u8,
+            g: (rgb >> 8) as u8,
+            b: rgb as u8,
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    /// Parses a hex color such as "#7FC64E".
+    fn from_str(s: &str) -> Result<Self> {
+        let hex = s.trim().trim_start_matches('#');
+        match u32::from_str_radix(hex, 16) {
+            Ok(rgb) if hex.len() == 6 => Ok(Self::from(rgb)),
+            _ => Err(Error::InvalidInput(format!("invalid color: {}", s))),
+        }
+    }
+}
+
+// Name, rank, parent, base (Ma), uncertainty of the base (Ma), GSSP status and color.
+pub(crate) type UnitData = (
+    &'static str,
+    Rank,
+    Option<&'static str>,
+    f64,
+    f64,
+    Gssp,
+    u32,
+);
+
+/// A named unit of a time scale.  A unit starts at its base and lasts until its top, the base
+/// of the next younger unit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unit {
+    name: String,
+    rank: Rank,
+    parent: Option<String>,
+    base: UncertainGeotime,
+    top: Option<UncertainGeotime>,
+    gssp: Gssp,
+    color: Option<Color>,
+}
+
+impl Unit {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rank(&self) -> Rank {
+        self.rank
+    }
+
+    /// The name of the enclosing unit, if any.
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// The older boundary of the unit.
+    pub fn base(&self) -> UncertainGeotime {
+        self.base
+    }
+
+    /// The younger boundary of the unit, or `None` if the unit extends to the present.
+    pub fn top(&self) -> Option<UncertainGeotime> {
+        self.top
+    }
+
+    pub fn gssp(&self) -> Gssp {
+        self.gssp
+    }
+
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Whether `time` falls within the unit.  Boundaries belong to the younger unit.
+    pub fn contains(&self, time: Geotime) -> bool {
+        self.base.time() <= time && self.top.is_none_or(|top| time < top.time())
+    }
+
+    fn fraction(&self, time: Geotime) -> Option<f64> {
+        let top = self.top?.time().millis();
+        let base = self.base.time().millis();
+        Some((time.millis() - base) as f64 / (top - base) as f64)
+    }
+}
+
+/// A unit before the boundaries of its top have been worked out.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) name: String,
+    pub(crate) rank: Rank,
+    pub(crate) parent: Option<String>,
+    pub(crate) base: UncertainGeotime,
+    // An explicit top, rather than one implied by the next younger sibling.
+    pub(crate) top: Option<UncertainGeotime>,
+    pub(crate) gssp: Gssp,
+    pub(crate) color: Option<Color>,
+}
+
+impl From<&UnitData> for Entry {
+    fn from(data: &UnitData) -> Self {
+        let (name, rank, parent, base, sigma, gssp, color) = *data;
+        Self {
+            name: name.to_string(),
+            rank,
+            parent: parent.map(str::to_string),
+            base: UncertainGeotime::from_ma(base, sigma),
+            top: None,
+            gssp,
+            color: Some(Color::from(color)),
+        }
+    }
+}
+
+/// A hierarchy of named units covering some span of time.
+///
+/// ```
+/// use geotime::{Geotime, Rank, TimeScale};
+///
+/// let ics = TimeScale::ics();
+/// let t = Geotime::from_ma(70.0);
+/// let period = ics.unit_at(t, Rank::Period).unwrap();
+/// assert_eq!(period.name(), "Cretaceous");
+/// assert_eq!(ics.unit_at(t, Rank::Age).unwrap().name(), "Maastrichtian");
+/// assert_eq!(period.color().unwrap().to_string(), "#7FC64E");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeScale {
+    name: String,
+    units: Vec<Unit>,
+}
+
+impl TimeScale {
+    /// Builds a scale from units whose tops are implied by the bases of their younger siblings,
+    /// unless given explicitly.  The youngest unit among its siblings shares the top of its
+    /// parent.
+    pub(crate) fn build(name: &str, mut entries: Vec<Entry>) -> Self {
+        // Oldest first, so that parents come before their children at the same base.
+        entries.sort_by(|a, b| a.base.time().cmp(&b.base.time()).then(a.rank.cmp(&b.rank)));
+
+        let mut tops: Vec<Option<Option<UncertainGeotime>>> = vec![None; entries.len()];
+        let mut remaining = entries.len();
+
+        // Parents are resolved before their children; entries with a missing parent are treated
+        // as roots.
+        while remaining > 0 {
+            let before = remaining;
+            for i in 0..entries.len() {
+                if tops[i].is_some() {
+                    continue;
+                }
+
+                let entry = &entries[i];
+                if let Some(top) = entry.top {
+                    tops[i] = Some(Some(top));
+                    remaining -= 1;
+                    continue;
+                }
+
+                let parent = entry
+                    .parent
+                    .as_ref()
+                    .and_then(|p| entries.iter().position(|e| &e.name == p));
+                let inherited = match parent {
+                    Some(j) => match tops[j] {
+                        Some(top) => top,
+                        None => continue,
+                    },
+                    None => None,
+                };
+
+                let younger = entries
+                    .iter()
+                    .filter(|e| e.parent == entry.parent && e.base.time() > entry.base.time())
+                    .map(|e| e.base)
+                    .min_by_key(|b| b.time());
+
+                tops[i] = Some(younger.or(inherited));
+                remaining -= 1;
+            }
+
+            if remaining == before {
+                // A cycle in the parent relation; leave the rest open-ended.
+                for top in tops.iter_mut().filter(|t| t.is_none()) {
+                    *top = Some(None);
+                }
+                break;
+            }
+        }
+
+        let units = entries
+            .into_iter()
+            .zip(tops)
+            .map(|(e, top)| Unit {
+                name: e.name,
+                rank: e.rank,
+                parent: e.parent,
+                base: e.base,
+                top: top.flatten(),
+                gssp: e.gssp,
+                color: e.color,
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            units,
+        }
+    }
+
+    pub(crate) fn from_data(name: &str, data: &[UnitData]) -> Self {
+        Self::build(name, data.iter().map(Entry::from).collect())
+    }
+
+    /// The current chart with the bases of some units replaced.  Units that have children take
+    /// the base of their oldest child.
+    fn patched(name: &str, patches: &[versions::Patch]) -> Self {
+        let mut entries: Vec<Entry> = ics::ICS_2022.iter().map(Entry::from).collect();
+
+        for (unit, base) in patches {
+            match base {
+                Some((base, sigma)) => {
+                    if let Some(e) = entries.iter_mut().find(|e| e.name == *unit) {
+                        e.base = UncertainGeotime::from_ma(*base, *sigma);
+                    }
+                }
+                None => entries.retain(|e| e.name != *unit),
+            }
+        }
+
+        for rank in [
+            Rank::Epoch,
+            Rank::Subperiod,
+            Rank::Period,
+            Rank::Era,
+            Rank::Eon,
+            Rank::Supereon,
+        ] {
+            for i in 0..entries.len() {
+                if entries[i].rank != rank {
+                    continue;
+                }
+                let oldest = entries
+                    .iter()
+                    .filter(|e| e.parent.as_ref() == Some(&entries[i].name))
+                    .map(|e| e.base)
+                    .min_by_key(|b| b.time());
+                if let Some(base) = oldest {
+                    entries[i].base = base;
+                }
+            }
+        }
+
+        Self::build(name, entries)
+    }
+
+    /// The International Chronostratigraphic Chart (v2022/10).
+    pub fn ics() -> &'static TimeScale {
+        static SCALE: OnceLock<TimeScale> = OnceLock::new();
+        SCALE.get_or_init(|| Self::from_data("ICS 2022/10", ics::ICS_2022))
+    }
+
+    /// The Geologic Time Scale 2012 (Gradstein et al., 2012).
+    pub fn gts2012() -> &'static TimeScale {
+        static SCALE: OnceLock<TimeScale> = OnceLock::new();
+        SCALE.get_or_init(|| Self::patched("GTS2012", versions::GTS_2012))
+    }
+
+    /// The Geologic Time Scale 2020 (Gradstein et al., 2020).
+    pub fn gts2020() -> &'static TimeScale {
+        static SCALE: OnceLock<TimeScale> = OnceLock::new();
+        SCALE.get_or_init(|| Self::patched("GTS2020", versions::GTS_2020))
+    }
+
+    /// Adds a scale to the registry, replacing any scale with the same name.
+    pub fn register(scale: TimeScale) -> Arc<TimeScale> {
+        let scale = Arc::new(scale);
+        let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+        match registry.scales.iter().position(|s| s.name == scale.name) {
+            Some(i) => registry.scales[i] = scale.clone(),
+            None => registry.scales.push(scale.clone()),
+        }
+        scale
+    }
+
+    /// A registered scale, looked up by name, ignoring case.  The bundled scales are registered as
+    /// "ICS 2022/10", "GTS2012" and "GTS2020", along with the planetary scales under their own
+    /// names, such as "Mars (Hartmann 2005)".
+    pub fn named(name: &str) -> Option<Arc<TimeScale>> {
+        let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+        registry
+            .scales
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    /// The names of the registered scales.
+    pub fn registered() -> Vec<String> {
+        let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+        registry.scales.iter().map(|s| s.name.clone()).collect()
+    }
+
+    /// The scale used by [`Geotime::geologic_units`], initially the current ICS chart.
+    pub fn default_scale() -> Arc<TimeScale> {
+        let registry = registry().read().unwrap_or_else(|e| e.into_inner());
+        registry.default.clone()
+    }
+
+    /// Makes the registered scale `name` the default.
+    ///
+    /// ```
+    /// use geotime::TimeScale;
+    ///
+    /// TimeScale::set_default("GTS2012").unwrap();
+    /// assert_eq!(TimeScale::default_scale().name(), "GTS2012");
+    /// assert!(TimeScale::set_default("GTS1066").is_err());
+    /// ```
+    pub fn set_default(name: &str) -> Result<()> {
+        let scale = Self::named(name)
+            .ok_or_else(|| Error::NotFound(format!("no time scale named {}", name)))?;
+        let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+        registry.default = scale;
+        Ok(())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// All of the units, oldest first.
+    pub fn units(&self) -> &[Unit] {
+        &self.units
+    }
+
+    /// Looks up a unit by name, ignoring case.
+    pub fn unit(&self, name: &str) -> Option<&Unit> {
+        self.units
+            .iter()
+            .find(|u| u.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The enclosing unit of `unit`.
+    pub fn parent(&self, unit: &Unit) -> Option<&Unit> {
+        unit.parent().and_then(|p| self.unit(p))
+    }
+
+    /// The units directly below `unit` in the hierarchy, oldest first.
+    pub fn children(&self, unit: &Unit) -> Vec<&Unit> {
+        self.units
+            .iter()
+            .filter(|u| u.parent() == Some(unit.name()))
+            .collect()
+    }
+
+    /// The units containing `time` at every rank, from the most to the least inclusive.
+    pub fn units_at(&self, time: Geotime) -> Vec<&Unit> {
+        let mut units: Vec<&Unit> = self.units.iter().filter(|u| u.contains(time)).collect();
+        units.sort_by_key(|u| u.rank);
+        units
+    }
+
+    /// The unit of the given rank containing `time`, if any.
+    pub fn unit_at(&self, time: Geotime, rank: Rank) -> Option<&Unit> {
+        self.units
+            .iter()
+            .find(|u| u.rank == rank && u.contains(time))
+    }
+
+    /// The most finely ranked unit containing `time`, and how far through that unit `time` falls,
+    /// from 0.0 at its base to 1.0 at its top.  Units that extend to the present have no
+    /// position.
+    pub fn position(&self, time: Geotime) -> Option<(&Unit, f64)> {
+        let unit = self
+            .units_at(time)
+            .into_iter()
+            .rev()
+            .find(|u| u.top.is_some())?;
+        let fraction = unit.fraction(time)?;
+        Some((unit, fraction))
+    }
+
+    /// The time at the given fraction of the way through the unit `name`.
+    pub fn at_position(&self, name: &str, fraction: f64) -> Result<Geotime> {
+        let unit = self
+            .unit(name)
+            .ok_or_else(|| Error::NotFound(format!("no unit named {} in {}", name, self.name)))?;
+        let top = unit
+            .top
+            .ok_or_else(|| Error::OutOfRange(format!("{} has no upper boundary", name)))?;
+        let (base, top) = (unit.base.time().millis(), top.time().millis());
+        let offset = ((top - base) as f64 * fraction).round() as i128;
+        Ok(Geotime::from(base + offset))
+    }
+
+    /// Converts a time in this scale to the corresponding time in another scale, by keeping its
+    /// relative position within the finest unit that the two scales have in common.  Times in
+    /// units that extend to the present keep their offset from the base of the unit.
+    ///
+    /// ```
+    /// use geotime::{Geotime, Rank, TimeScale};
+    ///
+    /// let (gts2012, ics) = (TimeScale::gts2012(), TimeScale::ics());
+    /// let t = Geotime::from_ma(540.0);
+    /// assert_eq!(gts2012.unit_at(t, Rank::Period).unwrap().name(), "Cambrian");
+    /// assert_eq!(ics.unit_at(t, Rank::Period).unwrap().name(), "Ediacaran");
+    ///
+    /// let remapped = gts2012.remap(t, ics).unwrap();
+    /// assert_eq!(ics.unit_at(remapped, Rank::Period).unwrap().name(), "Cambrian");
+    /// ```
+    pub fn remap(&self, time: Geotime, to: &TimeScale) -> Result<Geotime> {
+        let units = self.units_at(time);
+        let out_of_range = || {
+            Error::OutOfRange(format!(
+                "{:?} is not within a unit shared by {} and {}",
+                time, self.name, to.name
+            ))
+        };
+
+        for unit in units.into_iter().rev() {
+            let target = match to.unit(&unit.name) {
+                Some(target) => target,
+                None => continue,
+            };
+
+            return match (unit.fraction(time), target.top) {
+                (Some(fraction), Some(_)) => to.at_position(&target.name, fraction),
+                _ => {
+                    let offset = time.millis() - unit.base.time().millis();
+                    Ok(Geotime::from(target.base.time().millis() + offset))
+                }
+            };
+        }
+
+        Err(out_of_range())
+    }
+}
+
+struct Registry {
+    scales: Vec<Arc<TimeScale>>,
+    default: Arc<TimeScale>,
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let ics = Arc::new(TimeScale::ics().clone());
+        let mut scales = vec![
+            ics.clone(),
+            Arc::new(TimeScale::gts2012().clone()),
+            Arc::new(TimeScale::gts2020().clone()),
+        ];
+        scales.extend(TimeScale::planetary().into_iter().cloned().map(Arc::new));
+        RwLock::new(Registry {
+            scales,
+            default: ics,
+        })
+    })
+}
+
+impl Geotime {
+    /// The units of the default time scale that contain the timestamp, from the eon (or
+    /// supereon) down to the age.  See [`TimeScale::set_default`].
+    ///
+    /// ```
+    /// use geotime::Geotime;
+    ///
+    /// let units = Geotime::from_ma(147.0).geologic_units();
+    /// let names: Vec<&str> = units.iter().map(|u| u.name()).collect();
+    /// assert_eq!(
+    ///     names,
+    ///     vec!["Phanerozoic", "Mesozoic", "Jurassic", "Late Jurassic", "Tithonian"]
+    /// );
+    /// ```
+    pub fn geologic_units(&self) -> Vec<Unit> {
+        TimeScale::default_scale()
+            .units_at(*self)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(units: &[Unit]) -> Vec<String> {
+        units.iter().map(|u| u.name().to_string()).collect()
+    }
+
+    #[test]
+    fn parents_exist() {
+        let ics = TimeScale::ics();
+        for unit in ics.units() {
+            if let Some(parent) = unit.parent() {
+                let parent = ics.unit(parent).unwrap();
+                assert!(parent.rank() < unit.rank());
+                assert!(parent.base().time() <= unit.base().time());
+            }
+        }
+    }
+
+    #[test]
+    fn children_tile_their_parent() {
+        let ics = TimeScale::ics();
+        for unit in ics.units() {
+            let children = ics.children(unit);
+            if children.is_empty() {
+                continue;
+            }
+            assert_eq!(children[0].base(), unit.base(), "{}", unit.name());
+            assert_eq!(children[children.len() - 1].top(), unit.top());
+            for pair in children.windows(2) {
+                assert_eq!(pair[0].top(), Some(pair[1].base()));
+            }
+        }
+    }
+
+    #[test]
+    fn tops() {
+        let ics = TimeScale::ics();
+        assert_eq!(ics.unit("Holocene").unwrap().top(), None);
+        assert_eq!(
+            ics.unit("Cretaceous").unwrap().top(),
+            Some(UncertainGeotime::from_ma(66.0, 0.0))
+        );
+        assert_eq!(
+            ics.unit("precambrian").unwrap().top(),
+            Some(UncertainGeotime::from_ma(538.8, 0.2))
+        );
+    }
+
+    #[test]
+    fn units_at() {
+        let units = Geotime::from_ma(310.0).geologic_units();
+        assert_eq!(
+            names(&units),
+            vec![
+                "Phanerozoic",
+                "Paleozoic",
+                "Carboniferous",
+                "Pennsylvanian",
+                "Middle Pennsylvanian",
+                "Moscovian"
+            ]
+        );
+
+        let units = Geotime::from_ma(3000.0).geologic_units();
+        assert_eq!(names(&units), vec!["Precambrian", "Archean", "Mesoarchean"]);
+
+        let units = Geotime::now().geologic_units();
+        assert_eq!(units.last().unwrap().name(), "Meghalayan");
+    }
+
+    #[test]
+    fn boundaries_belong_to_the_younger_unit() {
+        let ics = TimeScale::ics();
+        let t = Geotime::from_ma(66.0);
+        assert_eq!(ics.unit_at(t, Rank::Period).unwrap().name(), "Paleogene");
+        let t = Geotime::from(t.millis() - 1);
+        assert_eq!(ics.unit_at(t, Rank::Period).unwrap().name(), "Cretaceous");
+    }
+
+    #[test]
+    fn before_the_earth() {
+        assert!(Geotime::from_ma(5000.0).geologic_units().is_empty());
+    }
+
+    mod versions {
+        use super::*;
+
+        #[test]
+        fn bundled_versions_are_registered() {
+            let names = TimeScale::registered();
+            for name in ["ICS 2022/10", "GTS2012", "GTS2020"] {
+                assert!(names.iter().any(|n| n == name));
+            }
+            assert_eq!(TimeScale::named("gts2020").unwrap().name(), "GTS2020");
+            assert!(TimeScale::named("GTS1066").is_none());
+        }
+
+        #[test]
+        fn base_of_the_cambrian() {
+            let base = |scale: &TimeScale| scale.unit("Cambrian").unwrap().base();
+            assert_eq!(
+                base(TimeScale::gts2012()),
+                UncertainGeotime::from_ma(541.0, 1.0)
+            );
+            assert_eq!(
+                base(TimeScale::gts2020()),
+                UncertainGeotime::from_ma(538.8, 0.6)
+            );
+            assert_eq!(
+                base(TimeScale::ics()),
+                UncertainGeotime::from_ma(538.8, 0.2)
+            );
+
+            let phanerozoic = TimeScale::gts2012().unit("Phanerozoic").unwrap();
+            assert_eq!(phanerozoic.base(), UncertainGeotime::from_ma(541.0, 1.0));
+            let precambrian = TimeScale::gts2012().unit("Precambrian").unwrap();
+            assert_eq!(precambrian.top(), Some(phanerozoic.base()));
+        }
+
+        #[test]
+        fn stage_bases() {
+            // One stage per period: name, GTS2012 base, GTS2020 base.
+            for (stage, gts2012, gts2020) in [
+                ("Chibanian", 0.781, 0.773),
+                ("Langhian", 15.97, 15.99),
+                ("Chattian", 28.1, 27.29),
+                ("Aptian", 126.3, 121.4),
+                ("Toarcian", 182.7, 184.2),
+                ("Ladinian", 241.5, 241.5),
+                ("Olenekian", 250.0, 249.9),
+                ("Kungurian", 279.3, 283.3),
+                ("Serpukhovian", 330.9, 330.3),
+                ("Emsian", 407.6, 410.5),
+                ("Pridoli", 423.0, 422.7),
+                ("Katian", 453.0, 452.8),
+                ("Wuliuan", 509.0, 506.5),
+                ("Cryogenian", 850.0, 720.0),
+            ] {
+                for (scale, ma) in [
+                    (TimeScale::gts2012(), gts2012),
+                    (TimeScale::gts2020(), gts2020),
+                ] {
+                    let base = scale.unit(stage).unwrap().base().to_ma();
+                    assert!(
+                        (base - ma).abs() < 1e-9,
+                        "{} {}: {}",
+                        scale.name(),
+                        stage,
+                        base
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn patches_name_units() {
+            for patches in [
+                crate::timescale::versions::GTS_2012,
+                crate::timescale::versions::GTS_2020,
+            ] {
+                for (name, _) in patches {
+                    assert!(ics::ICS_2022.iter().any(|u| u.0 == *name), "{}", name);
+                }
+            }
+        }
+
+        #[test]
+        fn removed_units() {
+            let gts2012 = TimeScale::gts2012();
+            assert!(gts2012.unit("Meghalayan").is_none());
+            assert_eq!(gts2012.unit("Holocene").unwrap().top(), None);
+            assert!(gts2012
+                .children(gts2012.unit("Holocene").unwrap())
+                .is_empty());
+        }
+
+        #[test]
+        fn versions_tile() {
+            for scale in [TimeScale::gts2012(), TimeScale::gts2020()] {
+                for unit in scale.units() {
+                    let children = scale.children(unit);
+                    for pair in children.windows(2) {
+                        assert!(pair[0].base().time() < pair[1].base().time());
+                        assert_eq!(pair[0].top(), Some(pair[1].base()));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn position() {
+            let ics = TimeScale::ics();
+            let (unit, fraction) = ics.position(Geotime::from_ma(69.05)).unwrap();
+            assert_eq!(unit.name(), "Maastrichtian");
+            assert!((fraction - 0.5).abs() < 1e-9);
+
+            let t = ics.at_position("Maastrichtian", 0.5).unwrap();
+            assert!((t.to_ma() - 69.05).abs() < 1e-9);
+            assert!(ics.at_position("Holocene", 0.5).is_err());
+            assert!(ics.at_position("Eemian", 0.5).is_err());
+            assert!(ics.position(Geotime::now()).is_none());
+        }
+
+        #[test]
+        fn remap() {
+            let (gts2012, ics) = (TimeScale::gts2012(), TimeScale::ics());
+
+            // Halfway through the Tithonian.
+            let t = Geotime::from_ma((152.1 + 145.0) / 2.0);
+            let remapped = gts2012.remap(t, ics).unwrap();
+            assert!((remapped.to_ma() - (149.2 + 145.0) / 2.0).abs() < 1e-9);
+
+            // The Meghalayan does not exist in GTS2012, so the Holocene is used.
+            let t = Geotime::from_ma(0.002);
+            assert_eq!(ics.remap(t, gts2012).unwrap(), t);
+
+            assert!(ics.remap(Geotime::from_ma(5000.0), gts2012).is_err());
+        }
+
+        #[test]
+        fn register() {
+            let scale = TimeScale::from_data("test scale", &ics::ICS_2022[0..2]);
+            TimeScale::register(scale.clone());
+            TimeScale::register(scale);
+            let count = TimeScale::registered()
+                .iter()
+                .filter(|n| *n == "test scale")
+                .count();
+            assert_eq!(count, 1);
+            assert!(TimeScale::set_default("GTS1066").is_err());
+        }
+    }
+}
@@ -0,0 +1,615 @@
+//! User-defined time scales, such as regional stages or archaeological periodizations, loaded
+//! from CSV, JSON or TOML.
+//!
+//! Each unit has a name, a rank, an optional parent and a base, and may give an uncertainty for
+//! the base, an explicit top and a hex color.  When the top is omitted it is taken from the base
+//! of the next younger unit with the same parent.  In JSON and TOML the units are listed under
+//! `units`, along with the `name` of the scale and an optional `age_unit`:
+//!
+//! ```toml
+//! name = "NALMA"
+//! age_unit = "Ma"
+//!
+//! [[units]]
+//! name = "Puercan"
+//! rank = "age"
+//! base = 66.0
+//! top = 63.3
+//! ```
+//!
+//! CSV input has a header row naming the columns `name`, `rank`, `parent`, `base`,
+//! `uncertainty`, `top` and `color`, of which only `name`, `rank` and `base` are required.
+use serde::Deserialize;
+use std::fmt;
+
+use super::{Color, Entry, Gssp, Rank, TimeScale, Unit};
+use crate::{Error, Geotime, Result, UncertainGeotime, MAX_YEARS};
+
+/// The unit in which the ages of a time scale definition are given.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+pub enum AgeUnit {
+    /// Millions of years before present.
+    #[default]
+    #[serde(rename = "Ma")]
+    Ma,
+    /// Thousands of years before present.
+    #[serde(rename = "ka")]
+    Ka,
+    /// Years before present (1950).
+    #[serde(rename = "BP")]
+    YearsBp,
+    /// Calendar years, with negative years before the Common Era (astronomical numbering).
+    #[serde(rename = "CE")]
+    YearCe,
+}
+
+impl AgeUnit {
+    /// Converts an age, which must be within `MAX_YEARS` of the present so that the spans of
+    /// units can be computed without overflow.
+    fn to_geotime(self, value: f64, sigma: f64) -> Result<UncertainGeotime> {
+        let age = match self {
+            Self::Ma => UncertainGeotime::try_from_ma(value, sigma),
+            Self::Ka => UncertainGeotime::try_from_years_bp(value * 1e3, sigma * 1e3),
+            Self::YearsBp => UncertainGeotime::try_from_years_bp(value, sigma),
+            Self::YearCe => UncertainGeotime::try_from_years_bp(1950.0 - value, sigma),
+        }?;
+        if age.to_years_bp().abs() >= MAX_YEARS || age.sigma_years() >= MAX_YEARS {
+            return Err(Error::OutOfRange(format!(
+                "age {} is more than {} years from the present",
+                value, MAX_YEARS
+            )));
+        }
+        Ok(age)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+struct UnitDefinition {
+    name: String,
+    rank: String,
+    #[serde(default)]
+    parent: Option<String>,
+    base: f64,
+    #[serde(default)]
+    uncertainty: f64,
+    #[serde(default)]
+    top: Option<f64>,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+struct Definition {
+    name: String,
+    #[serde(default)]
+    age_unit: AgeUnit,
+    units: Vec<UnitDefinition>,
+}
+
+impl Definition {
+    fn into_scale(self) -> Result<TimeScale> {
+        let age_unit = self.age_unit;
+
+        let entries = self
+            .units
+            .into_iter()
+            .map(|u| {
+                if !u.base.is_finite() || !u.uncertainty.is_finite() {
+                    return Err(Error::InvalidInput(format!(
+                        "{} has an invalid base",
+                        u.name
+                    )));
+                }
+                if u.top.is_some_and(|top| !top.is_finite()) {
+                    return Err(Error::InvalidInput(format!(
+                        "{} has an invalid top",
+                        u.name
+                    )));
+                }
+                let color = match u.color.as_deref() {
+                    Some(c) if !c.is_empty() => Some(c.parse::<Color>()?),
+                    _ => None,
+                };
+                Ok(Entry {
+                    rank: u.rank.parse()?,
+                    parent: u.parent.filter(|p| !p.is_empty()),
+                    base: age_unit.to_geotime(u.base, u.uncertainty)?,
+                    top: u.top.map(|top| age_unit.to_geotime(top, 0.0)).transpose()?,
+                    gssp: Gssp::Informal,
+                    color,
+                    name: u.name,
+                })
+            })
+            .collect::<Result<Vec<Entry>>>()?;
+
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        let duplicate = names
+            .iter()
+            .enumerate()
+            .find(|(i, n)| names[..*i].contains(n));
+        if let Some((_, name)) = duplicate {
+            return Err(Error::InvalidInput(
+                Issue::DuplicateName(name.to_string()).to_string(),
+            ));
+        }
+
+        let scale = TimeScale::build(&self.name, entries);
+        let issues = scale.validate();
+        if !issues.is_empty() {
+            let issues: Vec<String> = issues.iter().map(Issue::to_string).collect();
+            return Err(Error::InvalidInput(issues.join("; ")));
+        }
+        Ok(scale)
+    }
+}
+
+// Splits CSV input into records, handling quoted fields and doubled quotes.
+fn csv_records(input: &str) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            ('\r', false) => {}
+            (c, _) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(Error::InvalidInput("unterminated quote in CSV".to_string()));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records
+        .into_iter()
+        .map(|r| {
+            r.into_iter()
+                .map(|f| f.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter(|r| r.iter().any(|f| !f.is_empty()))
+        .collect())
+}
+
+fn parse_number(column: &str, value: &str) -> Result<f64> {
+    value
+        .parse()
+        .map_err(|_| Error::InvalidInput(format!("invalid {}: {}", column, value)))
+}
+
+impl TimeScale {
+    /// Loads a scale from CSV.  See the [module documentation](self) for the columns.
+    ///
+    /// ```
+    /// use geotime::{AgeUnit, Geotime, Rank, TimeScale};
+    ///
+    /// let csv = "name,rank,parent,base\n\
+    ///            Blancan,age,,4.9\n\
+    ///            Irvingtonian,age,,1.8\n\
+    ///            Rancholabrean,age,,0.24\n";
+    /// let nalma = TimeScale::from_csv("NALMA", csv, AgeUnit::Ma).unwrap();
+    /// let unit = nalma.unit_at(Geotime::from_ma(1.0), Rank::Age).unwrap();
+    /// assert_eq!(unit.name(), "Irvingtonian");
+    /// ```
+    pub fn from_csv(name: &str, input: &str, age_unit: AgeUnit) -> Result<TimeScale> {
+        let mut records = csv_records(input)?.into_iter();
+        let header: Vec<String> = records
+            .next()
+            .ok_or_else(|| Error::InvalidInput("CSV input is empty".to_string()))?
+            .iter()
+            .map(|h| h.to_ascii_lowercase())
+            .collect();
+
+        let column = |name: &str| header.iter().position(|h| h == name);
+        let required = |name: &str| {
+            column(name).ok_or_else(|| Error::InvalidInput(format!("missing column: {}", name)))
+        };
+        let (name_col, rank_col, base_col) =
+            (required("name")?, required("rank")?, required("base")?);
+        let (parent_col, sigma_col, top_col, color_col) = (
+            column("parent"),
+            column("uncertainty"),
+            column("top"),
+            column("color"),
+        );
+
+        let mut units = Vec::new();
+        for record in records {
+            let field = |i: Option<usize>| {
+                i.and_then(|i| record.get(i))
+                    .map(String::as_str)
+                    .filter(|f| !f.is_empty())
+            };
+
+            units.push(UnitDefinition {
+                name: field(Some(name_col)).unwrap_or_default().to_string(),
+                rank: field(Some(rank_col)).unwrap_or_default().to_string(),
+                parent: field(parent_col).map(str::to_string),
+                base: parse_number("base", field(Some(base_col)).unwrap_or_default())?,
+                uncertainty: field(sigma_col)
+                    .map(|v| parse_number("uncertainty", v))
+                    .transpose()?
+                    .unwrap_or(0.0),
+                top: field(top_col).map(|v| parse_number("top", v)).transpose()?,
+                color: field(color_col).map(str::to_string),
+            });
+        }
+
+        Definition {
+            name: name.to_string(),
+            age_unit,
+            units,
+        }
+        .into_scale()
+    }
+
+    /// Loads a scale from JSON.  See the [module documentation](self) for the format.
+    pub fn from_json(input: &str) -> Result<TimeScale> {
+        let definition: Definition =
+            serde_json::from_str(input).map_err(|e| Error::InvalidInput(e.to_string()))?;
+        definition.into_scale()
+    }
+
+    /// Loads a scale from TOML.  See the [module documentation](self) for the format.
+    pub fn from_toml(input: &str) -> Result<TimeScale> {
+        let definition: Definition =
+            toml::from_str(input).map_err(|e| Error::InvalidInput(e.to_string()))?;
+        definition.into_scale()
+    }
+
+    /// Problems with the structure of the scale: gaps or overlaps between neighbouring units,
+    /// units that end before they begin, units that extend beyond their parents, and references
+    /// to parents that do not exist.
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for unit in &self.units {
+            if unit.top.is_some_and(|top| top.time() <= unit.base.time()) {
+                issues.push(Issue::NonMonotone(unit.name.clone()));
+            }
+
+            let parent = match unit.parent() {
+                Some(name) => match self.unit(name) {
+                    Some(parent) => Some(parent),
+                    None => {
+                        issues.push(Issue::UnknownParent {
+                            unit: unit.name.clone(),
+                            parent: name.to_string(),
+                        });
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            if let Some(parent) = parent {
+                let before_base = unit.base.time() < parent.base.time();
+                let after_top = match (unit.top, parent.top) {
+                    (_, None) => false,
+                    (None, Some(_)) => true,
+                    (Some(top), Some(parent_top)) => top.time() > parent_top.time(),
+                };
+                if before_base || after_top || unit.rank <= parent.rank {
+                    issues.push(Issue::OutsideParent {
+                        unit: unit.name.clone(),
+                        parent: parent.name.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut parents: Vec<Option<&str>> = self.units.iter().map(|u| u.parent()).collect();
+        parents.dedup();
+        parents.sort();
+        parents.dedup();
+
+        for parent in parents {
+            let mut siblings: Vec<&Unit> =
+                self.units.iter().filter(|u| u.parent() == parent).collect();
+            siblings.sort_by_key(|u| u.base.time());
+
+            for pair in siblings.windows(2) {
+                let (older, younger) = (pair[0], pair[1]);
+                let top = match older.top {
+                    Some(top) => top.time(),
+                    None => Geotime::from(i128::MAX),
+                };
+                let (older, younger) = (older.name.clone(), younger.name.clone());
+                if top < pair[1].base.time() {
+                    issues.push(Issue::Gap { older, younger });
+                } else if top > pair[1].base.time() {
+                    issues.push(Issue::Overlap { older, younger });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// The units of rank `rank` in `other` that overlap the unit `name` of this scale, with the
+    /// fraction of the unit that each of them covers.  Units that extend to the present are
+    /// taken to end now.
+    ///
+    /// ```
+    /// use geotime::{AgeUnit, Rank, TimeScale};
+    ///
+    /// let csv = "name,rank,base,top\nIrvingtonian,age,1.8,0.24\n";
+    /// let nalma = TimeScale::from_csv("NALMA", csv, AgeUnit::Ma).unwrap();
+    /// let ages = nalma.correlate("Irvingtonian", TimeScale::ics(), Rank::Age).unwrap();
+    /// let names: Vec<&str> = ages.iter().map(|c| c.unit.name()).collect();
+    /// assert_eq!(names, vec!["Calabrian", "Chibanian"]);
+    /// ```
+    pub fn correlate<'a>(
+        &self,
+        name: &str,
+        other: &'a TimeScale,
+        rank: Rank,
+    ) -> Result<Vec<Correlation<'a>>> {
+        let unit = self
+            .unit(name)
+            .ok_or_else(|| Error::NotFound(format!("no unit named {} in {}", name, self.name)))?;
+
+        let now = Geotime::now();
+        let span = |u: &Unit| {
+            let base = u.base.time();
+            let top = u.top.map_or(now, |t| t.time()).max(base);
+            (base.millis(), top.millis())
+        };
+        let (base, top) = span(unit);
+        let duration = (top - base) as f64;
+
+        Ok(other
+            .units
+            .iter()
+            .filter(|u| u.rank == rank)
+            .filter_map(|u| {
+                let (b, t) = span(u);
+                let overlap = top.min(t) - base.max(b);
+                if overlap <= 0 {
+                    return None;
+                }
+                let overlap = if duration > 0.0 {
+                    overlap as f64 / duration
+                } else {
+                    1.0
+                };
+                Some(Correlation { unit: u, overlap })
+            })
+            .collect())
+    }
+}
+
+/// A unit of another scale that overlaps a given unit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Correlation<'a> {
+    pub unit: &'a Unit,
+    /// The fraction of the given unit that the other unit covers.
+    pub overlap: f64,
+}
+
+/// A structural problem with a time scale.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Issue {
+    DuplicateName(String),
+    UnknownParent {
+        unit: String,
+        parent: String,
+    },
+    /// The unit ends before it begins.
+    NonMonotone(String),
+    OutsideParent {
+        unit: String,
+        parent: String,
+    },
+    Gap {
+        older: String,
+        younger: String,
+    },
+    Overlap {
+        older: String,
+        younger: String,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateName(name) => write!(f, "more than one unit named {}", name),
+            Self::UnknownParent { unit, parent } => {
+                write!(f, "{} has an unknown parent {}", unit, parent)
+            }
+            Self::NonMonotone(name) => write!(f, "{} ends before it begins", name),
+            Self::OutsideParent { unit, parent } => {
+                write!(f, "{} does not fit within {}", unit, parent)
+            }
+            Self::Gap { older, younger } => write!(f, "gap between {} and {}", older, younger),
+            Self::Overlap { older, younger } => {
+                write!(f, "{} overlaps {}", older, younger)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NZ_CSV: &str = "\
+name,rank,parent,base,uncertainty,top,color
+Wanganui,epoch,,5.33,,,#FFFF99
+Opoitian,age,Wanganui,5.33,0.05,,
+Waipipian,age,Wanganui,3.7,0.05,,
+Mangapanian,age,Wanganui,3.0,,,
+\"Nukumaruan, sensu lato\",age,Wanganui,2.4,,,
+";
+
+    #[test]
+    fn csv() {
+        let scale = TimeScale::from_csv("NZ", NZ_CSV, AgeUnit::Ma).unwrap();
+        assert_eq!(scale.units().len(), 5);
+
+        let unit = scale.unit("Waipipian").unwrap();
+        assert_eq!(unit.base(), UncertainGeotime::from_ma(3.7, 0.05));
+        assert_eq!(unit.top(), Some(UncertainGeotime::from_ma(3.0, 0.0)));
+        assert_eq!(unit.gssp(), Gssp::Informal);
+
+        let epoch = scale.unit("Wanganui").unwrap();
+        assert_eq!(epoch.color().unwrap().to_string(), "#FFFF99");
+        assert!(scale.unit("Nukumaruan, sensu lato").is_some());
+    }
+
+    #[test]
+    fn csv_errors() {
+        assert!(TimeScale::from_csv("x", "", AgeUnit::Ma).is_err());
+        assert!(TimeScale::from_csv("x", "name,base\nA,1.0\n", AgeUnit::Ma).is_err());
+        assert!(TimeScale::from_csv("x", "name,rank,base\nA,age,one\n", AgeUnit::Ma).is_err());
+        assert!(TimeScale::from_csv("x", "name,rank,base\nA,tier,1\n", AgeUnit::Ma).is_err());
+        assert!(TimeScale::from_csv("x", "name,rank,base\n\"A,age,1\n", AgeUnit::Ma).is_err());
+        assert!(matches!(
+            TimeScale::from_csv("x", "name,rank,base\nA,age,1e300\n", AgeUnit::Ma),
+            Err(Error::OutOfRange(_))
+        ));
+        assert!(matches!(
+            TimeScale::from_csv("x", "name,rank,base\nA,age,1e7\n", AgeUnit::Ma),
+            Err(Error::OutOfRange(_))
+        ));
+        assert!(matches!(
+            TimeScale::from_csv("x", "name,rank,base,top\nA,age,1,NaN\n", AgeUnit::Ma),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(matches!(
+            TimeScale::from_csv("x", "name,rank,base,top\nA,age,1,-inf\n", AgeUnit::Ma),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(matches!(
+            TimeScale::from_csv("x", "name,rank,base,top\nA,age,1,-1e300\n", AgeUnit::Ma),
+            Err(Error::OutOfRange(_))
+        ));
+        assert!(matches!(
+            TimeScale::from_csv("x", "name,rank,base\nA,age,1e307\n", AgeUnit::Ka),
+            Err(Error::OutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn json() {
+        let json = r#"{
+            "name": "Aegean Bronze Age",
+            "age_unit": "CE",
+            "units": [
+                {"name": "Early Minoan", "rank": "period", "base": -3100, "uncertainty": 50},
+                {"name": "Middle Minoan", "rank": "period", "base": -2160},
+                {"name": "Late Minoan", "rank": "period", "base": -1600, "top": -1100}
+            ]
+        }"#;
+        let scale = TimeScale::from_json(json).unwrap();
+        let unit = scale.unit("Middle Minoan").unwrap();
+        assert!((unit.base().to_years_bp() - 4110.0).abs() < 1e-6);
+        assert!((unit.top().unwrap().to_years_bp() - 3550.0).abs() < 1e-6);
+        assert!((scale.unit("Early Minoan").unwrap().base().sigma_years() - 50.0).abs() < 1e-6);
+
+        assert!(TimeScale::from_json("{").is_err());
+    }
+
+    #[test]
+    fn toml() {
+        let toml = r#"
+            name = "Lisiecki"
+            age_unit = "ka"
+
+            [[units]]
+            name = "Glacial"
+            rank = "age"
+            base = 29
+            top = 14
+        "#;
+        let scale = TimeScale::from_toml(toml).unwrap();
+        let unit = scale.unit("Glacial").unwrap();
+        assert!((unit.base().to_years_bp() - 29000.0).abs() < 1e-6);
+        assert!(TimeScale::from_toml("name = ").is_err());
+    }
+
+    #[test]
+    fn validation() {
+        let csv = "name,rank,parent,base,top\n\
+                   A,age,,10,8\n\
+                   B,age,,7,5\n\
+                   C,age,,6,4\n\
+                   D,age,,3,3.5\n\
+                   E,age,F,2,1\n\
+                   G,period,,20,15\n\
+                   H,age,G,21,16\n";
+        let error = TimeScale::from_csv("x", csv, AgeUnit::Ma).unwrap_err();
+        let message = format!("{:?}", error);
+        for expected in [
+            "gap between A and B",
+            "B overlaps C",
+            "D ends before it begins",
+            "E has an unknown parent F",
+            "H does not fit within G",
+        ] {
+            assert!(message.contains(expected), "{}", expected);
+        }
+
+        let csv = "name,rank,base\nA,age,2\nA,age,1\n";
+        let error = TimeScale::from_csv("x", csv, AgeUnit::Ma).unwrap_err();
+        assert!(format!("{:?}", error).contains("more than one unit named A"));
+    }
+
+    #[test]
+    fn bundled_scales_are_valid() {
+        for scale in [TimeScale::ics(), TimeScale::gts2012(), TimeScale::gts2020()] {
+            assert_eq!(scale.validate(), vec![]);
+        }
+    }
+
+    #[test]
+    fn correlation() {
+        let scale = TimeScale::from_csv("NZ", NZ_CSV, AgeUnit::Ma).unwrap();
+        let ages = scale
+            .correlate("Waipipian", TimeScale::ics(), Rank::Age)
+            .unwrap();
+        let names: Vec<&str> = ages.iter().map(|c| c.unit.name()).collect();
+        assert_eq!(names, vec!["Zanclean", "Piacenzian"]);
+        assert!((ages[0].overlap - 1.0 / 7.0).abs() < 1e-6);
+        assert!((ages[1].overlap - 6.0 / 7.0).abs() < 1e-6);
+
+        let ages = scale
+            .correlate("Opoitian", TimeScale::ics(), Rank::Age)
+            .unwrap();
+        let total: f64 = ages.iter().map(|c| c.overlap).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        assert!(scale
+            .correlate("Eemian", TimeScale::ics(), Rank::Age)
+            .is_err());
+    }
+
+    #[test]
+    fn registration() {
+        let scale = TimeScale::from_csv("NZ stages", NZ_CSV, AgeUnit::Ma).unwrap();
+        TimeScale::register(scale);
+        let scale = TimeScale::named("nz stages").unwrap();
+        assert!(scale.unit("Mangapanian").is_some());
+    }
+}
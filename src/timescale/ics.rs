@@ -0,0 +1,207 @@
+//! The International Chronostratigraphic Chart, v2022/10, with the official CGMW colors.  Base
+//! ages are in Ma with their published uncertainties; approximate ("~") ages have no
+//! uncertainty.
+use super::{Gssp, Rank, UnitData};
+
+use Gssp::{Gssa, Informal, Pending, Ratified};
+use Rank::{Age, Eon, Epoch, Era, Period, Subperiod, Supereon};
+
+#[rustfmt::skip]
+pub(crate) const ICS_2022: &[UnitData] = &[
+    ("Precambrian", Supereon, None, 4567.0, 0.0, Informal, 0xF74370),
+    ("Phanerozoic", Eon, None, 538.8, 0.2, Ratified, 0x9AD9DD),
+
+    ("Hadean", Eon, Some("Precambrian"), 4567.0, 0.0, Informal, 0xAE027E),
+    ("Archean", Eon, Some("Precambrian"), 4031.0, 3.0, Gssa, 0xF0047F),
+    ("Proterozoic", Eon, Some("Precambrian"), 2500.0, 0.0, Gssa, 0xF73563),
+
+    ("Eoarchean", Era, Some("Archean"), 4031.0, 3.0, Gssa, 0xDA037F),
+    ("Paleoarchean", Era, Some("Archean"), 3600.0, 0.0, Gssa, 0xF4449F),
+    ("Mesoarchean", Era, Some("Archean"), 3200.0, 0.0, Gssa, 0xF768A9),
+    ("Neoarchean", Era, Some("Archean"), 2800.0, 0.0, Gssa, 0xF99BC1),
+
+    ("Paleoproterozoic", Era, Some("Proterozoic"), 2500.0, 0.0, Gssa, 0xF74370),
+    ("Mesoproterozoic", Era, Some("Proterozoic"), 1600.0, 0.0, Gssa, 0xFDB462),
+    ("Neoproterozoic", Era, Some("Proterozoic"), 1000.0, 0.0, Gssa, 0xFEB342),
+
+    ("Siderian", Period, Some("Paleoproterozoic"), 2500.0, 0.0, Gssa, 0xF74F7C),
+    ("Rhyacian", Period, Some("Paleoproterozoic"), 2300.0, 0.0, Gssa, 0xF75B89),
+    ("Orosirian", Period, Some("Paleoproterozoic"), 2050.0, 0.0, Gssa, 0xF76898),
+    ("Statherian", Period, Some("Paleoproterozoic"), 1800.0, 0.0, Gssa, 0xF875A7),
+    ("Calymmian", Period, Some("Mesoproterozoic"), 1600.0, 0.0, Gssa, 0xFDC07A),
+    ("Ectasian", Period, Some("Mesoproterozoic"), 1400.0, 0.0, Gssa, 0xFDCC8A),
+    ("Stenian", Period, Some("Mesoproterozoic"), 1200.0, 0.0, Gssa, 0xFED99A),
+    ("Tonian", Period, Some("Neoproterozoic"), 1000.0, 0.0, Gssa, 0xFEBF4E),
+    ("Cryogenian", Period, Some("Neoproterozoic"), 720.0, 0.0, Pending, 0xFECC5C),
+    ("Ediacaran", Period, Some("Neoproterozoic"), 635.0, 0.0, Ratified, 0xFED96A),
+
+    ("Paleozoic", Era, Some("Phanerozoic"), 538.8, 0.2, Ratified, 0x99C08D),
+    ("Mesozoic", Era, Some("Phanerozoic"), 251.902, 0.024, Ratified, 0x67C5CA),
+    ("Cenozoic", Era, Some("Phanerozoic"), 66.0, 0.0, Ratified, 0xF2F91D),
+
+    ("Cambrian", Period, Some("Paleozoic"), 538.8, 0.2, Ratified, 0x7FA056),
+    ("Ordovician", Period, Some("Paleozoic"), 485.4, 1.9, Ratified, 0x009270),
+    ("Silurian", Period, Some("Paleozoic"), 443.8, 1.5, Ratified, 0xB3E1B6),
+    ("Devonian", Period, Some("Paleozoic"), 419.2, 3.2, Ratified, 0xCB8C37),
+    ("Carboniferous", Period, Some("Paleozoic"), 358.9, 0.4, Ratified, 0x67A599),
+    ("Permian", Period, Some("Paleozoic"), 298.9, 0.15, Ratified, 0xF04028),
+    ("Triassic", Period, Some("Mesozoic"), 251.902, 0.024, Ratified, 0x812B92),
+    ("Jurassic", Period, Some("Mesozoic"), 201.4, 0.2, Ratified, 0x34B2C9),
+    ("Cretaceous", Period, Some("Mesozoic"), 145.0, 0.0, Pending, 0x7FC64E),
+    ("Paleogene", Period, Some("Cenozoic"), 66.0, 0.0, Ratified, 0xFD9A52),
+    ("Neogene", Period, Some("Cenozoic"), 23.03, 0.0, Ratified, 0xFFE619),
+    ("Quaternary", Period, Some("Cenozoic"), 2.58, 0.0, Ratified, 0xF9F97F),
+
+    ("Mississippian", Subperiod, Some("Carboniferous"), 358.9, 0.4, Ratified, 0x678F66),
+    ("Pennsylvanian", Subperiod, Some("Carboniferous"), 323.2, 0.4, Ratified, 0x99C2B5),
+
+    ("Terreneuvian", Epoch, Some("Cambrian"), 538.8, 0.2, Ratified, 0x8CB06C),
+    ("Series 2", Epoch, Some("Cambrian"), 521.0, 0.0, Pending, 0x99C078),
+    ("Miaolingian", Epoch, Some("Cambrian"), 506.5, 0.0, Ratified, 0xA6CF86),
+    ("Furongian", Epoch, Some("Cambrian"), 497.0, 0.0, Ratified, 0xB3E095),
+    ("Early Ordovician", Epoch, Some("Ordovician"), 485.4, 1.9, Ratified, 0x1A9D6F),
+    ("Middle Ordovician", Epoch, Some("Ordovician"), 470.0, 1.4, Ratified, 0x4DB47E),
+    ("Late Ordovician", Epoch, Some("Ordovician"), 458.4, 0.9, Ratified, 0x7FCA93),
+    ("Llandovery", Epoch, Some("Silurian"), 443.8, 1.5, Ratified, 0x99D7B3),
+    ("Wenlock", Epoch, Some("Silurian"), 433.4, 0.8, Ratified, 0xB3E1C2),
+    ("Ludlow", Epoch, Some("Silurian"), 427.4, 0.5, Ratified, 0xBFE6CF),
+    ("Pridoli", Epoch, Some("Silurian"), 423.0, 2.3, Ratified, 0xE6F5E1),
+    ("Early Devonian", Epoch, Some("Devonian"), 419.2, 3.2, Ratified, 0xE5AC4D),
+    ("Middle Devonian", Epoch, Some("Devonian"), 393.3, 1.2, Ratified, 0xF1C868),
+    ("Late Devonian", Epoch, Some("Devonian"), 382.7, 1.6, Ratified, 0xF1E19D),
+    ("Early Mississippian", Epoch, Some("Mississippian"), 358.9, 0.4, Ratified, 0x80AB6C),
+    ("Middle Mississippian", Epoch, Some("Mississippian"), 346.7, 0.4, Ratified, 0x99B46C),
+    ("Late Mississippian", Epoch, Some("Mississippian"), 330.9, 0.2, Pending, 0xB3BE6C),
+    ("Early Pennsylvanian", Epoch, Some("Pennsylvanian"), 323.2, 0.4, Ratified, 0x8CBEB4),
+    ("Middle Pennsylvanian", Epoch, Some("Pennsylvanian"), 315.2, 0.2, Pending, 0xA6C7B7),
+    ("Late Pennsylvanian", Epoch, Some("Pennsylvanian"), 307.0, 0.1, Pending, 0xBFD0BA),
+    ("Cisuralian", Epoch, Some("Permian"), 298.9, 0.15, Ratified, 0xEF5845),
+    ("Guadalupian", Epoch, Some("Permian"), 273.01, 0.14, Ratified, 0xFB745C),
+    ("Lopingian", Epoch, Some("Permian"), 259.51, 0.21, Ratified, 0xFBA794),
+    ("Early Triassic", Epoch, Some("Triassic"), 251.902, 0.024, Ratified, 0x983999),
+    ("Middle Triassic", Epoch, Some("Triassic"), 247.2, 0.0, Pending, 0xB168B1),
+    ("Late Triassic", Epoch, Some("Triassic"), 237.0, 0.0, Ratified, 0xBD8CC3),
+    ("Early Jurassic", Epoch, Some("Jurassic"), 201.4, 0.2, Ratified, 0x42AED0),
+    ("Middle Jurassic", Epoch, Some("Jurassic"), 174.7, 0.8, Ratified, 0x80CFD8),
+    ("Late Jurassic", Epoch, Some("Jurassic"), 161.5, 1.0, Pending, 0xB3E3EE),
+    ("Early Cretaceous", Epoch, Some("Cretaceous"), 145.0, 0.0, Pending, 0x8CCD57),
+    ("Late Cretaceous", Epoch, Some("Cretaceous"), 100.5, 0.0, Ratified, 0xA6D84A),
+    ("Paleocene", Epoch, Some("Paleogene"), 66.0, 0.0, Ratified, 0xFDA75F),
+    ("Eocene", Epoch, Some("Paleogene"), 56.0, 0.0, Ratified, 0xFDB46C),
+    ("Oligocene", Epoch, Some("Paleogene"), 33.9, 0.0, Ratified, 0xFEC07A),
+    ("Miocene", Epoch, Some("Neogene"), 23.03, 0.0, Ratified, 0xFFFF00),
+    ("Pliocene", Epoch, Some("Neogene"), 5.333, 0.0, Ratified, 0xFFFF99),
+    ("Pleistocene", Epoch, Some("Quaternary"), 2.58, 0.0, Ratified, 0xFFF2AE),
+    ("Holocene", Epoch, Some("Quaternary"), 0.0117, 0.0, Ratified, 0xFEF2E0),
+
+    ("Fortunian", Age, Some("Terreneuvian"), 538.8, 0.2, Ratified, 0x99B575),
+    ("Stage 2", Age, Some("Terreneuvian"), 529.0, 0.0, Pending, 0xA6BA80),
+    ("Stage 3", Age, Some("Series 2"), 521.0, 0.0, Pending, 0xA6C583),
+    ("Stage 4", Age, Some("Series 2"), 514.5, 0.0, Pending, 0xB3CA8E),
+    ("Wuliuan", Age, Some("Miaolingian"), 506.5, 0.0, Ratified, 0xB3D492),
+    ("Drumian", Age, Some("Miaolingian"), 504.5, 0.0, Ratified, 0xBFD99D),
+    ("Guzhangian", Age, Some("Miaolingian"), 500.5, 0.0, Ratified, 0xCCDFAA),
+    ("Paibian", Age, Some("Furongian"), 497.0, 0.0, Ratified, 0xCCEBAE),
+    ("Jiangshanian", Age, Some("Furongian"), 494.2, 0.0, Ratified, 0xD9F0BB),
+    ("Stage 10", Age, Some("Furongian"), 491.0, 0.0, Pending, 0xE6F5C9),
+
+    ("Tremadocian", Age, Some("Early Ordovician"), 485.4, 1.9, Ratified, 0x33A97E),
+    ("Floian", Age, Some("Early Ordovician"), 477.7, 1.4, Ratified, 0x41B087),
+    ("Dapingian", Age, Some("Middle Ordovician"), 470.0, 1.4, Ratified, 0x66C092),
+    ("Darriwilian", Age, Some("Middle Ordovician"), 467.3, 1.1, Ratified, 0x74C69C),
+    ("Sandbian", Age, Some("Late Ordovician"), 458.4, 0.9, Ratified, 0x8CD094),
+    ("Katian", Age, Some("Late Ordovician"), 453.0, 0.7, Ratified, 0x99D69F),
+    ("Hirnantian", Age, Some("Late Ordovician"), 445.2, 1.4, Ratified, 0xA6DBAB),
+
+    ("Rhuddanian", Age, Some("Llandovery"), 443.8, 1.5, Ratified, 0xA6DCB5),
+    ("Aeronian", Age, Some("Llandovery"), 440.8, 1.2, Ratified, 0xB3E1C2),
+    ("Telychian", Age, Some("Llandovery"), 438.5, 1.1, Ratified, 0xBFE6CF),
+    ("Sheinwoodian", Age, Some("Wenlock"), 433.4, 0.8, Ratified, 0xBFE6C3),
+    ("Homerian", Age, Some("Wenlock"), 430.5, 0.7, Ratified, 0xCCEBD1),
+    ("Gorstian", Age, Some("Ludlow"), 427.4, 0.5, Ratified, 0xCCECDD),
+    ("Ludfordian", Age, Some("Ludlow"), 425.6, 0.9, Ratified, 0xD9F0DF),
+
+    ("Lochkovian", Age, Some("Early Devonian"), 419.2, 3.2, Ratified, 0xE5B75A),
+    ("Pragian", Age, Some("Early Devonian"), 410.8, 2.8, Ratified, 0xE5C468),
+    ("Emsian", Age, Some("Early Devonian"), 407.6, 2.6, Ratified, 0xE5D075),
+    ("Eifelian", Age, Some("Middle Devonian"), 393.3, 1.2, Ratified, 0xF1D576),
+    ("Givetian", Age, Some("Middle Devonian"), 387.7, 0.8, Ratified, 0xF1E185),
+    ("Frasnian", Age, Some("Late Devonian"), 382.7, 1.6, Ratified, 0xF2EDAD),
+    ("Famennian", Age, Some("Late Devonian"), 372.2, 1.6, Ratified, 0xF2EDC5),
+
+    ("Tournaisian", Age, Some("Early Mississippian"), 358.9, 0.4, Ratified, 0x8CB06C),
+    ("Visean", Age, Some("Middle Mississippian"), 346.7, 0.4, Ratified, 0xA6B96C),
+    ("Serpukhovian", Age, Some("Late Mississippian"), 330.9, 0.2, Pending, 0xBFC26B),
+    ("Bashkirian", Age, Some("Early Pennsylvanian"), 323.2, 0.4, Ratified, 0x99C2B5),
+    ("Moscovian", Age, Some("Middle Pennsylvanian"), 315.2, 0.2, Pending, 0xB3CBB9),
+    ("Kasimovian", Age, Some("Late Pennsylvanian"), 307.0, 0.1, Pending, 0xBFD0C5),
+    ("Gzhelian", Age, Some("Late Pennsylvanian"), 303.7, 0.1, Pending, 0xCCD4C7),
+
+    ("Asselian", Age, Some("Cisuralian"), 298.9, 0.15, Ratified, 0xE36350),
+    ("Sakmarian", Age, Some("Cisuralian"), 293.52, 0.17, Pending, 0xE36F5C),
+    ("Artinskian", Age, Some("Cisuralian"), 290.1, 0.26, Pending, 0xE37B68),
+    ("Kungurian", Age, Some("Cisuralian"), 283.5, 0.6, Pending, 0xE38776),
+    ("Roadian", Age, Some("Guadalupian"), 273.01, 0.14, Ratified, 0xFB8069),
+    ("Wordian", Age, Some("Guadalupian"), 266.9, 0.4, Ratified, 0xFB8D76),
+    ("Capitanian", Age, Some("Guadalupian"), 264.28, 0.16, Ratified, 0xFB9A85),
+    ("Wuchiapingian", Age, Some("Lopingian"), 259.51, 0.21, Ratified, 0xFCB4A2),
+    ("Changhsingian", Age, Some("Lopingian"), 254.14, 0.07, Ratified, 0xFCC0B2),
+
+    ("Induan", Age, Some("Early Triassic"), 251.902, 0.024, Ratified, 0xA4469F),
+    ("Olenekian", Age, Some("Early Triassic"), 251.2, 0.0, Pending, 0xB051A5),
+    ("Anisian", Age, Some("Middle Triassic"), 247.2, 0.0, Pending, 0xBC75B7),
+    ("Ladinian", Age, Some("Middle Triassic"), 242.0, 0.0, Ratified, 0xC983BF),
+    ("Carnian", Age, Some("Late Triassic"), 237.0, 0.0, Ratified, 0xC99BCB),
+    ("Norian", Age, Some("Late Triassic"), 227.0, 0.0, Pending, 0xD6AAD3),
+    ("Rhaetian", Age, Some("Late Triassic"), 208.5, 0.0, Pending, 0xE3B9DB),
+
+    ("Hettangian", Age, Some("Early Jurassic"), 201.4, 0.2, Ratified, 0x4EB3D3),
+    ("Sinemurian", Age, Some("Early Jurassic"), 199.5, 0.3, Ratified, 0x67BCD8),
+    ("Pliensbachian", Age, Some("Early Jurassic"), 192.9, 0.3, Ratified, 0x80C5DD),
+    ("Toarcian", Age, Some("Early Jurassic"), 184.2, 0.3, Ratified, 0x99CEE3),
+    ("Aalenian", Age, Some("Middle Jurassic"), 174.7, 0.8, Ratified, 0x9AD9DD),
+    ("Bajocian", Age, Some("Middle Jurassic"), 170.9, 0.8, Ratified, 0xA6DDE0),
+    ("Bathonian", Age, Some("Middle Jurassic"), 168.2, 1.2, Ratified, 0xB3E2E3),
+    ("Callovian", Age, Some("Middle Jurassic"), 165.3, 1.1, Pending, 0xBFE7E5),
+    ("Oxfordian", Age, Some("Late Jurassic"), 161.5, 1.0, Pending, 0xBFE7F1),
+    ("Kimmeridgian", Age, Some("Late Jurassic"), 154.8, 0.8, Ratified, 0xCCECF4),
+    ("Tithonian", Age, Some("Late Jurassic"), 149.2, 0.7, Pending, 0xD9F1F7),
+
+    ("Berriasian", Age, Some("Early Cretaceous"), 145.0, 0.0, Pending, 0x8CCD60),
+    ("Valanginian", Age, Some("Early Cretaceous"), 139.8, 0.0, Pending, 0x99D36A),
+    ("Hauterivian", Age, Some("Early Cretaceous"), 132.6, 0.0, Pending, 0xA6D975),
+    ("Barremian", Age, Some("Early Cretaceous"), 125.77, 0.0, Pending, 0xB3DF7F),
+    ("Aptian", Age, Some("Early Cretaceous"), 121.4, 0.0, Pending, 0xBFE48A),
+    ("Albian", Age, Some("Early Cretaceous"), 113.0, 0.0, Ratified, 0xCCEA97),
+    ("Cenomanian", Age, Some("Late Cretaceous"), 100.5, 0.0, Ratified, 0xB3DE53),
+    ("Turonian", Age, Some("Late Cretaceous"), 93.9, 0.0, Ratified, 0xBFE35D),
+    ("Coniacian", Age, Some("Late Cretaceous"), 89.8, 0.3, Ratified, 0xCCE968),
+    ("Santonian", Age, Some("Late Cretaceous"), 86.3, 0.5, Ratified, 0xD9EF74),
+    ("Campanian", Age, Some("Late Cretaceous"), 83.6, 0.2, Pending, 0xE6F47F),
+    ("Maastrichtian", Age, Some("Late Cretaceous"), 72.1, 0.2, Ratified, 0xF2FA8C),
+
+    ("Danian", Age, Some("Paleocene"), 66.0, 0.0, Ratified, 0xFDB462),
+    ("Selandian", Age, Some("Paleocene"), 61.6, 0.0, Ratified, 0xFEBF65),
+    ("Thanetian", Age, Some("Paleocene"), 59.2, 0.0, Ratified, 0xFDBF6F),
+    ("Ypresian", Age, Some("Eocene"), 56.0, 0.0, Ratified, 0xFCA773),
+    ("Lutetian", Age, Some("Eocene"), 47.8, 0.0, Ratified, 0xFCB482),
+    ("Bartonian", Age, Some("Eocene"), 41.2, 0.0, Pending, 0xFDC091),
+    ("Priabonian", Age, Some("Eocene"), 37.71, 0.0, Ratified, 0xFDCDA1),
+    ("Rupelian", Age, Some("Oligocene"), 33.9, 0.0, Ratified, 0xFED99A),
+    ("Chattian", Age, Some("Oligocene"), 27.82, 0.0, Pending, 0xFEE6AA),
+    ("Aquitanian", Age, Some("Miocene"), 23.03, 0.0, Ratified, 0xFFFF33),
+    ("Burdigalian", Age, Some("Miocene"), 20.44, 0.0, Pending, 0xFFFF41),
+    ("Langhian", Age, Some("Miocene"), 15.98, 0.0, Pending, 0xFFFF4D),
+    ("Serravallian", Age, Some("Miocene"), 13.82, 0.0, Ratified, 0xFFFF59),
+    ("Tortonian", Age, Some("Miocene"), 11.63, 0.0, Ratified, 0xFFFF66),
+    ("Messinian", Age, Some("Miocene"), 7.246, 0.0, Ratified, 0xFFFF73),
+    ("Zanclean", Age, Some("Pliocene"), 5.333, 0.0, Ratified, 0xFFFFB3),
+    ("Piacenzian", Age, Some("Pliocene"), 3.6, 0.0, Ratified, 0xFFFFBF),
+    ("Gelasian", Age, Some("Pleistocene"), 2.58, 0.0, Ratified, 0xFFEDB3),
+    ("Calabrian", Age, Some("Pleistocene"), 1.8, 0.0, Ratified, 0xFFF2BA),
+    ("Chibanian", Age, Some("Pleistocene"), 0.774, 0.0, Ratified, 0xFFF2C7),
+    ("Late Pleistocene", Age, Some("Pleistocene"), 0.129, 0.0, Pending, 0xFFF2D3),
+    ("Greenlandian", Age, Some("Holocene"), 0.0117, 0.0, Ratified, 0xFEECDB),
+    ("Northgrippian", Age, Some("Holocene"), 0.0082, 0.0, Ratified, 0xFDECE4),
+    ("Meghalayan", Age, Some("Holocene"), 0.0042, 0.0, Ratified, 0xFDEDEC),
+];
@@ -0,0 +1,238 @@
+//! Stratigraphic systems of the Moon, Mars and Mercury.  Their boundaries are dated by crater
+//! counts calibrated against returned samples, so the ages depend on the crater-chronology model,
+//! and each model gives a separate scale.  Ages are in Ma and are approximate; only the lunar
+//! basin ages of Stöffler and Ryder (2001) carry uncertainties.  The colors are indicative.
+use std::sync::OnceLock;
+
+use super::{Gssp, Rank, TimeScale, UnitData};
+
+use Gssp::Informal;
+use Rank::{Epoch, Period};
+
+/// A crater-chronology model of the lunar systems.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LunarChronology {
+    /// Wilhelms (1987), The Geologic History of the Moon.
+    Wilhelms1987,
+    /// Stöffler and Ryder (2001), with the Nectaris and Imbrium basins at 3.92 and 3.85 Ga.
+    StofflerRyder2001,
+}
+
+/// A crater-chronology model of the Martian systems.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MartianChronology {
+    /// Hartmann (2005), the Hartmann production function.
+    Hartmann2005,
+    /// Hartmann and Neukum (2001), the Neukum production function with the Ivanov (2001) scaling.
+    Neukum2001,
+}
+
+/// A crater-chronology model of the Mercurian systems.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MercurianChronology {
+    /// Spudis and Guest (1988).
+    SpudisGuest1988,
+    /// Banks et al. (2017), with the younger Mansurian and Kuiperian bases.
+    Banks2017,
+}
+
+#[rustfmt::skip]
+const MOON_WILHELMS_1987: &[UnitData] = &[
+    ("pre-Nectarian", Period, None, 4533.0, 0.0, Informal, 0x9F8B7A),
+    ("Nectarian", Period, None, 3920.0, 0.0, Informal, 0xB59C86),
+    ("Imbrian", Period, None, 3850.0, 0.0, Informal, 0xC9B195),
+    ("Early Imbrian", Epoch, Some("Imbrian"), 3850.0, 0.0, Informal, 0xC4AA8E),
+    ("Late Imbrian", Epoch, Some("Imbrian"), 3800.0, 0.0, Informal, 0xD1BCA1),
+    ("Eratosthenian", Period, None, 3200.0, 0.0, Informal, 0xDDCBB0),
+    ("Copernican", Period, None, 1100.0, 0.0, Informal, 0xEEE4CF),
+];
+
+#[rustfmt::skip]
+const MOON_STOFFLER_RYDER_2001: &[UnitData] = &[
+    ("pre-Nectarian", Period, None, 4533.0, 0.0, Informal, 0x9F8B7A),
+    ("Nectarian", Period, None, 3920.0, 30.0, Informal, 0xB59C86),
+    ("Imbrian", Period, None, 3850.0, 20.0, Informal, 0xC9B195),
+    ("Early Imbrian", Epoch, Some("Imbrian"), 3850.0, 20.0, Informal, 0xC4AA8E),
+    ("Late Imbrian", Epoch, Some("Imbrian"), 3800.0, 0.0, Informal, 0xD1BCA1),
+    ("Eratosthenian", Period, None, 3150.0, 0.0, Informal, 0xDDCBB0),
+    ("Copernican", Period, None, 800.0, 15.0, Informal, 0xEEE4CF),
+];
+
+#[rustfmt::skip]
+const MARS_HARTMANN_2005: &[UnitData] = &[
+    ("pre-Noachian", Period, None, 4500.0, 0.0, Informal, 0x8C6A5C),
+    ("Noachian", Period, None, 4100.0, 0.0, Informal, 0xA97C62),
+    ("Early Noachian", Epoch, Some("Noachian"), 4100.0, 0.0, Informal, 0xA3765D),
+    ("Middle Noachian", Epoch, Some("Noachian"), 3950.0, 0.0, Informal, 0xAE8266),
+    ("Late Noachian", Epoch, Some("Noachian"), 3850.0, 0.0, Informal, 0xB98E70),
+    ("Hesperian", Period, None, 3700.0, 0.0, Informal, 0xC9A27E),
+    ("Early Hesperian", Epoch, Some("Hesperian"), 3700.0, 0.0, Informal, 0xC59D79),
+    ("Late Hesperian", Epoch, Some("Hesperian"), 3600.0, 0.0, Informal, 0xD2AE8A),
+    ("Amazonian", Period, None, 3370.0, 0.0, Informal, 0xE3C9A8),
+    ("Early Amazonian", Epoch, Some("Amazonian"), 3370.0, 0.0, Informal, 0xDFC4A2),
+    ("Middle Amazonian", Epoch, Some("Amazonian"), 1230.0, 0.0, Informal, 0xE8D2B4),
+    ("Late Amazonian", Epoch, Some("Amazonian"), 330.0, 0.0, Informal, 0xF1E2CB),
+];
+
+#[rustfmt::skip]
+const MARS_NEUKUM_2001: &[UnitData] = &[
+    ("pre-Noachian", Period, None, 4500.0, 0.0, Informal, 0x8C6A5C),
+    ("Noachian", Period, None, 4100.0, 0.0, Informal, 0xA97C62),
+    ("Early Noachian", Epoch, Some("Noachian"), 4100.0, 0.0, Informal, 0xA3765D),
+    ("Middle Noachian", Epoch, Some("Noachian"), 3970.0, 0.0, Informal, 0xAE8266),
+    ("Late Noachian", Epoch, Some("Noachian"), 3860.0, 0.0, Informal, 0xB98E70),
+    ("Hesperian", Period, None, 3740.0, 0.0, Informal, 0xC9A27E),
+    ("Early Hesperian", Epoch, Some("Hesperian"), 3740.0, 0.0, Informal, 0xC59D79),
+    ("Late Hesperian", Epoch, Some("Hesperian"), 3600.0, 0.0, Informal, 0xD2AE8A),
+    ("Amazonian", Period, None, 3310.0, 0.0, Informal, 0xE3C9A8),
+    ("Early Amazonian", Epoch, Some("Amazonian"), 3310.0, 0.0, Informal, 0xDFC4A2),
+    ("Middle Amazonian", Epoch, Some("Amazonian"), 1600.0, 0.0, Informal, 0xE8D2B4),
+    ("Late Amazonian", Epoch, Some("Amazonian"), 600.0, 0.0, Informal, 0xF1E2CB),
+];
+
+#[rustfmt::skip]
+const MERCURY_SPUDIS_GUEST_1988: &[UnitData] = &[
+    ("pre-Tolstojan", Period, None, 4500.0, 0.0, Informal, 0x7F7F8C),
+    ("Tolstojan", Period, None, 3970.0, 0.0, Informal, 0x9494A3),
+    ("Calorian", Period, None, 3770.0, 0.0, Informal, 0xAAAAB8),
+    ("Mansurian", Period, None, 3000.0, 0.0, Informal, 0xC1C1CC),
+    ("Kuiperian", Period, None, 1000.0, 0.0, Informal, 0xDADAE2),
+];
+
+#[rustfmt::skip]
+const MERCURY_BANKS_2017: &[UnitData] = &[
+    ("pre-Tolstojan", Period, None, 4500.0, 0.0, Informal, 0x7F7F8C),
+    ("Tolstojan", Period, None, 4000.0, 0.0, Informal, 0x9494A3),
+    ("Calorian", Period, None, 3900.0, 0.0, Informal, 0xAAAAB8),
+    ("Mansurian", Period, None, 1700.0, 0.0, Informal, 0xC1C1CC),
+    ("Kuiperian", Period, None, 280.0, 0.0, Informal, 0xDADAE2),
+];
+
+impl TimeScale {
+    /// The lunar systems, from the pre-Nectarian to the Copernican.
+    ///
+    /// ```
+    /// use geotime::{Geotime, LunarChronology, Rank, TimeScale};
+    ///
+    /// let t = Geotime::from_ma(2000.0);
+    /// let moon = TimeScale::moon(LunarChronology::Wilhelms1987);
+    /// assert_eq!(moon.unit_at(t, Rank::Period).unwrap().name(), "Eratosthenian");
+    /// assert_eq!(moon.name(), "Moon (Wilhelms 1987)");
+    /// ```
+    pub fn moon(model: LunarChronology) -> &'static TimeScale {
+        static WILHELMS: OnceLock<TimeScale> = OnceLock::new();
+        static STOFFLER_RYDER: OnceLock<TimeScale> = OnceLock::new();
+        match model {
+            LunarChronology::Wilhelms1987 => {
+                WILHELMS.get_or_init(|| Self::from_data("Moon (Wilhelms 1987)", MOON_WILHELMS_1987))
+            }
+            LunarChronology::StofflerRyder2001 => STOFFLER_RYDER.get_or_init(|| {
+                Self::from_data("Moon (Stöffler & Ryder 2001)", MOON_STOFFLER_RYDER_2001)
+            }),
+        }
+    }
+
+    /// The Martian systems, from the pre-Noachian to the Amazonian.
+    ///
+    /// ```
+    /// use geotime::{Geotime, MartianChronology, Rank, TimeScale};
+    ///
+    /// let t = Geotime::from_ma(3650.0);
+    /// let hartmann = TimeScale::mars(MartianChronology::Hartmann2005);
+    /// let neukum = TimeScale::mars(MartianChronology::Neukum2001);
+    /// assert_eq!(hartmann.unit_at(t, Rank::Epoch).unwrap().name(), "Early Hesperian");
+    /// assert_eq!(neukum.unit_at(t, Rank::Epoch).unwrap().name(), "Early Hesperian");
+    ///
+    /// let t = Geotime::from_ma(3340.0);
+    /// assert_eq!(hartmann.unit_at(t, Rank::Period).unwrap().name(), "Amazonian");
+    /// assert_eq!(neukum.unit_at(t, Rank::Period).unwrap().name(), "Hesperian");
+    /// ```
+    pub fn mars(model: MartianChronology) -> &'static TimeScale {
+        static HARTMANN: OnceLock<TimeScale> = OnceLock::new();
+        static NEUKUM: OnceLock<TimeScale> = OnceLock::new();
+        match model {
+            MartianChronology::Hartmann2005 => {
+                HARTMANN.get_or_init(|| Self::from_data("Mars (Hartmann 2005)", MARS_HARTMANN_2005))
+            }
+            MartianChronology::Neukum2001 => {
+                NEUKUM.get_or_init(|| Self::from_data("Mars (Neukum 2001)", MARS_NEUKUM_2001))
+            }
+        }
+    }
+
+    /// The Mercurian systems, from the pre-Tolstojan to the Kuiperian.
+    pub fn mercury(model: MercurianChronology) -> &'static TimeScale {
+        static SPUDIS_GUEST: OnceLock<TimeScale> = OnceLock::new();
+        static BANKS: OnceLock<TimeScale> = OnceLock::new();
+        match model {
+            MercurianChronology::SpudisGuest1988 => SPUDIS_GUEST.get_or_init(|| {
+                Self::from_data("Mercury (Spudis & Guest 1988)", MERCURY_SPUDIS_GUEST_1988)
+            }),
+            MercurianChronology::Banks2017 => BANKS
+                .get_or_init(|| Self::from_data("Mercury (Banks et al. 2017)", MERCURY_BANKS_2017)),
+        }
+    }
+
+    pub(crate) fn planetary() -> Vec<&'static TimeScale> {
+        vec![
+            Self::moon(LunarChronology::Wilhelms1987),
+            Self::moon(LunarChronology::StofflerRyder2001),
+            Self::mars(MartianChronology::Hartmann2005),
+            Self::mars(MartianChronology::Neukum2001),
+            Self::mercury(MercurianChronology::SpudisGuest1988),
+            Self::mercury(MercurianChronology::Banks2017),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Geotime;
+
+    #[test]
+    fn valid() {
+        for scale in TimeScale::planetary() {
+            assert_eq!(scale.validate(), vec![], "{}", scale.name());
+            assert!(TimeScale::named(scale.name()).is_some());
+        }
+    }
+
+    #[test]
+    fn youngest_systems_extend_to_the_present() {
+        for scale in TimeScale::planetary() {
+            let units = scale.units_at(Geotime::PRESENT);
+            assert!(!units.is_empty());
+            assert!(units.iter().all(|u| u.top().is_none()));
+        }
+    }
+
+    #[test]
+    fn model_dependence() {
+        let t = Geotime::from_ma(1000.0);
+        let wilhelms = TimeScale::moon(LunarChronology::Wilhelms1987);
+        let stoffler = TimeScale::moon(LunarChronology::StofflerRyder2001);
+        assert_eq!(wilhelms.unit_at(t, Period).unwrap().name(), "Copernican");
+        assert_eq!(stoffler.unit_at(t, Period).unwrap().name(), "Eratosthenian");
+
+        let copernican = stoffler.unit("Copernican").unwrap();
+        assert!((copernican.base().sigma_ma() - 15.0).abs() < 1e-9);
+
+        let t = Geotime::from_ma(2000.0);
+        let spudis = TimeScale::mercury(MercurianChronology::SpudisGuest1988);
+        let banks = TimeScale::mercury(MercurianChronology::Banks2017);
+        assert_eq!(spudis.unit_at(t, Period).unwrap().name(), "Mansurian");
+        assert_eq!(banks.unit_at(t, Period).unwrap().name(), "Calorian");
+    }
+
+    #[test]
+    fn remap_between_models() {
+        let hartmann = TimeScale::mars(MartianChronology::Hartmann2005);
+        let neukum = TimeScale::mars(MartianChronology::Neukum2001);
+        let t = hartmann.at_position("Middle Amazonian", 0.5).unwrap();
+        let remapped = hartmann.remap(t, neukum).unwrap();
+        let (unit, fraction) = neukum.position(remapped).unwrap();
+        assert_eq!(unit.name(), "Middle Amazonian");
+        assert!((fraction - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{Error, Geotime, Result, MILLISECONDS_IN_JULIAN_YEAR};
+
+/// A measured quantity and its one-sigma uncertainty.
+///
+/// ```
+/// use geotime::Measurement;
+///
+/// let ratio = Measurement::new(0.0104, 0.00002);
+/// assert_eq!(ratio.relative(), 0.00002 / 0.0104);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    pub value: f64,
+    pub sigma: f64,
+}
+
+impl Measurement {
+    pub const fn new(value: f64, sigma: f64) -> Self {
+        Self { value, sigma }
+    }
+
+    /// A value that is taken to be known exactly.
+    pub const fn exact(value: f64) -> Self {
+        Self { value, sigma: 0.0 }
+    }
+
+    /// The uncertainty as a fraction of the value.
+    pub fn relative(&self) -> f64 {
+        self.sigma / self.value
+    }
+}
+
+/// Propagates the uncertainties of `inputs` through `f` to first order, using central differences
+/// to estimate the partial derivatives.  The inputs are assumed to be uncorrelated.
+pub(crate) fn propagate<F>(inputs: &[Measurement], f: F) -> Measurement
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let values: Vec<f64> = inputs.iter().map(|m| m.value).collect();
+    let value = f(&values);
+
+    let mut variance = 0.0;
+    for (i, m) in inputs.iter().enumerate() {
+        if m.sigma == 0.0 {
+            continue;
+        }
+
+        let h = if m.value == 0.0 {
+            m.sigma * 1e-3
+        } else {
+            m.value.abs() * 1e-7
+        };
+
+        let mut args = values.clone();
+        args[i] = m.value + h;
+        let upper = f(&args);
+        args[i] = m.value - h;
+        let lower = f(&args);
+
+        let partial = (upper - lower) / (2.0 * h);
+        variance += (partial * m.sigma).powi(2);
+    }
+
+    Measurement::new(value, variance.sqrt())
+}
+
+/// A timestamp with a symmetric one-sigma uncertainty, in milliseconds.
+///
+/// ```
+/// use geotime::UncertainGeotime;
+///
+/// let age = UncertainGeotime::from_ma(66.0, 0.05);
+/// assert!((age.to_ma() - 66.0).abs() < 1e-9);
+/// assert!((age.sigma_ma() - 0.05).abs() < 1e-9);
+/// assert!(age.earliest(2.0) < age.time());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UncertainGeotime {
+    time: Geotime,
+    sigma: i128,
+}
+
+impl From<Geotime> for UncertainGeotime {
+    fn from(time: Geotime) -> Self {
+        Self::new(time, 0)
+    }
+}
+
+impl UncertainGeotime {
+    pub fn new(time: Geotime, sigma_millis: i128) -> Self {
+        Self {
+            time,
+            sigma: sigma_millis.saturating_abs(),
+        }
+    }
+
+    /// An age in millions of years before present, with its uncertainty in millions of years.
+    pub fn from_ma(age: f64, sigma: f64) -> Self {
+        Self::from_years_bp(age * 1e6, sigma * 1e6)
+    }
+
+    /// An age in years before present, with its uncertainty in years.
+    pub fn from_years_bp(age: f64, sigma: f64) -> Self {
+        let sigma = (sigma * MILLISECONDS_IN_JULIAN_YEAR as f64).round() as i128;
+        Self::new(Geotime::from_years_bp(age), sigma)
+    }
+
+    /// As [`UncertainGeotime::from_ma`], but an error if the age or its uncertainty is not finite
+    /// or lies beyond the range of a timestamp.
+    pub fn try_from_ma(age: f64, sigma: f64) -> Result<Self> {
+        Self::try_from_years_bp(age * 1e6, sigma * 1e6)
+    }
+
+    /// As [`UncertainGeotime::from_years_bp`], but an error if the age or its uncertainty is not
+    /// finite or lies beyond the range of a timestamp.
+    pub fn try_from_years_bp(age: f64, sigma: f64) -> Result<Self> {
+        let time = Geotime::try_from_years_bp(age)?;
+        let millis = (sigma * MILLISECONDS_IN_JULIAN_YEAR as f64).round().abs();
+        if millis.is_nan() || millis >= i128::MAX as f64 {
+            return Err(Error::OutOfRange(format!(
+                "an uncertainty of {} years is out of range",
+                sigma
+            )));
+        }
+        Ok(Self::new(time, millis as i128))
+    }
+
+    /// The central value.
+    pub fn time(&self) -> Geotime {
+        self.time
+    }
+
+    pub fn sigma_millis(&self) -> i128 {
+        self.sigma
+    }
+
+    pub fn to_ma(&self) -> f64 {
+        self.time.to_ma()
+    }
+
+    pub fn to_years_bp(&self) -> f64 {
+        self.time.to_years_bp()
+    }
+
+    pub fn sigma_ma(&self) -> f64 {
+        self.sigma_years() / 1e6
+    }
+
+    pub fn sigma_years(&self) -> f64 {
+        self.sigma as f64 / MILLISECONDS_IN_JULIAN_YEAR as f64
+    }
+
+    /// The earliest time within `k` standard deviations of the central value.
+    pub fn earliest(&self, k: f64) -> Geotime {
+        Geotime(
+            self.time
+                .0
+                .saturating_sub((self.sigma as f64 * k).round() as i128),
+        )
+    }
+
+    /// The latest time within `k` standard deviations of the central value.
+    pub fn latest(&self, k: f64) -> Geotime {
+        Geotime(
+            self.time
+                .0
+                .saturating_add((self.sigma as f64 * k).round() as i128),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn propagate_linear() {
+        let inputs = [Measurement::new(2.0, 0.3), Measurement::new(5.0, 0.4)];
+        let m = propagate(&inputs, |v| v[0] + v[1]);
+        assert_eq!(m.value, 7.0);
+        assert!((m.sigma - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn propagate_exact_inputs() {
+        let inputs = [Measurement::exact(2.0), Measurement::exact(3.0)];
+        let m = propagate(&inputs, |v| v[0] * v[1]);
+        assert_eq!(m, Measurement::exact(6.0));
+    }
+
+    #[test]
+    fn bounds() {
+        let ts = UncertainGeotime::new(Geotime::from(1000), 10);
+        assert_eq!(ts.earliest(2.0), Geotime::from(980));
+        assert_eq!(ts.latest(1.0), Geotime::from(1010));
+        assert_eq!(
+            UncertainGeotime::new(Geotime::from(0), -5).sigma_millis(),
+            5
+        );
+        let wide = UncertainGeotime::new(Geotime::from(i128::MIN), i128::MIN);
+        assert_eq!(wide.sigma_millis(), i128::MAX);
+        assert_eq!(wide.earliest(1.0), Geotime::from(i128::MIN));
+        assert_eq!(wide.latest(2.0), Geotime::from(-1));
+    }
+
+    #[test]
+    fn ages_out_of_range() {
+        let age = UncertainGeotime::try_from_ma(66.0, 0.05).unwrap();
+        assert_eq!(age, UncertainGeotime::from_ma(66.0, 0.05));
+        for (age, sigma) in [
+            (1e30, 0.0),
+            (-1e300, 0.0),
+            (f64::NAN, 0.0),
+            (1.0, f64::INFINITY),
+        ] {
+            assert!(matches!(
+                UncertainGeotime::try_from_ma(age, sigma),
+                Err(Error::OutOfRange(_))
+            ));
+        }
+
+        // The infallible constructors saturate.
+        assert_eq!(Geotime::from_ma(1e30), Geotime::from(i128::MIN));
+        assert!(Geotime::from_ma(-1e30) > Geotime::from(i128::MAX / 2));
+        assert!(Geotime::try_from_years_bp(f64::NEG_INFINITY).is_err());
+        let oldest = Geotime::from(i128::MIN).to_years_bp();
+        assert!(Geotime::try_from_years_bp(oldest * 0.999).is_ok());
+    }
+}